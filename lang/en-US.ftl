@@ -13,6 +13,10 @@ cli-unable-to-request-confirmation = Unable to request confirmation.
     .winpty-workaround = If you are using a Bash emulator (like Git Bash), try running winpty.
 cli-backup-id-with-multiple-games = Cannot specify backup ID when restoring multiple games.
 cli-invalid-backup-id = Invalid backup ID.
+cli-watching-games = Watching for changes in {$total-games} {$total-games ->
+    [one] game
+    *[other] games
+}. Press Ctrl+C to stop.
 
 badge-failed = FAILED
 badge-duplicates = DUPLICATES
@@ -20,11 +24,18 @@ badge-duplicated = DUPLICATED
 badge-ignored = IGNORED
 badge-redirected-from = FROM: {$path}
 badge-redirecting-to = TO: {$path}
+badge-owned-by = OWNER: {$game}
+badge-from-launcher = LAUNCHER
+badge-snapshot = SNAPSHOT
 
 some-entries-failed = Some entries failed to process; look for {badge-failed} in the output for details. Double check whether you can access those files or whether their paths are very long.
 
 cli-game-line-item-redirected = Redirected from: {$path}
 cli-game-line-item-redirecting = Redirecting to: {$path}
+cli-game-line-item-owned-by = Owned by: {$game}
+cli-game-line-item-steam-account = Steam account: {$account}
+cli-game-line-item-from-launcher = Found via launcher metadata
+steam-account-most-recent = most recent
 
 button-backup = Back up
 button-preview = Preview
@@ -61,10 +72,13 @@ restoration-source-is-invalid = Error: The restoration source is invalid (either
 registry-issue = Error: Some registry entries were skipped.
 unable-to-browse-file-system = Error: Unable to browse on your system.
 unable-to-open-directory = Error: Unable to open directory:
+unable-to-write-file = Error: Unable to write file:
 unable-to-open-url = Error: Unable to open URL:
 unable-to-configure-cloud = Unable to configure cloud.
 unable-to-synchronize-with-cloud = Unable to synchronize with cloud.
 cloud-synchronize-conflict = Your local and cloud backups are in conflict. Perform an upload or download to resolve this.
+operation-locked = Another Ludusavi process is backing up or restoring right now. Try again after it finishes.
+unable-to-watch-files = Unable to watch for file changes.
 
 command-unlaunched = Command did not launch: {$command}
 command-terminated = Command terminated abruptly: {$command}
@@ -102,6 +116,14 @@ field-backup-format = Format:
 field-backup-compression = Compression:
 # The compression level determines how much compresison we perform.
 field-backup-compression-level = Level:
+# This is how to handle symbolic links (shortcuts to other files or folders).
+field-symlinks = Symbolic links:
+# These are folders inside of a root that should not be scanned.
+field-root-ignored-paths = Ignored paths:
+# This is how many seconds to wait for a root (like a network drive) to respond. 0 means no limit.
+field-root-timeout = Timeout:
+# This is how many folders deep to look inside of a root. 0 means no limit.
+field-root-max-depth = Max depth:
 
 label-manifest = Manifest
 # This shows the time when we checked for an update to the manifest.
@@ -126,6 +148,8 @@ label-cloud = Cloud
 label-remote = Remote
 label-remote-name = Remote name
 label-folder = Folder
+# "Flatpak" is a proper noun: https://flatpak.org
+label-flatpak = Flatpak
 # An executable file
 label-executable = Executable
 # Options given to a command line program
@@ -154,12 +178,19 @@ label-original-name = Original name
 label-source = Source
 # This refers to the main Ludusavi manifest: https://github.com/mtkennerly/ludusavi-manifest
 label-primary-manifest = Primary manifest
+label-restored = Restored
+label-backed-up = Backed up
+# This is followed by the date and duration of the most recent play session.
+label-last-played = Last played
 
+store-bottles = Bottles
 store-ea = EA
+store-emulator = Emulator
 store-epic = Epic
 store-gog = GOG
 store-gog-galaxy = GOG Galaxy
 store-heroic = Heroic
+store-itch = itch
 store-legendary = Legendary
 store-lutris = Lutris
 store-microsoft = Microsoft
@@ -181,6 +212,12 @@ store-other-linux = Linux drive
 store-other-mac = Mac drive
 store-other = Other
 
+# These are categories of files, as labeled in the manifest.
+tag-save = Save
+tag-config = Config
+tag-screenshot = Screenshot
+tag-other = Other
+
 backup-format-simple = Simple
 backup-format-zip = Zip
 
@@ -190,6 +227,12 @@ compression-deflate = Deflate
 compression-bzip2 = Bzip2
 compression-zstd = Zstd
 
+# Back up the content that a symbolic link points to.
+symlinks-follow = Follow links
+# Back up the symbolic link itself and recreate it when restoring.
+symlinks-preserve = Keep as links
+symlinks-skip = Skip links
+
 theme = Theme
 theme-light = Light
 theme-dark = Dark
@@ -209,6 +252,15 @@ explanation-for-exclude-store-screenshots =
 explanation-for-exclude-cloud-games =
     Do not back up games with cloud support on these platforms
 
+explanation-for-exclude-tags =
+    Do not back up files with these tags in the manifest
+
+explanation-for-exclude-untagged =
+    Do not back up files without any tags in the manifest
+
+explanation-for-installed-only =
+    Only back up games detected as installed, custom games, and games with existing backups
+
 consider-doing-a-preview =
     If you haven't already, consider doing a preview first so that there
     are no surprises.
@@ -266,13 +318,33 @@ game-is-unrecognized = Ludusavi does not recognize this game.
 game-has-nothing-to-restore = This game does not have a backup to restore.
 launch-game-after-error = Launch the game anyway?
 game-did-not-launch = Game failed to launch.
+game-exited-with-code = Game exited with code {$code}.
+session-exit-code = Exit code: {$code}
 
 back-up-specific-game =
     .confirm = Back up save data for {$game}?
     .failed = Failed to back up save data for {$game}
 restore-specific-game =
     .confirm = Restore save data for {$game}?
+    .overwrite-newer-confirm =
+        Your current save data for {$game} is newer than the latest backup ({$total} {$total ->
+            [one] file
+            *[other] files
+        }).
+        Restoring would overwrite it. Restore anyway?
     .failed = Failed to restore save data for {$game}
 
+cloud-specific-game =
+    .newer-confirm =
+        The cloud has newer save data for {$game}. Download it before restoring?
+        If you choose not to, then cloud sync will be skipped for this session.
+    .diverged-confirm =
+        Your local and cloud save data for {$game} have diverged.
+        Download the cloud version and replace your local backups?
+        If you choose not to, then cloud sync will be skipped for this session.
+    .overwrite-confirm =
+        The cloud save data for {$game} changed while you were playing.
+        Upload your local save data and replace the cloud version?
+
 new-version-check = Check for application updates automatically
 new-version-available = An application update is available: {$version}. Would you like to view the release notes?
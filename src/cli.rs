@@ -1,32 +1,45 @@
 mod api;
+mod daemon;
 mod parse;
 mod report;
 mod ui;
+mod watch;
 
-use std::{collections::BTreeSet, process::Command, time::Duration};
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
 
 use clap::CommandFactory;
 use indicatif::{ParallelProgressIterator, ProgressBar};
 use rayon::{
-    iter::{IntoParallelRefIterator, ParallelIterator},
+    iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator},
     prelude::IndexedParallelIterator,
 };
 
 use crate::{
     cli::{
-        parse::{Cli, CompletionShell, ManifestSubcommand, Subcommand},
-        report::{report_cloud_changes, Reporter},
+        parse::{Cli, CompletionShell, DaemonSubcommand, ManifestSubcommand, RootsSubcommand, Subcommand},
+        report::{report_cloud_changes, report_wrap, Reporter},
     },
-    cloud::{CloudChange, Rclone, Remote},
+    cloud::{CloudChange, CloudRelation, Rclone, Remote},
     lang::{Language, TRANSLATOR},
     prelude::{
         app_dir, get_threads_from_env, initialize_rayon, register_sigint, unregister_sigint, Error, Finality,
-        StrictPath, SyncDirection,
+        OperationLock, StrictPath, SyncDirection,
+    },
+    resource::{
+        cache::Cache,
+        config::{Config, Root, WrapPolicy},
+        manifest::{Manifest, Store},
+        sessions::{Session, Sessions},
+        ResourceFile, SaveableResourceFile,
     },
-    resource::{cache::Cache, config::Config, manifest::Manifest, ResourceFile, SaveableResourceFile},
     scan::{
-        layout::BackupLayout, prepare_backup_target, scan_game_for_backup, BackupId, DuplicateDetector, Launchers,
-        OperationStepDecision, SteamShortcuts, TitleFinder, TitleQuery,
+        layout::{BackupLayout, IndividualMapping},
+        prepare_backup_target, scan_game_for_backup, BackupId, DuplicateClaim, DuplicateDetector, Launchers,
+        OperationStepDecision, SteamAccounts, SteamShortcuts, TitleFinder, TitleQuery,
     },
     wrap,
 };
@@ -142,6 +155,11 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             differential_limit,
             cloud_sync,
             no_cloud_sync,
+            tags,
+            exclude_tags,
+            untagged,
+            installed_only,
+            symlinks,
             games,
         } => {
             let games = parse_games(games);
@@ -155,6 +173,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 Some(p) => p,
             };
             let roots = config.expanded_roots();
+            let steam_accounts = SteamAccounts::discover(&roots);
 
             if !preview && !force {
                 match dialoguer::Confirm::new()
@@ -167,6 +186,12 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            let _lock = if preview {
+                None
+            } else {
+                Some(OperationLock::try_acquire().ok_or(Error::OperationLocked)?)
+            };
+
             if !preview {
                 prepare_backup_target(&backup_dir)?;
             }
@@ -193,7 +218,22 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             };
 
             let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
-            let filter = config.backup.filter.clone();
+            let mut filter = config.backup.filter.clone();
+            if !tags.is_empty() {
+                filter.tags.include = tags.into_iter().collect();
+            }
+            if !exclude_tags.is_empty() {
+                filter.tags.exclude = exclude_tags.into_iter().collect();
+            }
+            if let Some(untagged) = untagged {
+                filter.tags.untagged = untagged;
+            }
+            if installed_only {
+                filter.installed_only = true;
+            }
+            if let Some(symlinks) = symlinks {
+                filter.symlinks = symlinks;
+            }
             let toggled_paths = config.backup.toggled_paths.clone();
             let toggled_registry = config.backup.toggled_registry.clone();
             let steam_shortcuts = SteamShortcuts::scan();
@@ -231,7 +271,8 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             log::info!("beginning backup with {} steps", games.len());
 
-            let mut info: Vec<_> = games
+            let root_filters = Root::path_filters(&roots);
+            let scanned: Vec<_> = games
                 .par_iter()
                 .enumerate()
                 .progress_with(scan_progress_bar(games.len() as u64))
@@ -245,11 +286,21 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         log::trace!("[{name}] excluded by backup filter");
                         return None;
                     }
+                    if filter.excludes_uninstalled(
+                        games_specified,
+                        previous.is_some(),
+                        config.is_game_customized(name),
+                        launchers.is_detected(name),
+                    ) {
+                        log::trace!("[{name}] excluded because it is not installed");
+                        return None;
+                    }
 
                     let scan_info = scan_game_for_backup(
                         game,
                         name,
                         &roots,
+                        &root_filters,
                         &app_dir(),
                         &launchers,
                         &filter,
@@ -260,6 +311,33 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         &config.redirects,
                         &steam_shortcuts,
                     );
+                    log::trace!("step {i} completed");
+                    Some((name, scan_info))
+                })
+                .collect();
+            log::info!("completed scan");
+
+            // Ownership of duplicated files has to be decided before anything is backed up.
+            duplicate_detector.set_rules(config.backup.duplicates.clone());
+            for (name, scan_info) in &scanned {
+                if !scan_info.can_report_game() {
+                    continue;
+                }
+                duplicate_detector.add_claim(
+                    name,
+                    DuplicateClaim {
+                        detected: launchers.is_detected(name),
+                        manifest_paths: manifest.0[*name].files.keys().cloned().collect(),
+                    },
+                );
+                duplicate_detector.add_game(scan_info, config.is_game_enabled_for_operation(name, false));
+            }
+
+            let mut info: Vec<_> = scanned
+                .into_par_iter()
+                .filter_map(|(name, mut scan_info)| {
+                    duplicate_detector.apply_ownership(&mut scan_info);
+
                     let ignored = !&config.is_game_enabled_for_backup(name) && !games_specified;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
@@ -286,7 +364,6 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                             .game_layout(name)
                             .back_up(&scan_info, &chrono::Utc::now(), &backup_format)
                     };
-                    log::trace!("step {i} completed");
                     if !scan_info.can_report_game() {
                         None
                     } else {
@@ -318,13 +395,6 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
-            for (_, scan_info, _, _) in info.iter() {
-                duplicate_detector.add_game(
-                    scan_info,
-                    config.is_game_enabled_for_operation(&scan_info.game_name, false),
-                );
-            }
-
             let sort = sort.map(From::from).unwrap_or_else(|| config.backup.sort.clone());
             info.sort_by(
                 |(name1, scan_info1, backup_info1, ..), (name2, scan_info2, backup_info2, ..)| {
@@ -344,7 +414,14 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             }
 
             for (name, scan_info, backup_info, decision) in info {
-                if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
+                if !reporter.add_game(
+                    name,
+                    &scan_info,
+                    &backup_info,
+                    &decision,
+                    &duplicate_detector,
+                    &steam_accounts,
+                ) {
                     failed = true;
                 }
             }
@@ -381,7 +458,15 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            let _lock = if preview {
+                None
+            } else {
+                Some(OperationLock::try_acquire().ok_or(Error::OperationLocked)?)
+            };
+
             let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+            let roots = config.expanded_roots();
+            let steam_accounts = SteamAccounts::discover(&roots);
 
             if backup.is_some() && games.len() != 1 {
                 return Err(Error::CliBackupIdWithMultipleGames);
@@ -431,6 +516,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             log::info!("beginning restore with {} steps", games.len());
 
+            let users = config.users.restorable();
             let mut info: Vec<_> = games
                 .par_iter()
                 .enumerate()
@@ -438,13 +524,15 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 .filter_map(|(i, name)| {
                     log::trace!("step {i} / {}: {name}", games.len());
                     let mut layout = layout.game_layout(name);
-                    let scan_info = layout.scan_for_restoration(
+                    let mut scan_info = layout.scan_for_restoration(
                         name,
                         backup_id.as_ref().unwrap_or(&BackupId::Latest),
                         &config.redirects,
                         &config.restore.toggled_paths,
                         &config.restore.toggled_registry,
                     );
+                    scan_info.ignore_unselected_steam_accounts(&roots);
+                    scan_info.ignore_other_users(&users);
                     let ignored = !&config.is_game_enabled_for_restore(name) && !games_specified;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
@@ -513,7 +601,14 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             }
 
             for (name, scan_info, backup_info, decision, _) in info {
-                if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
+                if !reporter.add_game(
+                    name,
+                    &scan_info,
+                    &backup_info,
+                    &decision,
+                    &duplicate_detector,
+                    &steam_accounts,
+                ) {
                     failed = true;
                 }
             }
@@ -575,6 +670,80 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             }
             reporter.print(&restore_dir);
         }
+        Subcommand::Sessions { api, games } => {
+            let games = parse_games(games);
+
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let sessions = Sessions::load().unwrap_or_else(|e| {
+                log::error!("Unable to load sessions: {:?}", e);
+                Sessions::default()
+            });
+            let layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(sessions.games.keys().cloned().collect(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => {
+                    reporter.trip_unknown_games(games.clone());
+                    reporter.print_failure();
+                    return Err(Error::CliUnrecognizedGames { games });
+                }
+            };
+
+            for name in games {
+                if let Some(played) = sessions.games.get(&name) {
+                    reporter.add_sessions(&name, config.display_name(&name), played);
+                }
+            }
+            reporter.print(&config.restore.path);
+        }
+        Subcommand::Installed { api, games } => {
+            let games = parse_games(games);
+
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+            let roots = config.expanded_roots();
+            let layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(manifest.primary_titles(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => {
+                    reporter.trip_unknown_games(games.clone());
+                    reporter.print_failure();
+                    return Err(Error::CliUnrecognizedGames { games });
+                }
+            };
+
+            let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+            let games: HashSet<_> = games.iter().collect();
+            for (name, installations) in launchers.installed() {
+                if !games.contains(name) {
+                    continue;
+                }
+                reporter.add_installed(name, config.display_name(name), &installations);
+            }
+            reporter.print(&config.restore.path);
+        }
+        Subcommand::SteamAccounts { api } => {
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let mut roots = config.expanded_roots();
+            if !roots.iter().any(|root| root.store() == Store::Steam) {
+                if let Ok(steam) = steamlocate::SteamDir::locate() {
+                    roots.push(Root::new(StrictPath::from(steam.path()), Store::Steam));
+                }
+            }
+
+            reporter.add_steam_accounts(&SteamAccounts::discover(&roots));
+            reporter.print(&config.restore.path);
+        }
         Subcommand::Find {
             api,
             path,
@@ -583,6 +752,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             steam_id,
             gog_id,
             lutris_id,
+            flatpak_id,
             normalized,
             disabled,
             partial,
@@ -607,6 +777,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 steam_id,
                 gog_id,
                 lutris_id,
+                flatpak_id,
                 normalized,
                 backup,
                 restore,
@@ -630,6 +801,48 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             reporter.print(&restore_dir);
         }
+        Subcommand::Daemon { sub: daemon_sub } => match daemon_sub {
+            None => daemon::run_daemon(&config, no_manifest_update, try_manifest_update)?,
+            Some(DaemonSubcommand::Systemd { on_calendar, install }) => {
+                daemon::generate_systemd(on_calendar.as_deref(), install)?;
+            }
+        },
+        Subcommand::Watch { debounce, games } => {
+            watch::run_watch(
+                parse_games(games),
+                Duration::from_secs(debounce),
+                no_manifest_update,
+                try_manifest_update,
+            )?;
+        }
+        Subcommand::Roots { sub: roots_sub } => match roots_sub {
+            RootsSubcommand::Discover { add, api } => {
+                let roots = config.find_missing_roots();
+
+                if add && !roots.is_empty() {
+                    for root in &roots {
+                        if config.merge_root(root).is_none() {
+                            config.roots.push(root.clone());
+                        }
+                    }
+                    config.save();
+                }
+
+                if api {
+                    #[derive(serde::Serialize)]
+                    struct Output<'a> {
+                        roots: &'a [Root],
+                    }
+                    println!("{}", serde_json::to_string_pretty(&Output { roots: &roots }).unwrap());
+                } else if roots.is_empty() {
+                    println!("{}", TRANSLATOR.no_missing_roots());
+                } else {
+                    for root in &roots {
+                        println!("[{}] {}", TRANSLATOR.store(&root.store()), root.path().render());
+                    }
+                }
+            }
+        },
         Subcommand::Manifest { sub: manifest_sub } => match manifest_sub {
             ManifestSubcommand::Show { api } => {
                 let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
@@ -824,6 +1037,16 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             name_source,
             force,
             gui,
+            wait_for,
+            wait_children,
+            always_game_exit_code,
+            snapshot_interval,
+            cloud_sync,
+            no_cloud_sync,
+            restore,
+            backup,
+            launch_on_error,
+            api,
             commands,
         } => {
             let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
@@ -838,10 +1061,36 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 })
             } else if let Some(infer) = name_source.infer {
                 let roots = config.expanded_roots();
-                match infer {
+                let infer_one = |launcher| match launcher {
+                    parse::Launcher::Auto => None,
+                    parse::Launcher::Bottles => wrap::bottles::infer_game_from_bottles(&roots, &commands),
+                    parse::Launcher::Gog => wrap::gog::infer_game_from_gog(&commands),
                     parse::Launcher::Heroic => wrap::heroic::infer_game_from_heroic(&roots),
+                    parse::Launcher::Itch => wrap::itch::infer_game_from_itch(&roots, &commands),
+                    parse::Launcher::Legendary => wrap::legendary::infer_game_from_legendary(&roots, &commands),
                     parse::Launcher::Lutris => wrap::lutris::infer(),
-                    parse::Launcher::Steam => wrap::infer_game_from_steam(),
+                    parse::Launcher::Steam => wrap::infer_game_from_steam(&SteamShortcuts::scan()),
+                };
+                match infer {
+                    // Steam goes last, since other launchers may themselves be launched from Steam.
+                    parse::Launcher::Auto => [
+                        parse::Launcher::Heroic,
+                        parse::Launcher::Lutris,
+                        parse::Launcher::Legendary,
+                        parse::Launcher::Gog,
+                        parse::Launcher::Bottles,
+                        parse::Launcher::Itch,
+                        parse::Launcher::Steam,
+                    ]
+                    .into_iter()
+                    .find_map(|launcher| {
+                        let info = infer_one(launcher);
+                        if info.is_some() {
+                            log::debug!("Inferred game via launcher: {:?}", launcher);
+                        }
+                        info
+                    }),
+                    launcher => infer_one(launcher),
                 }
             } else {
                 unreachable!();
@@ -864,6 +1113,24 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             });
             log::debug!("Title finder result: {:?}", &game_name);
 
+            // If the game is unrecognized, then we still check the name that we were given,
+            // so that users can configure a policy for it.
+            let mut policies = game_name
+                .clone()
+                .or_else(|| wrap_game_info.as_ref().and_then(|info| info.name.clone()))
+                .map(|game| config.wrap.policies(&game))
+                .unwrap_or_default();
+            if let Some(restore) = restore {
+                policies.restore = restore;
+            }
+            if let Some(backup) = backup {
+                policies.backup = backup;
+            }
+            if let Some(launch_on_error) = launch_on_error {
+                policies.launch_on_error = launch_on_error;
+            }
+            log::debug!("Wrap policies: {:?}", &policies);
+
             match game_name.as_ref() {
                 Some(game_name) => {
                     wrap::lutris::save_normalized_title(game_name.clone());
@@ -871,7 +1138,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 None => {
                     if !ui::confirm_with_question(
                         gui,
-                        force.then_some(true),
+                        policies.launch_on_error.decide(force.then_some(true)),
                         &TRANSLATOR.game_is_unrecognized(),
                         &TRANSLATOR.launch_game_after_error(),
                     )? {
@@ -880,18 +1147,111 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            // Hold the lock until we're done, so that the daemon doesn't back up the game in the middle of a session.
+            // The nested restore and backup reuse it since they run on the same thread.
+            let (game_name, _lock) = match game_name {
+                Some(game_name) => match OperationLock::try_acquire() {
+                    Some(lock) => (Some(game_name), Some(lock)),
+                    None => {
+                        if !ui::confirm_with_question(
+                            gui,
+                            policies.launch_on_error.decide(force.then_some(true)),
+                            &TRANSLATOR.operation_locked(),
+                            &TRANSLATOR.launch_game_after_error(),
+                        )? {
+                            return Ok(());
+                        }
+                        (None, None)
+                    }
+                },
+                None => (None, None),
+            };
+
+            // Cloud download
+            let cloud_sync = negatable_flag(
+                cloud_sync,
+                no_cloud_sync,
+                config.cloud.synchronize && crate::cloud::validate_cloud_config(&config, &config.cloud.path).is_ok(),
+            );
+            let mut should_sync_cloud_after = false;
+            'cloud_download: {
+                let Some(game_name) = game_name.as_ref() else {
+                    break 'cloud_download;
+                };
+                if !cloud_sync {
+                    break 'cloud_download;
+                }
+
+                let relation = match compare_cloud_backups(&config, &config.restore.path, game_name) {
+                    Ok(relation) => relation,
+                    Err(err) => {
+                        log::error!("WRAP::cloud_download: failed for game {:?} with: {:?}", game_name, err);
+                        ui::alert_with_error(gui, &TRANSLATOR.unable_to_synchronize_with_cloud(), &err)?;
+                        break 'cloud_download;
+                    }
+                };
+                log::debug!(
+                    "WRAP::cloud_download: cloud relation for {:?}: {:?}",
+                    game_name,
+                    relation
+                );
+
+                let download = match relation {
+                    CloudRelation::Same | CloudRelation::LocalNewer => false,
+                    CloudRelation::CloudNewer => ui::confirm(
+                        gui,
+                        force.then_some(true),
+                        &TRANSLATOR.cloud_game_newer_confirm(game_name),
+                    )?,
+                    // Replacing local backups is destructive, so we don't assume that with `--force`.
+                    CloudRelation::Diverged => ui::confirm(
+                        gui,
+                        force.then_some(false),
+                        &TRANSLATOR.cloud_game_diverged_confirm(game_name),
+                    )?,
+                };
+
+                if download {
+                    if let Err(err) = sync_cloud(
+                        &config,
+                        &config.restore.path,
+                        &config.cloud.path,
+                        SyncDirection::Download,
+                        Finality::Final,
+                        std::slice::from_ref(game_name),
+                    ) {
+                        log::error!("WRAP::cloud_download: failed for game {:?} with: {:?}", game_name, err);
+                        ui::alert_with_error(gui, &TRANSLATOR.unable_to_synchronize_with_cloud(), &err)?;
+                        break 'cloud_download;
+                    }
+                } else if relation != CloudRelation::Same && relation != CloudRelation::LocalNewer {
+                    log::info!(
+                        "WRAP::cloud_download: skipping cloud sync for this session of {:?}",
+                        game_name
+                    );
+                    break 'cloud_download;
+                }
+
+                should_sync_cloud_after = true;
+            }
+
             // Restore
-            // TODO: Detect if saves are unchanged and skip the question?
+            let mut restored = false;
+            let mut restore_output = None;
             'restore: {
                 let Some(game_name) = game_name.as_ref() else {
                     break 'restore;
                 };
+                if policies.restore == WrapPolicy::Never {
+                    log::info!("WRAP::restore: skipping by policy for game {:?}", game_name);
+                    break 'restore;
+                }
 
-                let game_layout = layout.game_layout(game_name);
+                let mut game_layout = layout.game_layout(game_name);
                 if !game_layout.has_backups() {
                     if ui::confirm_with_question(
                         gui,
-                        force.then_some(true),
+                        policies.launch_on_error.decide(force.then_some(true)),
                         &TRANSLATOR.game_has_nothing_to_restore(),
                         &TRANSLATOR.launch_game_after_error(),
                     )? {
@@ -901,88 +1261,256 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     }
                 }
 
-                if !ui::confirm(
-                    gui,
-                    force.then_some(true),
-                    &TRANSLATOR.restore_one_game_confirm(game_name),
-                )? {
+                let mut scan_info = game_layout.scan_for_restoration(
+                    game_name,
+                    &BackupId::Latest,
+                    &config.redirects,
+                    &config.restore.toggled_paths,
+                    &config.restore.toggled_registry,
+                );
+                scan_info.ignore_unselected_steam_accounts(&config.expanded_roots());
+                scan_info.ignore_other_users(&config.users.restorable());
+
+                if !scan_info.found_anything_processable() {
+                    log::info!("WRAP::restore: saves are unchanged for game {:?}", game_name);
                     break 'restore;
                 }
 
-                if let Err(err) = run(
-                    Subcommand::Restore {
-                        games: vec![game_name.clone()],
-                        force: true,
-                        preview: Default::default(),
-                        path: Default::default(),
-                        api: Default::default(),
-                        sort: Default::default(),
-                        backup: Default::default(),
-                        cloud_sync: Default::default(),
-                        no_cloud_sync: Default::default(),
-                    },
-                    no_manifest_update,
-                    try_manifest_update,
-                ) {
+                let newer = scan_info.files_newer_than_backup();
+                let confirmed = if newer.is_empty() {
+                    ui::confirm(
+                        gui,
+                        policies.restore.decide(force.then_some(true)),
+                        &TRANSLATOR.restore_one_game_confirm(game_name),
+                    )?
+                } else {
+                    log::warn!(
+                        "WRAP::restore: current saves are newer than the backup for game {:?}: {:?}",
+                        game_name,
+                        newer
+                    );
+                    // Even with `--force` or an `always` policy,
+                    // we don't want to silently overwrite newer saves.
+                    ui::confirm(
+                        gui,
+                        policies.restore.decide_overwrite(force.then_some(false)),
+                        &TRANSLATOR.restore_one_game_overwrite_newer_confirm(game_name, newer.len()),
+                    )?
+                };
+                if !confirmed {
+                    break 'restore;
+                }
+
+                let (result, output) = report::capture_json(api, || {
+                    run(
+                        Subcommand::Restore {
+                            games: vec![game_name.clone()],
+                            force: true,
+                            preview: Default::default(),
+                            path: Default::default(),
+                            api,
+                            sort: Default::default(),
+                            backup: Default::default(),
+                            cloud_sync: Default::default(),
+                            // Cloud sync is handled separately above.
+                            no_cloud_sync: true,
+                        },
+                        no_manifest_update,
+                        try_manifest_update,
+                    )
+                });
+                restore_output = output;
+                if let Err(err) = result {
                     log::error!("WRAP::restore: failed for game {:?} with: {:?}", wrap_game_info, err);
+                    if let Some(output) = restore_output.as_ref() {
+                        report::print_json(output);
+                    }
                     ui::alert_with_error(gui, &TRANSLATOR.restore_one_game_failed(game_name), &err)?;
                     return Err(err);
                 }
+                restored = true;
             }
 
             // Launch game
-            // TODO: Legendary returns immediately. Can we handle that?
-            let result = Command::new(&commands[0]).args(&commands[1..]).status();
-            match result {
-                Ok(status) => {
-                    log::debug!("WRAP::execute: Game command executed, returning status: {:#?}", status);
+            let snapshot_interval = snapshot_interval
+                .or(config.wrap.snapshots.interval)
+                .filter(|minutes| *minutes > 0)
+                .map(|minutes| Duration::from_secs(minutes as u64 * 60));
+            let game_running = AtomicBool::new(true);
+            let started = chrono::Utc::now();
+            let result = std::thread::scope(|scope| {
+                if let (Some(game_name), Some(interval)) = (game_name.as_ref(), snapshot_interval) {
+                    let (config, manifest, game_running) = (&config, &manifest, &game_running);
+                    scope.spawn(move || take_snapshots(config, manifest, game_name, interval, game_running));
+                }
+
+                let result = wrap::process::launch(
+                    &commands,
+                    &wrap::process::WaitOptions {
+                        children: wait_children,
+                        pattern: wait_for,
+                    },
+                );
+                game_running.store(false, Ordering::Relaxed);
+                result
+            });
+            let ended = chrono::Utc::now();
+            let outcome = match result {
+                Ok(outcome) => {
+                    if outcome.status.success() {
+                        log::info!("WRAP::execute: Game command exited with status: {:?}", outcome);
+                    } else {
+                        log::warn!("WRAP::execute: Game command exited with status: {:?}", outcome);
+                    }
+                    outcome
                 }
                 Err(err) => {
                     log::error!("WRAP::execute: Game command execution failed with: {:#?}", err);
                     ui::alert_with_raw_error(gui, &TRANSLATOR.game_did_not_launch(), &err.to_string())?;
                     return Err(Error::GameDidNotLaunch { why: err.to_string() });
                 }
-            }
+            };
 
             // Backup
-            'backup: {
+            let mut new_backup = None;
+            let mut backup_output = None;
+            let backed_up = 'backup: {
                 let Some(game_name) = game_name.as_ref() else {
-                    break 'backup;
+                    break 'backup Ok(false);
                 };
 
                 if !ui::confirm(
                     gui,
-                    force.then_some(true),
+                    policies.backup.decide(force.then_some(true)),
                     &TRANSLATOR.back_up_one_game_confirm(game_name),
                 )? {
-                    break 'backup;
+                    break 'backup Ok(false);
                 }
 
-                if let Err(err) = run(
-                    Subcommand::Backup {
-                        games: vec![game_name.clone()],
-                        force: true,
-                        preview: Default::default(),
-                        path: Default::default(),
-                        wine_prefix: Default::default(),
-                        api: Default::default(),
-                        sort: Default::default(),
-                        format: Default::default(),
-                        compression: Default::default(),
-                        compression_level: Default::default(),
-                        full_limit: Default::default(),
-                        differential_limit: Default::default(),
-                        cloud_sync: Default::default(),
-                        no_cloud_sync: Default::default(),
-                    },
-                    no_manifest_update,
-                    try_manifest_update,
-                ) {
+                let previous_backup = latest_backup_id(&config, game_name);
+
+                let (result, output) = report::capture_json(api, || {
+                    run(
+                        Subcommand::Backup {
+                            games: vec![game_name.clone()],
+                            force: true,
+                            preview: Default::default(),
+                            path: Default::default(),
+                            wine_prefix: Default::default(),
+                            api,
+                            sort: Default::default(),
+                            format: Default::default(),
+                            compression: Default::default(),
+                            compression_level: Default::default(),
+                            full_limit: Default::default(),
+                            differential_limit: Default::default(),
+                            cloud_sync: Default::default(),
+                            // Cloud sync is handled separately below.
+                            no_cloud_sync: true,
+                            tags: Default::default(),
+                            exclude_tags: Default::default(),
+                            untagged: Default::default(),
+                            installed_only: Default::default(),
+                            symlinks: Default::default(),
+                        },
+                        no_manifest_update,
+                        try_manifest_update,
+                    )
+                });
+                backup_output = output;
+                if let Err(err) = result {
                     log::error!("WRAP::backup: failed with: {:#?}", err);
                     ui::alert_with_error(gui, &TRANSLATOR.back_up_one_game_failed(game_name), &err)?;
+                    break 'backup Err(err);
+                }
+
+                let latest_backup = latest_backup_id(&config, game_name);
+                if latest_backup != previous_backup {
+                    new_backup = latest_backup.map(|(name, _)| name);
+                }
+
+                Ok(true)
+            };
+
+            if let (Some(game_name), Some(_)) = (game_name.as_ref(), snapshot_interval) {
+                BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone())
+                    .game_layout(game_name)
+                    .forget_excess_snapshots(config.wrap.snapshots.retention);
+            }
+
+            // Cloud upload
+            'cloud_upload: {
+                let Some(game_name) = game_name.as_ref() else {
+                    break 'cloud_upload;
+                };
+                if !should_sync_cloud_after || backed_up.is_err() {
+                    break 'cloud_upload;
+                }
+
+                let relation = match compare_cloud_backups(&config, &config.backup.path, game_name) {
+                    Ok(relation) => relation,
+                    Err(err) => {
+                        log::error!("WRAP::cloud_upload: failed for game {:?} with: {:?}", game_name, err);
+                        ui::alert_with_error(gui, &TRANSLATOR.unable_to_synchronize_with_cloud(), &err)?;
+                        break 'cloud_upload;
+                    }
+                };
+                log::debug!("WRAP::cloud_upload: cloud relation for {:?}: {:?}", game_name, relation);
+
+                let upload = match relation {
+                    CloudRelation::Same | CloudRelation::LocalNewer => true,
+                    // Someone else uploaded while the game was running.
+                    CloudRelation::CloudNewer | CloudRelation::Diverged => ui::confirm(
+                        gui,
+                        policies.backup.decide_overwrite(force.then_some(false)),
+                        &TRANSLATOR.cloud_game_overwrite_confirm(game_name),
+                    )?,
+                };
+                if !upload {
+                    break 'cloud_upload;
+                }
+
+                if let Err(err) = sync_cloud(
+                    &config,
+                    &config.backup.path,
+                    &config.cloud.path,
+                    SyncDirection::Upload,
+                    Finality::Final,
+                    std::slice::from_ref(game_name),
+                ) {
+                    log::error!("WRAP::cloud_upload: failed for game {:?} with: {:?}", game_name, err);
+                    ui::alert_with_error(gui, &TRANSLATOR.unable_to_synchronize_with_cloud(), &err)?;
+                }
+            }
+
+            if let Some(game_name) = game_name.as_ref() {
+                Sessions::record(
+                    game_name,
+                    Session {
+                        start: started,
+                        end: ended,
+                        exit_code: outcome.exit_code(),
+                        restored,
+                        backed_up: backed_up.as_ref().is_ok_and(|x| *x),
+                        backup: new_backup,
+                    },
+                );
+            }
+
+            if api {
+                report_wrap(game_name.as_deref(), &outcome, restore_output, backup_output);
+            }
+
+            if let Err(err) = backed_up {
+                if !always_game_exit_code {
                     return Err(err);
                 }
             }
+
+            let code = outcome.exit_code();
+            if code != 0 {
+                return Err(Error::WrappedGameFailed { code });
+            }
         }
         Subcommand::Api { input } => {
             TRANSLATOR.set_language(Language::English);
@@ -1027,6 +1555,15 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
     }
 }
 
+/// Identify the latest backup by its name and time,
+/// since a simple full backup reuses the same name when it is replaced.
+fn latest_backup_id(config: &Config, game: &str) -> Option<(String, chrono::DateTime<chrono::Utc>)> {
+    BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone())
+        .try_game_layout(game)?
+        .find_by_id_flattened(&BackupId::Latest)
+        .map(|backup| (backup.name().to_string(), *backup.when()))
+}
+
 fn configure_cloud(config: &mut Config, remote: Remote) -> Result<(), Error> {
     if let Some(old_remote) = config.cloud.remote.as_ref() {
         _ = Rclone::new(config.apps.rclone.clone(), old_remote.clone()).unconfigure_remote();
@@ -1079,6 +1616,103 @@ fn cloud_progress_bar() -> ProgressBar {
     bar
 }
 
+/// Periodically back up a wrapped game until it stops running.
+fn take_snapshots(config: &Config, manifest: &Manifest, game: &str, interval: Duration, running: &AtomicBool) {
+    let Some(entry) = manifest.0.get(game) else {
+        return;
+    };
+
+    // These don't change while the game is running, so we only need to look them up once.
+    let roots = config.expanded_roots();
+    let title_finder = TitleFinder::new(
+        config,
+        manifest,
+        BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone()).restorable_game_set(),
+    );
+    let launchers = Launchers::scan(&roots, manifest, &[game.to_string()], &title_finder, None);
+    let steam_shortcuts = SteamShortcuts::scan();
+
+    let mut last = Instant::now();
+
+    while running.load(Ordering::Relaxed) {
+        std::thread::sleep(Duration::from_secs(1));
+        if last.elapsed() < interval {
+            continue;
+        }
+        last = Instant::now();
+
+        let layout = BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone());
+
+        let scan_info = scan_game_for_backup(
+            entry,
+            game,
+            &roots,
+            &Root::path_filters(&roots),
+            &app_dir(),
+            &launchers,
+            &config.backup.filter,
+            &None,
+            &config.backup.toggled_paths,
+            &config.backup.toggled_registry,
+            layout.latest_backup(game, false, &config.redirects, &config.restore.toggled_paths),
+            &config.redirects,
+            &steam_shortcuts,
+        );
+
+        match layout
+            .game_layout(game)
+            .back_up_snapshot(&scan_info, &chrono::Utc::now(), &config.backup.format)
+        {
+            Some(backup_info) if backup_info.successful() => {
+                log::info!("WRAP::snapshot: created snapshot for {:?}", game);
+            }
+            Some(backup_info) => {
+                log::error!("WRAP::snapshot: failed for {:?}: {:?}", game, backup_info.failed_files);
+            }
+            None => {
+                log::debug!("WRAP::snapshot: no changes for {:?}", game);
+            }
+        }
+    }
+}
+
+/// Check how a single game's local backups compare to the cloud.
+fn compare_cloud_backups(config: &Config, local: &StrictPath, game: &str) -> Result<CloudRelation, Error> {
+    let changes = sync_cloud(
+        config,
+        local,
+        &config.cloud.path,
+        SyncDirection::Download,
+        Finality::Preview,
+        &[game.to_string()],
+    )?;
+    if changes.is_empty() {
+        return Ok(CloudRelation::Same);
+    }
+
+    let layout = BackupLayout::new(local.clone(), config.backup.retention.clone());
+    let game_layout = layout.game_layout(game);
+
+    // We've just confirmed that the cloud is reachable,
+    // so if we can't read the mapping file, then the cloud doesn't have a usable backup.
+    let remote = crate::cloud::validate_cloud_config(config, &config.cloud.path)?;
+    let rclone = Rclone::new(config.apps.rclone.clone(), remote);
+    let cloud_mapping = game_layout
+        .path
+        .leaf()
+        .and_then(|folder| {
+            rclone
+                .cat(&format!("{}/{}/mapping.yaml", config.cloud.path, folder))
+                .ok()
+        })
+        .and_then(|content| IndividualMapping::load_from_string(&content).ok());
+
+    Ok(CloudRelation::evaluate(
+        Some(game_layout.mapping()),
+        cloud_mapping.as_ref(),
+    ))
+}
+
 fn sync_cloud(
     config: &Config,
     local: &StrictPath,
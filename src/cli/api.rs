@@ -81,6 +81,8 @@ pub mod request {
         pub gog_id: Option<u64>,
         /// Look up game by a Lutris slug.
         pub lutris_id: Option<String>,
+        /// Look up game by a Flatpak ID.
+        pub flatpak_id: Option<String>,
         /// Look up game by an approximation of the title.
         /// Ignores capitalization, "edition" suffixes, year suffixes, and some special symbols.
         /// This may find multiple games for a single input.
@@ -190,6 +192,7 @@ pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> R
                 steam_id,
                 gog_id,
                 lutris_id,
+                flatpak_id,
                 normalized,
                 disabled,
                 partial,
@@ -200,6 +203,7 @@ pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> R
                     steam_id,
                     gog_id,
                     lutris_id,
+                    flatpak_id,
                     normalized,
                     backup,
                     restore,
@@ -0,0 +1,514 @@
+use std::{str::FromStr, time::Duration};
+
+use chrono::{DateTime, Local};
+
+use crate::{
+    cli::{parse::Subcommand, run},
+    prelude::{app_dir, Error, OperationLock, StrictPath},
+    resource::config::{Config, DaemonConfig, DaemonRule},
+};
+
+/// How long to wait before trying again when another process holds the lock.
+const LOCK_RETRY: Duration = Duration::from_secs(60);
+
+/// Longest time to sleep at once, so that we notice if the system clock changes
+/// (e.g., after waking from suspend).
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+const SERVICE_NAME: &str = "ludusavi-daemon.service";
+const BACKUP_SERVICE_NAME: &str = "ludusavi-backup.service";
+const BACKUP_TIMER_NAME: &str = "ludusavi-backup.timer";
+
+#[derive(Clone, Debug)]
+pub enum Schedule {
+    Cron(Box<cron::Schedule>),
+    Interval(chrono::Duration),
+}
+
+impl Schedule {
+    pub fn parse(rule: &DaemonRule) -> Result<Self, String> {
+        match (&rule.cron, rule.interval) {
+            (Some(expression), None) => cron::Schedule::from_str(&normalize_cron(expression))
+                .map(|schedule| Self::Cron(Box::new(schedule)))
+                .map_err(|e| format!("invalid cron expression '{}': {}", expression, e)),
+            (None, Some(0)) => Err("interval must be at least 1 minute".to_string()),
+            (None, Some(minutes)) => chrono::Duration::from_std(Duration::from_secs(minutes as u64 * 60))
+                .map(Self::Interval)
+                .map_err(|e| format!("invalid interval '{}': {}", minutes, e)),
+            (Some(_), Some(_)) => Err("rule cannot have both `cron` and `interval`".to_string()),
+            (None, None) => Err("rule must have either `cron` or `interval`".to_string()),
+        }
+    }
+
+    pub fn next_after(&self, time: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Self::Cron(schedule) => schedule.after(&time).next(),
+            Self::Interval(interval) => Some(time + *interval),
+        }
+    }
+}
+
+/// Standard cron expressions have 5 fields and number the days of the week from 0 (Sunday),
+/// but the `cron` crate also expects a seconds field and numbers the days from 1 (Sunday).
+/// We convert standard expressions and leave anything else as-is.
+fn normalize_cron(expression: &str) -> String {
+    let fields: Vec<_> = expression.split_whitespace().collect();
+    if fields.len() != 5 {
+        return fields.join(" ");
+    }
+
+    let weekdays = fields[4]
+        .split(',')
+        .map(|part| {
+            let (base, step) = match part.split_once('/') {
+                Some((base, step)) => (base, Some(step)),
+                None => (part, None),
+            };
+            let base = base
+                .split('-')
+                .map(|day| match day {
+                    "0" | "7" => "Sun",
+                    "1" => "Mon",
+                    "2" => "Tue",
+                    "3" => "Wed",
+                    "4" => "Thu",
+                    "5" => "Fri",
+                    "6" => "Sat",
+                    other => other,
+                })
+                .collect::<Vec<_>>()
+                .join("-");
+            match step {
+                Some(step) => format!("{base}/{step}"),
+                None => base,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("0 {} {} {} {} {}", fields[0], fields[1], fields[2], fields[3], weekdays)
+}
+
+/// If any due rule covers all games, then we back up everything.
+fn combine_games<'a>(rules: impl Iterator<Item = &'a DaemonRule>) -> Vec<String> {
+    let mut games = vec![];
+    for rule in rules {
+        if rule.games.is_empty() {
+            return vec![];
+        }
+        for game in &rule.games {
+            if !games.contains(game) {
+                games.push(game.clone());
+            }
+        }
+    }
+    games
+}
+
+struct ScheduledRule {
+    rule: DaemonRule,
+    schedule: Schedule,
+    next: Option<DateTime<Local>>,
+}
+
+/// Any rule that hasn't changed keeps its next run time,
+/// so that editing one rule doesn't postpone the others.
+fn schedule_rules(
+    config: &DaemonConfig,
+    previous: &[ScheduledRule],
+    now: DateTime<Local>,
+) -> Result<Vec<ScheduledRule>, Error> {
+    let mut rules = vec![];
+    for rule in &config.rules {
+        let schedule = Schedule::parse(rule).map_err(|why| Error::ConfigInvalid { why })?;
+        let next = match previous.iter().find(|x| x.rule == *rule) {
+            Some(previous) => previous.next,
+            None => schedule.next_after(now),
+        };
+        rules.push(ScheduledRule {
+            rule: rule.clone(),
+            schedule,
+            next,
+        });
+    }
+    if rules.is_empty() {
+        return Err(Error::ConfigInvalid {
+            why: "no rules are configured in the `daemon` section".to_string(),
+        });
+    }
+    Ok(rules)
+}
+
+fn next_runs(rules: &[ScheduledRule]) -> Vec<Option<DateTime<Local>>> {
+    rules.iter().map(|x| x.next).collect()
+}
+
+pub fn run_daemon(config: &Config, no_manifest_update: bool, try_manifest_update: bool) -> Result<(), Error> {
+    let mut daemon = config.daemon.clone();
+    let mut rules = schedule_rules(&daemon, &[], Local::now())?;
+    let mut reload_error = None;
+    log::info!(
+        "DAEMON: started with {} rules; next runs: {:?}",
+        rules.len(),
+        next_runs(&rules)
+    );
+
+    loop {
+        // Pick up changes to the config file without needing a restart.
+        match Config::load() {
+            Ok(config) => {
+                reload_error = None;
+                if config.daemon != daemon {
+                    match schedule_rules(&config.daemon, &rules, Local::now()) {
+                        Ok(new) => {
+                            rules = new;
+                            log::info!(
+                                "DAEMON: reloaded {} rules; next runs: {:?}",
+                                rules.len(),
+                                next_runs(&rules)
+                            );
+                        }
+                        Err(e) => {
+                            log::error!(
+                                "DAEMON: keeping previous rules because the new ones are invalid: {:?}",
+                                e
+                            );
+                        }
+                    }
+                    daemon = config.daemon;
+                }
+            }
+            Err(e) => {
+                if reload_error.as_ref() != Some(&e) {
+                    log::error!("DAEMON: unable to reload config; keeping previous rules: {:?}", &e);
+                    reload_error = Some(e);
+                }
+            }
+        }
+
+        let now = Local::now();
+        let due: Vec<_> = rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| rule.next.is_some_and(|time| time <= now).then_some(i))
+            .collect();
+
+        if due.is_empty() {
+            let Some(soonest) = rules.iter().filter_map(|x| x.next).min() else {
+                log::info!("DAEMON: no more scheduled runs");
+                return Ok(());
+            };
+            let wait = (soonest - now).to_std().unwrap_or_default().min(MAX_SLEEP);
+            std::thread::sleep(wait);
+            continue;
+        }
+
+        let Some(lock) = OperationLock::try_acquire() else {
+            log::warn!(
+                "DAEMON: another operation is in progress; retrying in {} seconds",
+                LOCK_RETRY.as_secs()
+            );
+            std::thread::sleep(LOCK_RETRY);
+            continue;
+        };
+
+        let games = combine_games(due.iter().map(|i| &rules[*i].rule));
+        log::info!("DAEMON: running scheduled backup for games: {:?}", &games);
+        let result = run(
+            Subcommand::Backup {
+                games: games.clone(),
+                force: true,
+                preview: Default::default(),
+                path: Default::default(),
+                wine_prefix: Default::default(),
+                api: Default::default(),
+                sort: Default::default(),
+                format: Default::default(),
+                compression: Default::default(),
+                compression_level: Default::default(),
+                full_limit: Default::default(),
+                differential_limit: Default::default(),
+                cloud_sync: daemon.cloud_sync,
+                no_cloud_sync: !daemon.cloud_sync,
+                tags: Default::default(),
+                exclude_tags: Default::default(),
+                untagged: Default::default(),
+                installed_only: Default::default(),
+                symlinks: Default::default(),
+            },
+            no_manifest_update,
+            try_manifest_update,
+        );
+        drop(lock);
+
+        match result {
+            Ok(()) => log::info!("DAEMON: completed scheduled backup for games: {:?}", &games),
+            Err(e) => {
+                log::error!("DAEMON: scheduled backup failed for games {:?}: {:?}", &games, &e);
+                eprintln!("{}", crate::lang::TRANSLATOR.handle_error(&e));
+            }
+        }
+
+        let finished = Local::now();
+        for i in due {
+            rules[i].next = rules[i].schedule.next_after(finished);
+        }
+        log::info!("DAEMON: next runs: {:?}", next_runs(&rules));
+    }
+}
+
+/// Systemd treats spaces as separators, so we quote any arguments that contain them.
+fn quote_systemd(arg: &str) -> String {
+    if arg.contains(char::is_whitespace) || arg.contains('"') {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+fn exec_start(exe: &str, config_dir: &str, args: &[&str]) -> String {
+    let mut parts = vec![quote_systemd(exe), "--config".to_string(), quote_systemd(config_dir)];
+    parts.extend(args.iter().map(|x| x.to_string()));
+    parts.join(" ")
+}
+
+/// Generate the file names and contents of the systemd user units.
+/// Without a calendar, the service runs the daemon continuously.
+/// With a calendar, a timer starts a one-shot backup of all games instead.
+pub fn systemd_units(exe: &str, config_dir: &str, on_calendar: Option<&str>) -> Vec<(&'static str, String)> {
+    match on_calendar {
+        None => vec![(
+            SERVICE_NAME,
+            format!(
+                "\
+[Unit]
+Description=Ludusavi scheduled backups
+
+[Service]
+Type=simple
+ExecStart={}
+Restart=on-failure
+RestartSec=60
+
+[Install]
+WantedBy=default.target
+",
+                exec_start(exe, config_dir, &["daemon"])
+            ),
+        )],
+        Some(on_calendar) => vec![
+            (
+                BACKUP_SERVICE_NAME,
+                format!(
+                    "\
+[Unit]
+Description=Ludusavi backup
+
+[Service]
+Type=oneshot
+ExecStart={}
+",
+                    exec_start(exe, config_dir, &["backup", "--force"])
+                ),
+            ),
+            (
+                BACKUP_TIMER_NAME,
+                format!(
+                    "\
+[Unit]
+Description=Ludusavi backup timer
+
+[Timer]
+OnCalendar={}
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+",
+                    on_calendar
+                ),
+            ),
+        ],
+    }
+}
+
+pub fn generate_systemd(on_calendar: Option<&str>, install: bool) -> Result<(), Error> {
+    let exe = std::env::current_exe()
+        .map(|x| StrictPath::from(x).render())
+        .unwrap_or_else(|_| "ludusavi".to_string());
+    let units = systemd_units(&exe, &app_dir().render(), on_calendar);
+
+    if !install {
+        for (name, content) in &units {
+            println!("# {name}\n{content}");
+        }
+        return Ok(());
+    }
+
+    let folder = StrictPath::new("~/.config/systemd/user".to_string());
+    for (name, content) in &units {
+        let file = folder.joined(name);
+        if let Err(e) = file.create_parent_dir().and_then(|_| file.write_with_content(content)) {
+            log::error!("Unable to write systemd unit {:?}: {:?}", &file, e);
+            return Err(Error::UnableToWriteFile(file));
+        }
+        println!("{}", file.render());
+    }
+
+    let enabled = if on_calendar.is_some() {
+        BACKUP_TIMER_NAME
+    } else {
+        SERVICE_NAME
+    };
+    println!("\nsystemctl --user daemon-reload && systemctl --user enable --now {enabled}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_normalize_standard_cron() {
+        assert_eq!("0 0 3 * * *", normalize_cron("0 3 * * *"));
+        assert_eq!("0 30 2 * * Sun", normalize_cron("30 2 * * 0"));
+        assert_eq!("0 30 2 * * Mon-Fri,Sun", normalize_cron("30 2 * * 1-5,7"));
+        assert_eq!("0 0 * * * */2", normalize_cron("0 * * * */2"));
+        assert_eq!("0 0 3 * * *", normalize_cron("0 0 3 * * *"));
+    }
+
+    #[test]
+    fn can_parse_rules() {
+        assert!(Schedule::parse(&DaemonRule {
+            cron: Some("0 3 * * *".to_string()),
+            ..Default::default()
+        })
+        .is_ok());
+        assert!(Schedule::parse(&DaemonRule {
+            interval: Some(30),
+            ..Default::default()
+        })
+        .is_ok());
+        assert!(Schedule::parse(&DaemonRule::default()).is_err());
+        assert!(Schedule::parse(&DaemonRule {
+            cron: Some("nope".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(Schedule::parse(&DaemonRule {
+            cron: Some("0 3 * * *".to_string()),
+            interval: Some(30),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn can_find_next_run() {
+        let now = Local.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap();
+
+        let cron = Schedule::parse(&DaemonRule {
+            cron: Some("0 3 * * *".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            Some(Local.with_ymd_and_hms(2024, 1, 3, 3, 0, 0).unwrap()),
+            cron.next_after(now)
+        );
+
+        let interval = Schedule::parse(&DaemonRule {
+            interval: Some(90),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            Some(Local.with_ymd_and_hms(2024, 1, 2, 13, 30, 0).unwrap()),
+            interval.next_after(now)
+        );
+    }
+
+    #[test]
+    fn keeps_next_run_for_unchanged_rules_when_rescheduling() {
+        let before = Local.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap();
+        let after = Local.with_ymd_and_hms(2024, 1, 2, 12, 30, 0).unwrap();
+        let hourly = DaemonRule {
+            interval: Some(60),
+            ..Default::default()
+        };
+        let daily = DaemonRule {
+            interval: Some(60 * 24),
+            ..Default::default()
+        };
+
+        let rules = schedule_rules(
+            &DaemonConfig {
+                rules: vec![hourly.clone()],
+                ..Default::default()
+            },
+            &[],
+            before,
+        )
+        .unwrap();
+        let rules = schedule_rules(
+            &DaemonConfig {
+                rules: vec![hourly, daily],
+                ..Default::default()
+            },
+            &rules,
+            after,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                Some(Local.with_ymd_and_hms(2024, 1, 2, 13, 0, 0).unwrap()),
+                Some(Local.with_ymd_and_hms(2024, 1, 3, 12, 30, 0).unwrap()),
+            ],
+            next_runs(&rules)
+        );
+
+        assert!(schedule_rules(&DaemonConfig::default(), &rules, after).is_err());
+    }
+
+    #[test]
+    fn combines_games_from_due_rules() {
+        let foo = DaemonRule {
+            games: vec!["foo".to_string()],
+            ..Default::default()
+        };
+        let bar = DaemonRule {
+            games: vec!["bar".to_string(), "foo".to_string()],
+            ..Default::default()
+        };
+        let all = DaemonRule::default();
+
+        assert_eq!(vec!["foo", "bar"], combine_games([&foo, &bar].into_iter()));
+        assert_eq!(Vec::<String>::new(), combine_games([&foo, &all].into_iter()));
+    }
+
+    #[test]
+    fn can_generate_systemd_service() {
+        let units = systemd_units("/opt/my apps/ludusavi", "/home/user/.config/ludusavi", None);
+        assert_eq!(1, units.len());
+        assert_eq!(SERVICE_NAME, units[0].0);
+        assert!(units[0]
+            .1
+            .contains("ExecStart=\"/opt/my apps/ludusavi\" --config /home/user/.config/ludusavi daemon\n"));
+    }
+
+    #[test]
+    fn can_generate_systemd_timer() {
+        let units = systemd_units("/usr/bin/ludusavi", "/home/user/.config/ludusavi", Some("daily"));
+        assert_eq!(
+            vec![BACKUP_SERVICE_NAME, BACKUP_TIMER_NAME],
+            units.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+        );
+        assert!(units[0]
+            .1
+            .contains("ExecStart=/usr/bin/ludusavi --config /home/user/.config/ludusavi backup --force\n"));
+        assert!(units[1].1.contains("OnCalendar=daily\n"));
+    }
+}
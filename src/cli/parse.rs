@@ -3,7 +3,10 @@ use std::path::PathBuf;
 use crate::{
     cloud::WebDavProvider,
     prelude::StrictPath,
-    resource::config::{BackupFormat, Sort, SortKey, ZipCompression},
+    resource::{
+        config::{BackupFormat, Sort, SortKey, SymlinkPolicy, UntaggedPolicy, WrapPolicy, ZipCompression},
+        manifest::Tag,
+    },
 };
 
 use clap::{ArgGroup, Args, ValueEnum};
@@ -116,7 +119,14 @@ impl From<CliSort> for Sort {
 /// Supported launchers for wrap --infer command
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Launcher {
+    /// Try each of the other launchers until one works.
+    Auto,
+    Bottles,
+    /// GOG Galaxy or gogdl.
+    Gog,
     Heroic,
+    Itch,
+    Legendary,
     Lutris,
     Steam,
 }
@@ -205,6 +215,34 @@ pub enum Subcommand {
         #[clap(long, conflicts_with("cloud_sync"))]
         no_cloud_sync: bool,
 
+        /// Only back up files with at least one of these manifest tags.
+        /// Multiple values may be separated by commas.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_delimiter = ',', value_parser = possible_values!(Tag, ALL_NAMES))]
+        tags: Vec<Tag>,
+
+        /// Don't back up files with any of these manifest tags.
+        /// Multiple values may be separated by commas.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_delimiter = ',', value_parser = possible_values!(Tag, ALL_NAMES))]
+        exclude_tags: Vec<Tag>,
+
+        /// Whether to back up files that have no tags in the manifest.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_parser = possible_values!(UntaggedPolicy, ALL_NAMES))]
+        untagged: Option<UntaggedPolicy>,
+
+        /// Only scan games that a launcher detected as installed,
+        /// as well as custom games and games that already have a backup.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        installed_only: bool,
+
+        /// How to handle symbolic links.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_parser = possible_values!(SymlinkPolicy, ALL_NAMES))]
+        symlinks: Option<SymlinkPolicy>,
+
         /// Only back up these specific games.
         /// Alternatively supports stdin (one value per line).
         #[clap()]
@@ -279,6 +317,40 @@ pub enum Subcommand {
         #[clap()]
         games: Vec<String>,
     },
+    /// Show the play sessions recorded by the `wrap` command
+    Sessions {
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Only report these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Show games that launchers detected as installed
+    Installed {
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Only report these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Show the Steam accounts found in each Steam root
+    ///
+    /// The IDs can be used in the `accounts` field of a Steam root in the config file
+    /// to only back up and restore data for those accounts.
+    SteamAccounts {
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+    },
     /// Find game titles
     ///
     /// Precedence: Steam ID -> GOG ID -> Lutris ID -> exact names -> normalized names.
@@ -322,6 +394,10 @@ pub enum Subcommand {
         #[clap(long)]
         lutris_id: Option<String>,
 
+        /// Look up game by a Flatpak ID.
+        #[clap(long)]
+        flatpak_id: Option<String>,
+
         /// Look up game by an approximation of the title.
         /// Ignores capitalization, "edition" suffixes, year suffixes, and some special symbols.
         /// This may find multiple games for a single input.
@@ -342,6 +418,36 @@ pub enum Subcommand {
         #[clap()]
         names: Vec<String>,
     },
+    /// Run scheduled backups in the background.
+    ///
+    /// Schedules are configured in the `daemon` section of the config file.
+    /// While a scheduled backup is running, the GUI will not start another backup or restore,
+    /// and vice versa.
+    Daemon {
+        #[clap(subcommand)]
+        sub: Option<DaemonSubcommand>,
+    },
+    /// Back up games automatically when their save files change.
+    ///
+    /// Each game's save locations are found once at startup
+    /// and found again whenever the config file or manifest changes.
+    /// Only games with existing save data are watched.
+    Watch {
+        /// Wait until files stop changing for this long (in seconds)
+        /// before backing up.
+        #[clap(long, value_name = "SECONDS", default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+        debounce: u64,
+
+        /// Only watch these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Options for roots.
+    Roots {
+        #[clap(subcommand)]
+        sub: RootsSubcommand,
+    },
     /// Options for Ludusavi's data set.
     Manifest {
         #[clap(subcommand)]
@@ -365,6 +471,73 @@ pub enum Subcommand {
         #[clap(long)]
         gui: bool,
 
+        /// Before backing up, wait for a process whose executable name or path matches this glob
+        /// (e.g., `Game*.exe`).
+        /// This is useful when the game command only signals another program to start the game,
+        /// like `steam -applaunch`.
+        /// After the game command exits, Ludusavi waits up to 60 seconds for a matching process to start,
+        /// then waits for it to exit.
+        #[clap(long, value_name = "GLOB")]
+        wait_for: Option<String>,
+
+        /// Also wait for any processes spawned by the game command.
+        /// This is useful when the game command starts the game in the background and exits immediately,
+        /// like Legendary does.
+        /// Don't use this if the game command may start a long-running program,
+        /// like `steam -applaunch` when Steam isn't open yet,
+        /// since Ludusavi would then wait for that program to exit too.
+        #[clap(long)]
+        wait_children: bool,
+
+        /// Exit with the game's exit code even if the backup fails.
+        /// By default, Ludusavi exits with the game's exit code
+        /// unless there is a problem with the backup.
+        #[clap(long)]
+        always_game_exit_code: bool,
+
+        /// While the game is running, take a differential backup this often (in minutes),
+        /// as long as the save data has changed.
+        /// Use 0 to disable snapshots.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_name = "MINUTES")]
+        snapshot_interval: Option<u32>,
+
+        /// Download the game's backups from the cloud before restoring,
+        /// and upload them after backing up.
+        /// If the cloud has newer backups or the two have diverged,
+        /// then you will be asked what to do.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        cloud_sync: bool,
+
+        /// Don't perform any cloud checks or synchronization.
+        /// When not specified, this defers to the config file.
+        #[clap(long, conflicts_with("cloud_sync"))]
+        no_cloud_sync: bool,
+
+        /// Whether to restore before launching the game.
+        /// `always` only applies when the backup is newer than your current saves.
+        /// When not specified, this defers to the game's policy in the config file.
+        #[clap(long, value_name = "POLICY", value_parser = possible_values!(WrapPolicy, ALL_NAMES))]
+        restore: Option<WrapPolicy>,
+
+        /// Whether to back up after the game exits.
+        /// When not specified, this defers to the game's policy in the config file.
+        #[clap(long, value_name = "POLICY", value_parser = possible_values!(WrapPolicy, ALL_NAMES))]
+        backup: Option<WrapPolicy>,
+
+        /// Whether to launch the game anyway
+        /// if it is unrecognized or there is a problem before launching.
+        /// When not specified, this defers to the game's policy in the config file.
+        #[clap(long, value_name = "POLICY", value_parser = possible_values!(WrapPolicy, ALL_NAMES))]
+        launch_on_error: Option<WrapPolicy>,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This applies to the restore and backup steps,
+        /// followed by an object with a `wrap` field describing how the game exited.
+        #[clap(long)]
+        api: bool,
+
         /// Commands to launch the game.
         /// Use `--` first to separate these from the `wrap` options;
         /// e.g., `ludusavi wrap --name foo -- foo.exe --windowed`.
@@ -400,6 +573,36 @@ pub enum Subcommand {
     },
 }
 
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum RootsSubcommand {
+    /// Look for roots that are not configured yet,
+    /// including Wine prefixes outside of known launchers.
+    Discover {
+        /// Add the discovered roots to the config file.
+        #[clap(long)]
+        add: bool,
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum DaemonSubcommand {
+    /// Generate systemd user units to run Ludusavi in the background (Linux).
+    Systemd {
+        /// Instead of a service that runs the daemon continuously,
+        /// generate a timer that backs up all games on this schedule.
+        /// This is a systemd calendar expression, like `daily` or `*-*-* 03:00:00`.
+        #[clap(long, value_name = "SCHEDULE")]
+        on_calendar: Option<String>,
+
+        /// Write the units to `~/.config/systemd/user` instead of printing them.
+        #[clap(long)]
+        install: bool,
+    },
+}
+
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
 pub enum ManifestSubcommand {
     /// Print the content of the manifest, including any custom entries.
@@ -654,6 +857,11 @@ mod tests {
                     differential_limit: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
+                    tags: vec![],
+                    exclude_tags: vec![],
+                    untagged: None,
+                    installed_only: false,
+                    symlinks: None,
                     games: vec![],
                 }),
             },
@@ -686,6 +894,15 @@ mod tests {
                 "--differential-limit",
                 "2",
                 "--cloud-sync",
+                "--tags",
+                "save,config",
+                "--exclude-tags",
+                "other",
+                "--untagged",
+                "exclude",
+                "--installed-only",
+                "--symlinks",
+                "preserve",
                 "game1",
                 "game2",
             ],
@@ -707,6 +924,11 @@ mod tests {
                     differential_limit: Some(2),
                     cloud_sync: true,
                     no_cloud_sync: false,
+                    tags: vec![Tag::Save, Tag::Config],
+                    exclude_tags: vec![Tag::Other],
+                    untagged: Some(UntaggedPolicy::Exclude),
+                    installed_only: true,
+                    symlinks: Some(SymlinkPolicy::Preserve),
                     games: vec![s("game1"), s("game2")],
                 }),
             },
@@ -735,6 +957,11 @@ mod tests {
                     differential_limit: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
+                    tags: vec![],
+                    exclude_tags: vec![],
+                    untagged: None,
+                    installed_only: false,
+                    symlinks: None,
                     games: vec![],
                 }),
             },
@@ -771,6 +998,11 @@ mod tests {
                         differential_limit: None,
                         cloud_sync: false,
                         no_cloud_sync: false,
+                        tags: vec![],
+                        exclude_tags: vec![],
+                        untagged: None,
+                        installed_only: false,
+                        symlinks: None,
                         games: vec![],
                     }),
                 },
@@ -800,6 +1032,11 @@ mod tests {
                     differential_limit: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
+                    tags: vec![],
+                    exclude_tags: vec![],
+                    untagged: None,
+                    installed_only: false,
+                    symlinks: None,
                     games: vec![],
                 }),
             },
@@ -1027,6 +1264,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_cli_installed_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "installed"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Installed {
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_installed_with_all_arguments() {
+        check_args(
+            &["ludusavi", "installed", "--api", "game1", "game2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Installed {
+                    api: true,
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_steam_accounts() {
+        check_args(
+            &["ludusavi", "steam-accounts", "--api"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::SteamAccounts { api: true }),
+            },
+        );
+    }
+
     #[test]
     fn accepts_cli_find_with_minimal_arguments() {
         check_args(
@@ -1043,6 +1325,7 @@ mod tests {
                     steam_id: None,
                     gog_id: None,
                     lutris_id: None,
+                    flatpak_id: None,
                     normalized: false,
                     disabled: false,
                     partial: false,
@@ -1069,6 +1352,8 @@ mod tests {
                 "102",
                 "--lutris-id",
                 "slug",
+                "--flatpak-id",
+                "com.example.Game",
                 "--normalized",
                 "--disabled",
                 "--partial",
@@ -1087,6 +1372,7 @@ mod tests {
                     steam_id: Some(101),
                     gog_id: Some(102),
                     lutris_id: Some("slug".to_string()),
+                    flatpak_id: Some("com.example.Game".to_string()),
                     normalized: true,
                     disabled: true,
                     partial: true,
@@ -1095,4 +1381,167 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn accepts_cli_wrap_with_wait_options() {
+        check_args(
+            &[
+                "ludusavi",
+                "wrap",
+                "--name",
+                "foo",
+                "--wait-for",
+                "Game*.exe",
+                "--wait-children",
+                "--",
+                "steam",
+                "-applaunch",
+                "123",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Wrap {
+                    name_source: WrapSubcommand {
+                        infer: None,
+                        name: Some("foo".to_string()),
+                    },
+                    force: false,
+                    gui: false,
+                    wait_for: Some("Game*.exe".to_string()),
+                    wait_children: true,
+                    always_game_exit_code: false,
+                    snapshot_interval: None,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    restore: None,
+                    backup: None,
+                    launch_on_error: None,
+                    api: false,
+                    commands: vec!["steam".to_string(), "-applaunch".to_string(), "123".to_string()],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_wrap_with_policies() {
+        check_args(
+            &[
+                "ludusavi",
+                "wrap",
+                "--name",
+                "foo",
+                "--restore",
+                "always",
+                "--launch-on-error",
+                "never",
+                "--",
+                "foo.exe",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Wrap {
+                    name_source: WrapSubcommand {
+                        infer: None,
+                        name: Some("foo".to_string()),
+                    },
+                    force: false,
+                    gui: false,
+                    wait_for: None,
+                    wait_children: false,
+                    always_game_exit_code: false,
+                    snapshot_interval: None,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    restore: Some(WrapPolicy::Always),
+                    backup: None,
+                    launch_on_error: Some(WrapPolicy::Never),
+                    api: false,
+                    commands: vec!["foo.exe".to_string()],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_daemon() {
+        check_args(
+            &["ludusavi", "daemon"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Daemon { sub: None }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_watch_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "watch"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Watch {
+                    debounce: 5,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_watch_with_all_arguments() {
+        check_args(
+            &["ludusavi", "watch", "--debounce", "30", "game1", "game2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Watch {
+                    debounce: 30,
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_daemon_systemd() {
+        check_args(
+            &["ludusavi", "daemon", "systemd", "--on-calendar", "daily", "--install"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Daemon {
+                    sub: Some(DaemonSubcommand::Systemd {
+                        on_calendar: Some("daily".to_string()),
+                        install: true,
+                    }),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_roots_discover() {
+        check_args(
+            &["ludusavi", "roots", "discover", "--add", "--api"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                sub: Some(Subcommand::Roots {
+                    sub: RootsSubcommand::Discover { add: true, api: true },
+                }),
+            },
+        );
+    }
 }
@@ -1,4 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+};
 
 use itertools::Itertools;
 
@@ -6,14 +9,19 @@ use crate::{
     cloud::CloudChange,
     lang::TRANSLATOR,
     prelude::StrictPath,
-    resource::manifest::Os,
+    resource::{
+        config::Root,
+        manifest::{Os, Store, Tag},
+        sessions::Session,
+    },
     scan::{
-        layout::Backup, BackupError, BackupInfo, DuplicateDetector, OperationStatus, OperationStepDecision, ScanChange,
-        ScanInfo,
+        layout::Backup, BackupError, BackupInfo, DuplicateDetector, LauncherGame, OperationStatus,
+        OperationStepDecision, ScanChange, ScanInfo, SteamAccounts,
     },
+    wrap::process::Outcome,
 };
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiErrors {
     /// Whether any games failed.
@@ -50,14 +58,14 @@ impl ApiErrors {
 }
 
 pub mod concern {
-    #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+    #[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
     pub struct CloudConflict {}
 
-    #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+    #[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
     pub struct CloudSyncFailed {}
 }
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 struct SaveError {
     /// If the entry failed, then this explains why.
     message: String,
@@ -71,7 +79,7 @@ impl From<&BackupError> for SaveError {
     }
 }
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct ApiFile {
     /// Whether this entry failed to process.
@@ -98,9 +106,28 @@ struct ApiFile {
     /// Any other games that also have the same file path.
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     duplicated_by: BTreeSet<String>,
+    /// If several games have the same file path,
+    /// then this is the game that owns it according to the duplicate rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_owner: Option<String>,
+    /// Tags from the manifest entries that matched this file.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    tags: BTreeSet<Tag>,
+    /// If this is a symbolic link that was stored as a link,
+    /// then this is where it points.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    /// If the file is in a Steam account's `userdata` folder,
+    /// then this identifies the account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steam_account: Option<String>,
+    /// Whether the file was found via save locations from a launcher's own metadata,
+    /// rather than from the manifest.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    from_launcher: bool,
 }
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct ApiRegistry {
     /// Whether this entry failed to process.
@@ -122,7 +149,7 @@ struct ApiRegistry {
     values: BTreeMap<String, ApiRegistryValue>,
 }
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct ApiRegistryValue {
     /// Whether this entry was ignored.
@@ -136,7 +163,7 @@ struct ApiRegistryValue {
     duplicated_by: BTreeSet<String>,
 }
 
-#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, serde::Serialize, schemars::JsonSchema)]
 #[serde(untagged, rename_all = "camelCase")]
 enum ApiGame {
     /// Used by the `backup` and `restore` commands.
@@ -159,9 +186,47 @@ enum ApiGame {
     },
     /// Used by the `find` command.
     Found {},
+    /// Used by the `installed` command.
+    Installed {
+        /// Each place where a launcher detected the game.
+        installations: Vec<ApiInstallation>,
+    },
+    /// Used by the `sessions` command.
+    Played {
+        /// Play sessions recorded by the `wrap` command, oldest first.
+        sessions: Vec<ApiSession>,
+    },
 }
 
-#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiSession {
+    #[serde(flatten)]
+    session: Session,
+    /// How long the game ran, in seconds.
+    duration: i64,
+}
+
+#[derive(Clone, Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiInstallation {
+    /// Path of the root that detected the game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<String>,
+    /// Store of the root that detected the game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    store: Option<Store>,
+    /// Whether the game was detected as an installed Flatpak app.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    flatpak: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    install_dir: Option<String>,
+    /// Wine prefix, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct ApiBackup {
     name: String,
@@ -171,10 +236,12 @@ struct ApiBackup {
     #[serde(skip_serializing_if = "Option::is_none")]
     comment: Option<String>,
     pub locked: bool,
+    /// Whether this backup was taken while the game was running.
+    pub snapshot: bool,
 }
 
 /// General output used by commands in `--api` mode
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct JsonOutput {
     /// Any errors.
@@ -189,9 +256,63 @@ pub struct JsonOutput {
     /// Populated by the `cloud` commands.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     cloud: BTreeMap<String, CloudEntry>,
+    /// Each key is the path of a Steam root,
+    /// and each nested key is an account ID (the folder name in `userdata`).
+    /// Populated by the `steam-accounts` command.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    steam_accounts: BTreeMap<String, BTreeMap<String, ApiSteamAccount>>,
+    /// How the game exited.
+    /// Populated by the `wrap` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wrap: Option<ApiWrap>,
 }
 
-#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiSteamAccount {
+    /// 64-bit Steam ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steam_id: Option<String>,
+    /// Login name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_name: Option<String>,
+    /// Display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persona_name: Option<String>,
+    /// Whether this was the most recently logged in account.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    most_recent: bool,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiWrap {
+    /// Name of the game as known to Ludusavi, if recognized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game: Option<String>,
+    /// Exit code of the game command.
+    /// If the game was killed by a signal, then this is 128 plus the signal number.
+    exit_code: i32,
+    /// Signal that killed the game, if any (Unix only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signal: Option<i32>,
+    /// Termination signals that Ludusavi received while the game was running (Unix only).
+    /// When the game runs in the terminal's foreground,
+    /// keyboard signals like SIGINT go to the game directly, so they are not forwarded.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    received_signals: Vec<i32>,
+    /// Termination signals that Ludusavi forwarded to the game (Unix only).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    forwarded_signals: Vec<i32>,
+    /// Output of restoring the game's saves before launching it, if done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restore: Option<Box<JsonOutput>>,
+    /// Output of backing up the game's saves after it exited, if done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup: Option<Box<JsonOutput>>,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 struct CloudEntry {
     /// How this file compares to the cloud version (if doing an upload)
@@ -199,6 +320,30 @@ struct CloudEntry {
     change: ScanChange,
 }
 
+/// While this is set, JSON reports are collected here instead of being printed,
+/// so that the `wrap` command can combine them into a single document.
+static CAPTURED_JSON: Mutex<Option<Vec<JsonOutput>>> = Mutex::new(None);
+
+/// Run an operation, and if `enabled`, return its JSON report instead of printing it.
+pub fn capture_json<T>(enabled: bool, operation: impl FnOnce() -> T) -> (T, Option<JsonOutput>) {
+    if !enabled {
+        return (operation(), None);
+    }
+
+    *CAPTURED_JSON.lock().unwrap() = Some(vec![]);
+    let result = operation();
+    let captured = CAPTURED_JSON
+        .lock()
+        .unwrap()
+        .take()
+        .and_then(|mut outputs| outputs.pop());
+    (result, captured)
+}
+
+pub fn print_json(output: &JsonOutput) {
+    println!("{}", serde_json::to_string_pretty(output).unwrap());
+}
+
 #[derive(Debug)]
 pub enum Reporter {
     Standard {
@@ -227,6 +372,8 @@ impl Reporter {
                 overall: Some(Default::default()),
                 games: Default::default(),
                 cloud: Default::default(),
+                steam_accounts: Default::default(),
+                wrap: None,
             },
         }
     }
@@ -288,6 +435,7 @@ impl Reporter {
         backup_info: &BackupInfo,
         decision: &OperationStepDecision,
         duplicate_detector: &DuplicateDetector,
+        steam_accounts: &SteamAccounts,
     ) -> bool {
         if !scan_info.can_report_game() {
             return true;
@@ -311,7 +459,7 @@ impl Reporter {
                         successful = false;
                     }
                     parts.push(TRANSLATOR.cli_game_line_item(
-                        &entry.readable(restoring),
+                        &TRANSLATOR.cli_game_line_item_tagged(&entry.readable(restoring), &entry.tags),
                         entry_successful,
                         entry.ignored,
                         !duplicate_detector.is_file_duplicated(entry).resolved(),
@@ -327,6 +475,18 @@ impl Reporter {
                         }
                     }
 
+                    if let Some(owner) = duplicate_detector.file_owner(entry) {
+                        parts.push(TRANSLATOR.cli_game_line_item_owned_by(owner));
+                    }
+
+                    if let Some(account) = steam_accounts.label(entry.original_path()) {
+                        parts.push(TRANSLATOR.cli_game_line_item_steam_account(&account));
+                    }
+
+                    if entry.from_launcher {
+                        parts.push(TRANSLATOR.cli_game_line_item_from_launcher());
+                    }
+
                     if let Some(error) = backup_info.failed_files.get(entry) {
                         parts.push(TRANSLATOR.cli_game_line_item_error(error));
                     }
@@ -388,6 +548,10 @@ impl Reporter {
                         error: backup_info.failed_files.get(entry).map(SaveError::from),
                         ignored: entry.ignored,
                         change: entry.change(),
+                        tags: entry.tags.clone(),
+                        link: entry.link.clone(),
+                        steam_account: steam_accounts.label(entry.original_path()),
+                        from_launcher: entry.from_launcher,
                         ..Default::default()
                     };
                     if !duplicate_detector.is_file_duplicated(entry).resolved() {
@@ -395,6 +559,7 @@ impl Reporter {
                         duplicated_by.remove(&scan_info.game_name);
                         api_file.duplicated_by = duplicated_by;
                     }
+                    api_file.duplicate_owner = duplicate_detector.file_owner(entry).cloned();
 
                     if let Some(alt) = entry.alt_readable(restoring) {
                         if restoring {
@@ -511,6 +676,9 @@ impl Reporter {
                     if backup.locked() {
                         line += " [🔒]";
                     }
+                    if backup.snapshot() {
+                        line += &format!(" [{}]", TRANSLATOR.badge_snapshot());
+                    }
                     if let Some(comment) = backup.comment() {
                         line += &format!(" - {comment}");
                     }
@@ -533,6 +701,7 @@ impl Reporter {
                         os: backup.os(),
                         comment: backup.comment().cloned(),
                         locked: backup.locked(),
+                        snapshot: backup.snapshot(),
                     });
                 }
 
@@ -547,6 +716,155 @@ impl Reporter {
         }
     }
 
+    pub fn add_installed(&mut self, name: &str, display_title: &str, installations: &[(Option<&Root>, &LauncherGame)]) {
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!("{}:", display_title));
+                for (root, game) in installations {
+                    match root {
+                        Some(root) => parts.push(format!(
+                            "  - {} [{}]",
+                            root.path().render(),
+                            TRANSLATOR.store(&root.store())
+                        )),
+                        None => parts.push(format!("  - {}", TRANSLATOR.flatpak_label())),
+                    }
+                    if let Some(install_dir) = &game.install_dir {
+                        parts.push(format!("    {} {}", TRANSLATOR.folder_label(), install_dir.render()));
+                    }
+                    if let Some(prefix) = &game.prefix {
+                        parts.push(format!(
+                            "    {} {}",
+                            TRANSLATOR.field(&TRANSLATOR.store(&Store::OtherWine)),
+                            prefix.render()
+                        ));
+                    }
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output } => {
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Installed {
+                        installations: installations
+                            .iter()
+                            .map(|(root, game)| ApiInstallation {
+                                root: root.map(|x| x.path().render()),
+                                store: root.map(|x| x.store()),
+                                flatpak: root.is_none(),
+                                install_dir: game.install_dir.as_ref().map(|x| x.render()),
+                                prefix: game.prefix.as_ref().map(|x| x.render()),
+                            })
+                            .collect(),
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_sessions(&mut self, name: &str, display_title: &str, sessions: &[Session]) {
+        match self {
+            Self::Standard { parts, .. } => {
+                if sessions.is_empty() {
+                    return;
+                }
+
+                parts.push(format!("{}:", display_title));
+                for session in sessions {
+                    parts.push(format!(
+                        "  - {} ({})",
+                        session.start.with_timezone(&chrono::Local).format("%Y-%m-%dT%H:%M:%S"),
+                        TRANSLATOR.session_duration(session.duration()),
+                    ));
+                    parts.push(format!("    - {}", TRANSLATOR.session_exit_code(session.exit_code)));
+                    if session.restored {
+                        parts.push(format!("    - {}", TRANSLATOR.restored_label()));
+                    }
+                    if session.backed_up {
+                        match &session.backup {
+                            Some(backup) => {
+                                parts.push(format!("    - {}: \"{}\"", TRANSLATOR.backed_up_label(), backup))
+                            }
+                            None => parts.push(format!("    - {}", TRANSLATOR.backed_up_label())),
+                        }
+                    }
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output } => {
+                if sessions.is_empty() {
+                    return;
+                }
+
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Played {
+                        sessions: sessions
+                            .iter()
+                            .map(|session| ApiSession {
+                                session: session.clone(),
+                                duration: session.duration().num_seconds(),
+                            })
+                            .collect(),
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_steam_accounts(&mut self, steam_accounts: &SteamAccounts) {
+        match self {
+            Self::Standard { parts, .. } => {
+                for (root, accounts) in steam_accounts.iter() {
+                    parts.push(format!("{}:", root.render()));
+                    for account in accounts.values() {
+                        let mut line = format!("  - {}", account.id);
+                        let names: Vec<_> = [&account.persona_name, &account.account_name]
+                            .into_iter()
+                            .flatten()
+                            .cloned()
+                            .collect();
+                        if !names.is_empty() {
+                            line += &format!(" ({})", names.join(" / "));
+                        }
+                        if account.most_recent {
+                            line += &format!(" [{}]", TRANSLATOR.steam_account_most_recent());
+                        }
+                        parts.push(line);
+                    }
+
+                    // Blank line between roots.
+                    parts.push("".to_string());
+                }
+            }
+            Self::Json { output } => {
+                for (root, accounts) in steam_accounts.iter() {
+                    output.steam_accounts.insert(
+                        root.render(),
+                        accounts
+                            .values()
+                            .map(|account| {
+                                (
+                                    account.id.clone(),
+                                    ApiSteamAccount {
+                                        steam_id: account.steam_id.clone(),
+                                        account_name: account.account_name.clone(),
+                                        persona_name: account.persona_name.clone(),
+                                        most_recent: account.most_recent,
+                                    },
+                                )
+                            })
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+
     pub fn add_found_titles(&mut self, names: &BTreeSet<String>) {
         match self {
             Self::Standard { parts, .. } => {
@@ -587,6 +905,12 @@ impl Reporter {
     }
 
     pub fn print(&self, path: &StrictPath) {
+        if let Self::Json { output } = self {
+            if let Some(captured) = CAPTURED_JSON.lock().unwrap().as_mut() {
+                captured.push(output.clone());
+                return;
+            }
+        }
         println!("{}", self.render(path));
     }
 }
@@ -598,6 +922,8 @@ pub fn report_cloud_changes(changes: &[CloudChange], api: bool) {
             overall: None,
             games: Default::default(),
             cloud: Default::default(),
+            steam_accounts: Default::default(),
+            wrap: None,
         };
 
         output.cloud = changes
@@ -617,6 +943,26 @@ pub fn report_cloud_changes(changes: &[CloudChange], api: bool) {
     }
 }
 
+pub fn report_wrap(game: Option<&str>, outcome: &Outcome, restore: Option<JsonOutput>, backup: Option<JsonOutput>) {
+    let output = JsonOutput {
+        errors: None,
+        overall: None,
+        games: Default::default(),
+        cloud: Default::default(),
+        steam_accounts: Default::default(),
+        wrap: Some(ApiWrap {
+            game: game.map(|x| x.to_string()),
+            exit_code: outcome.exit_code(),
+            signal: outcome.signal(),
+            received_signals: outcome.received_signals.clone(),
+            forwarded_signals: outcome.forwarded_signals.clone(),
+            restore: restore.map(Box::new),
+            backup: backup.map(Box::new),
+        }),
+    };
+    print_json(&output);
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -624,7 +970,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        scan::{registry_compat::RegistryItem, BackupError, ScannedFile, ScannedRegistry},
+        scan::{layout::FullBackup, registry_compat::RegistryItem, BackupError, ScannedFile, ScannedRegistry},
         testing::s,
     };
 
@@ -637,6 +983,7 @@ mod tests {
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -668,6 +1015,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: StrictPath::new(s("/file2")),
@@ -678,6 +1029,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {
@@ -697,6 +1052,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -738,6 +1094,10 @@ Overall:
                         change: ScanChange::Same,
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {},
@@ -749,6 +1109,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         reporter.add_game(
             "bar",
@@ -764,6 +1125,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {},
@@ -775,6 +1140,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -812,6 +1178,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: StrictPath::new(s("/backup/file2")),
@@ -822,6 +1192,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {},
@@ -830,6 +1204,7 @@ Overall:
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -883,6 +1258,7 @@ Overall:
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &duplicate_detector,
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -923,6 +1299,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         reporter.add_game(
             "bar",
@@ -940,6 +1317,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -972,6 +1350,7 @@ Overall:
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -995,6 +1374,21 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_capture_json_output() {
+        let reporter = Reporter::json();
+        let path = StrictPath::new(s("/dev/null"));
+
+        let ((), output) = capture_json(true, || reporter.print(&path));
+        assert_eq!(
+            reporter.render(&path),
+            serde_json::to_string_pretty(&output.unwrap()).unwrap()
+        );
+
+        let ((), output) = capture_json(false, || ());
+        assert!(output.is_none());
+    }
+
     #[test]
     fn can_render_in_json_mode_with_one_game_in_backup_mode() {
         let mut reporter = Reporter::json();
@@ -1024,6 +1418,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -1107,6 +1502,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: StrictPath::new(s("/backup/file2")),
@@ -1117,6 +1516,10 @@ Overall:
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {},
@@ -1125,6 +1528,7 @@ Overall:
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -1200,6 +1604,7 @@ Overall:
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
             &duplicate_detector,
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -1268,6 +1673,7 @@ Overall:
             },
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
+            &SteamAccounts::default(),
         );
         assert_eq!(
             r#"
@@ -1308,6 +1714,55 @@ Overall:
       "registry": {}
     }
   }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_snapshot_backups_in_json_mode() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_backups(
+            "foo",
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[Backup::Full(FullBackup {
+                name: s("backup-1"),
+                when: chrono::DateTime::from_timestamp(0, 0).unwrap(),
+                snapshot: true,
+                ..Default::default()
+            })],
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 0,
+    "totalBytes": 0,
+    "processedGames": 0,
+    "processedBytes": 0,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 0
+    }
+  },
+  "games": {
+    "foo": {
+      "backupPath": "/backups/foo",
+      "backups": [
+        {
+          "name": "backup-1",
+          "when": "1970-01-01T00:00:00Z",
+          "locked": false,
+          "snapshot": true
+        }
+      ]
+    }
+  }
 }
             "#
             .trim(),
@@ -0,0 +1,578 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    cli::{evaluate_games, load_manifest, parse::Subcommand, run},
+    lang::TRANSLATOR,
+    path::CommonPath,
+    prelude::{app_dir, Error, OperationLock},
+    resource::{
+        cache::Cache,
+        config::{Config, Root},
+        manifest::Manifest,
+        ResourceFile,
+    },
+    scan::{backup_candidates, layout::BackupLayout, scan_game_for_backup, Launchers, SteamShortcuts, TitleFinder},
+};
+
+/// How long to wait before trying again when another process holds the lock.
+const LOCK_RETRY: Duration = Duration::from_secs(10);
+
+/// Folders to watch and the games whose save files are in them.
+/// We watch the folders rather than the files themselves
+/// so that we also notice when a game creates a new file or replaces an old one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct WatchPlan {
+    /// Save locations that are watched recursively, including any new subfolders.
+    folders: BTreeMap<PathBuf, BTreeSet<String>>,
+    /// Folders that directly contain save files.
+    /// These are only watched non-recursively.
+    parents: BTreeMap<PathBuf, BTreeSet<String>>,
+    /// Save files directly inside of a broad folder, like the home folder.
+    /// We still watch the folder, but only changes to the file itself are relevant.
+    lone_files: BTreeMap<PathBuf, BTreeSet<String>>,
+    /// Save locations that don't exist or are still empty, keyed by the closest existing folder.
+    /// When something appears on the way to one of them, we find the save paths again.
+    pending: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+    /// Changes here are ignored, since they're caused by our own backups.
+    backup_folder: Option<PathBuf>,
+}
+
+impl WatchPlan {
+    fn add_file(&mut self, game: &str, file: &Path) {
+        if let Some(folder) = file.parent() {
+            self.parents
+                .entry(folder.to_path_buf())
+                .or_default()
+                .insert(game.to_string());
+        }
+    }
+
+    fn add_lone_file(&mut self, game: &str, file: &Path) {
+        self.lone_files
+            .entry(file.to_path_buf())
+            .or_default()
+            .insert(game.to_string());
+    }
+
+    fn add_folder(&mut self, game: &str, folder: &Path) {
+        self.folders
+            .entry(folder.to_path_buf())
+            .or_default()
+            .insert(game.to_string());
+    }
+
+    fn add_pending(&mut self, location: &Path) {
+        let Some(existing) = location.ancestors().find(|x| x.is_dir()) else {
+            return;
+        };
+        self.pending
+            .entry(existing.to_path_buf())
+            .or_default()
+            .insert(location.to_path_buf());
+    }
+
+    fn games(&self) -> BTreeSet<&String> {
+        self.folders
+            .values()
+            .chain(self.parents.values())
+            .chain(self.lone_files.values())
+            .flatten()
+            .collect()
+    }
+
+    /// Each folder to watch and whether to watch it recursively.
+    /// Folders inside of a recursively watched folder are already covered.
+    fn watches(&self) -> BTreeMap<PathBuf, bool> {
+        let recursive: Vec<_> = self.folders.keys().collect();
+        let covered = |folder: &Path| recursive.iter().any(|x| folder != *x && folder.starts_with(x));
+
+        let mut watches = BTreeMap::new();
+        for folder in self.folders.keys() {
+            if !covered(folder) {
+                watches.insert(folder.clone(), true);
+            }
+        }
+        let lone_parents = self.lone_files.keys().filter_map(|file| file.parent());
+        for folder in self
+            .parents
+            .keys()
+            .map(PathBuf::as_path)
+            .chain(lone_parents)
+            .chain(self.pending.keys().map(PathBuf::as_path))
+        {
+            if !covered(folder) {
+                watches.entry(folder.to_path_buf()).or_insert(false);
+            }
+        }
+        watches
+    }
+
+    fn is_backup_folder(&self, path: &Path) -> bool {
+        self.backup_folder
+            .as_ref()
+            .is_some_and(|backups| path.starts_with(backups))
+    }
+
+    /// Find the games affected by a change to these paths.
+    /// A path may be a file inside of a watched folder or the watched folder itself.
+    fn affected<'a>(&self, paths: impl Iterator<Item = &'a Path>) -> BTreeSet<String> {
+        let mut games = BTreeSet::new();
+        for path in paths {
+            if self.is_backup_folder(path) {
+                continue;
+            }
+            for (folder, owners) in &self.folders {
+                if path.starts_with(folder) {
+                    games.extend(owners.iter().cloned());
+                }
+            }
+            for folder in [Some(path), path.parent()].into_iter().flatten() {
+                if let Some(owners) = self.parents.get(folder) {
+                    games.extend(owners.iter().cloned());
+                }
+            }
+            if let Some(owners) = self.lone_files.get(path) {
+                games.extend(owners.iter().cloned());
+            }
+        }
+        games
+    }
+
+    /// Check if a change to these paths may have created a pending save location.
+    fn needs_resolve<'a>(&self, mut paths: impl Iterator<Item = &'a Path>) -> bool {
+        paths.any(|path| {
+            !self.is_backup_folder(path)
+                && self
+                    .pending
+                    .values()
+                    .flatten()
+                    .any(|location| location.starts_with(path) || path.starts_with(location))
+        })
+    }
+}
+
+fn is_resource_file(path: &Path, app_dir: &Path) -> bool {
+    path.parent() == Some(app_dir)
+        && path
+            .file_name()
+            .is_some_and(|name| name == Config::FILE_NAME || name == Manifest::FILE_NAME)
+}
+
+/// The part of a path pattern before its first component with a glob.
+/// If the pattern has no globs, then the whole path is returned.
+fn static_prefix(pattern: &str) -> PathBuf {
+    let mut prefix = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        if component.as_os_str().to_string_lossy().contains(['*', '?', '[']) {
+            break;
+        }
+        prefix.push(component);
+    }
+    prefix
+}
+
+/// Find where each game's save files currently are.
+fn resolve(games: &[String], no_manifest_update: bool, try_manifest_update: bool) -> Result<WatchPlan, Error> {
+    let mut config = Config::load()?;
+    let mut cache = Cache::load().unwrap_or_default().migrate_config(&mut config);
+    let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+
+    let roots = config.expanded_roots();
+    let layout = BackupLayout::new(config.backup.path.clone(), config.backup.retention.clone());
+    let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+    let games_specified = !games.is_empty();
+    let games = evaluate_games(manifest.primary_titles(), games.to_vec(), &title_finder)
+        .map_err(|games| Error::CliUnrecognizedGames { games })?;
+
+    let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+    let root_filters = Root::path_filters(&roots);
+    let filter = &config.backup.filter;
+    let steam_shortcuts = SteamShortcuts::scan();
+
+    log::info!("WATCH: resolving save paths for {} games", games.len());
+
+    let found: Vec<_> = games
+        .par_iter()
+        .filter_map(|name| {
+            if !config.is_game_enabled_for_operation(name, false) {
+                return None;
+            }
+
+            let game = &manifest.0[name];
+            let previous = layout.latest_backup(name, false, &config.redirects, &config.restore.toggled_paths);
+
+            if filter.excludes(games_specified, previous.is_some(), &game.cloud)
+                || filter.excludes_uninstalled(
+                    games_specified,
+                    previous.is_some(),
+                    config.is_game_customized(name),
+                    launchers.is_detected(name),
+                )
+            {
+                return None;
+            }
+
+            let scan_info = scan_game_for_backup(
+                game,
+                name,
+                &roots,
+                &root_filters,
+                &app_dir(),
+                &launchers,
+                filter,
+                &None,
+                &config.backup.toggled_paths,
+                &config.backup.toggled_registry,
+                previous,
+                &config.redirects,
+                &steam_shortcuts,
+            );
+
+            let files: Vec<_> = scan_info
+                .found_files
+                .iter()
+                .filter(|file| !file.ignored && file.container.is_none())
+                .filter_map(|file| file.path.as_std_path_buf().ok())
+                .collect();
+
+            // We also look at where saves could be, so that we notice games that don't have any yet.
+            let locations: BTreeSet<_> =
+                backup_candidates(game, name, &roots, &app_dir(), &launchers, filter, &steam_shortcuts)
+                    .iter()
+                    .map(|location| static_prefix(&location.render()))
+                    .collect();
+
+            Some((name, files, locations))
+        })
+        .collect();
+
+    // These would produce too many unrelated changes to watch in full.
+    let broad: Vec<_> = [
+        CommonPath::Config,
+        CommonPath::Data,
+        CommonPath::DataLocal,
+        CommonPath::Document,
+        CommonPath::Home,
+        CommonPath::Public,
+    ]
+    .iter()
+    .filter_map(|x| x.get().map(PathBuf::from))
+    .chain(roots.iter().filter_map(|root| root.path().as_std_path_buf().ok()))
+    .collect();
+    let is_broad = |folder: &Path| folder.parent().is_none() || broad.iter().any(|x| x.starts_with(folder));
+
+    let mut plan = WatchPlan {
+        backup_folder: config.backup.path.as_std_path_buf().ok(),
+        ..Default::default()
+    };
+    for (name, files, locations) in found {
+        for file in &files {
+            if file.parent().is_some_and(is_broad) {
+                plan.add_lone_file(name, file);
+            } else {
+                plan.add_file(name, file);
+            }
+        }
+        for location in locations {
+            if is_broad(&location) {
+                continue;
+            }
+            if files.iter().any(|file| file.starts_with(&location)) {
+                if location.is_dir() {
+                    plan.add_folder(name, &location);
+                }
+            } else if !location.is_file() {
+                plan.add_pending(&location);
+            }
+        }
+    }
+    Ok(plan)
+}
+
+fn back_up(game: &str) {
+    let lock = loop {
+        match OperationLock::try_acquire() {
+            Some(lock) => break lock,
+            None => {
+                log::warn!(
+                    "WATCH: another operation is in progress; retrying in {} seconds",
+                    LOCK_RETRY.as_secs()
+                );
+                std::thread::sleep(LOCK_RETRY);
+            }
+        }
+    };
+
+    log::info!("WATCH: backing up {:?}", game);
+    let result = run(
+        Subcommand::Backup {
+            games: vec![game.to_string()],
+            force: true,
+            preview: Default::default(),
+            path: Default::default(),
+            wine_prefix: Default::default(),
+            api: Default::default(),
+            sort: Default::default(),
+            format: Default::default(),
+            compression: Default::default(),
+            compression_level: Default::default(),
+            full_limit: Default::default(),
+            differential_limit: Default::default(),
+            cloud_sync: Default::default(),
+            no_cloud_sync: Default::default(),
+            tags: Default::default(),
+            exclude_tags: Default::default(),
+            untagged: Default::default(),
+            installed_only: Default::default(),
+            symlinks: Default::default(),
+        },
+        // The manifest was already checked when we resolved the paths.
+        true,
+        false,
+    );
+    drop(lock);
+
+    if let Err(e) = result {
+        log::error!("WATCH: backup failed for {:?}: {:?}", game, &e);
+        eprintln!("{}", TRANSLATOR.handle_error(&e));
+    }
+}
+
+pub fn run_watch(
+    games: Vec<String>,
+    debounce: Duration,
+    no_manifest_update: bool,
+    try_manifest_update: bool,
+) -> Result<(), Error> {
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer =
+        new_debouncer(debounce, sender).map_err(|e| Error::UnableToWatchFiles { why: e.to_string() })?;
+
+    let resource_folder = app_dir()
+        .as_std_path_buf()
+        .map_err(|e| Error::UnableToWatchFiles { why: e.to_string() })?;
+    debouncer
+        .watcher()
+        .watch(&resource_folder, RecursiveMode::NonRecursive)
+        .map_err(|e| Error::UnableToWatchFiles { why: e.to_string() })?;
+
+    let mut plan = WatchPlan::default();
+    let mut watched = BTreeMap::<PathBuf, bool>::new();
+    let mut resolve_again = true;
+
+    loop {
+        if resolve_again {
+            match resolve(&games, no_manifest_update, try_manifest_update) {
+                Ok(new_plan) => {
+                    plan = new_plan;
+
+                    for folder in std::mem::take(&mut watched).keys() {
+                        let _ = debouncer.watcher().unwatch(folder);
+                    }
+                    for (folder, recursive) in plan.watches() {
+                        if folder == resource_folder {
+                            // Already watched for the whole session.
+                            continue;
+                        }
+                        let mode = if recursive {
+                            RecursiveMode::Recursive
+                        } else {
+                            RecursiveMode::NonRecursive
+                        };
+                        match debouncer.watcher().watch(&folder, mode) {
+                            Ok(()) => {
+                                watched.insert(folder, recursive);
+                            }
+                            Err(e) => log::warn!("WATCH: unable to watch {:?}: {:?}", folder, e),
+                        }
+                    }
+
+                    log::info!("WATCH: watching {} folders: {:?}", watched.len(), &watched);
+                    println!("{}", TRANSLATOR.cli_watching_games(plan.games().len()));
+                }
+                Err(e) => {
+                    // The config may just be mid-edit, so we keep the last plan until it changes again.
+                    log::error!("WATCH: unable to resolve save paths: {:?}", &e);
+                    eprintln!("{}", TRANSLATOR.handle_error(&e));
+                }
+            }
+        }
+
+        let Ok(first) = receiver.recv() else {
+            return Ok(());
+        };
+
+        // Merge anything else that's already waiting, so that we only back up each game once.
+        let mut paths = vec![];
+        for result in std::iter::once(first).chain(receiver.try_iter()) {
+            match result {
+                Ok(events) => paths.extend(events.into_iter().map(|event| event.path)),
+                Err(e) => log::error!("WATCH: error from file watcher: {:?}", e),
+            }
+        }
+
+        for game in plan.affected(paths.iter().map(PathBuf::as_path)) {
+            back_up(&game);
+        }
+
+        resolve_again = if paths.iter().any(|path| is_resource_file(path, &resource_folder)) {
+            log::info!("WATCH: config or manifest changed; resolving save paths again");
+            true
+        } else if plan.needs_resolve(paths.iter().map(PathBuf::as_path)) {
+            log::info!("WATCH: a new save location may have appeared; resolving save paths again");
+            true
+        } else {
+            false
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_find_affected_games() {
+        let mut plan = WatchPlan::default();
+        plan.add_file("foo", Path::new("/saves/foo/1.sav"));
+        plan.add_file("foo", Path::new("/saves/foo/2.sav"));
+        plan.add_file("bar", Path::new("/saves/bar/1.sav"));
+        plan.add_file("baz", Path::new("/saves/foo/baz.sav"));
+
+        assert_eq!(
+            BTreeSet::from(["baz".to_string(), "foo".to_string()]),
+            plan.affected([Path::new("/saves/foo/new.sav")].into_iter()),
+        );
+        assert_eq!(
+            BTreeSet::from(["bar".to_string()]),
+            plan.affected([Path::new("/saves/bar")].into_iter()),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            plan.affected([Path::new("/saves/other/1.sav")].into_iter()),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            plan.affected([Path::new("/saves/foo/sub/1.sav")].into_iter()),
+        );
+    }
+
+    #[test]
+    fn can_find_affected_games_in_new_subfolders() {
+        let mut plan = WatchPlan::default();
+        plan.add_folder("foo", Path::new("/saves/foo"));
+
+        assert_eq!(
+            BTreeSet::from(["foo".to_string()]),
+            plan.affected([Path::new("/saves/foo/new/1.sav")].into_iter()),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            plan.affected([Path::new("/saves/foobar/1.sav")].into_iter()),
+        );
+    }
+
+    #[test]
+    fn ignores_changes_in_backup_folder() {
+        let mut plan = WatchPlan {
+            backup_folder: Some(PathBuf::from("/saves/backups")),
+            ..Default::default()
+        };
+        plan.add_file("foo", Path::new("/saves/foo.sav"));
+        plan.add_folder("bar", Path::new("/saves"));
+
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            plan.affected(
+                [
+                    Path::new("/saves/backups"),
+                    Path::new("/saves/backups/foo/mapping.yaml")
+                ]
+                .into_iter()
+            ),
+        );
+        assert_eq!(
+            BTreeSet::from(["bar".to_string(), "foo".to_string()]),
+            plan.affected([Path::new("/saves/foo.sav")].into_iter()),
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_changes_next_to_lone_files() {
+        let mut plan = WatchPlan::default();
+        plan.add_lone_file("foo", Path::new("/home/.foo.cfg"));
+
+        assert_eq!(
+            BTreeSet::from(["foo".to_string()]),
+            plan.affected([Path::new("/home/.foo.cfg")].into_iter()),
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            plan.affected([Path::new("/home/.bash_history"), Path::new("/home")].into_iter()),
+        );
+        assert_eq!(BTreeMap::from([(PathBuf::from("/home"), false)]), plan.watches());
+    }
+
+    #[test]
+    fn can_detect_new_save_locations() {
+        let plan = WatchPlan {
+            pending: BTreeMap::from([(
+                PathBuf::from("/home"),
+                BTreeSet::from([PathBuf::from("/home/.foo/saves")]),
+            )]),
+            ..Default::default()
+        };
+
+        assert!(plan.needs_resolve([Path::new("/home/.foo")].into_iter()));
+        assert!(plan.needs_resolve([Path::new("/home/.foo/saves/1.sav")].into_iter()));
+        assert!(!plan.needs_resolve([Path::new("/home/.bar")].into_iter()));
+    }
+
+    #[test]
+    fn can_skip_watches_covered_by_recursive_folders() {
+        let mut plan = WatchPlan::default();
+        plan.add_folder("foo", Path::new("/saves/foo"));
+        plan.add_folder("foo", Path::new("/saves/foo/sub"));
+        plan.add_file("foo", Path::new("/saves/foo/1.sav"));
+        plan.add_file("bar", Path::new("/home/bar.cfg"));
+        plan.pending
+            .insert(PathBuf::from("/saves"), BTreeSet::from([PathBuf::from("/saves/baz")]));
+
+        assert_eq!(
+            BTreeMap::from([
+                (PathBuf::from("/home"), false),
+                (PathBuf::from("/saves"), false),
+                (PathBuf::from("/saves/foo"), true),
+            ]),
+            plan.watches(),
+        );
+    }
+
+    #[test]
+    fn can_find_static_prefix() {
+        assert_eq!(PathBuf::from("/saves/foo"), static_prefix("/saves/foo/*.sav"));
+        assert_eq!(PathBuf::from("/saves"), static_prefix("/saves/*/foo.sav"));
+        assert_eq!(PathBuf::from("/saves/foo.sav"), static_prefix("/saves/foo.sav"));
+    }
+
+    #[test]
+    fn can_recognize_resource_files() {
+        let app_dir = Path::new("/app");
+        assert!(is_resource_file(Path::new("/app/config.yaml"), app_dir));
+        assert!(is_resource_file(Path::new("/app/manifest.yaml"), app_dir));
+        assert!(!is_resource_file(Path::new("/app/cache.yaml"), app_dir));
+        assert!(!is_resource_file(Path::new("/app/other/config.yaml"), app_dir));
+        assert!(!is_resource_file(Path::new("/saves/config.yaml"), app_dir));
+    }
+}
@@ -1,10 +1,13 @@
-use std::io::{BufRead, BufReader};
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, BufReader},
+};
 
 use crate::{
     lang::TRANSLATOR,
     prelude::{run_command, CommandError, CommandOutput, Error, Finality, Privacy, StrictPath, SyncDirection},
     resource::config::{App, Config},
-    scan::ScanChange,
+    scan::{layout::IndividualMapping, ScanChange},
 };
 
 pub fn validate_cloud_config(config: &Config, cloud_path: &str) -> Result<Remote, Error> {
@@ -32,6 +35,57 @@ pub struct CloudChange {
     pub change: ScanChange,
 }
 
+/// How a game's cloud backups compare to its local backups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloudRelation {
+    Same,
+    LocalNewer,
+    CloudNewer,
+    /// Each side has backups that the other one doesn't.
+    Diverged,
+}
+
+impl CloudRelation {
+    /// Either side may have dropped old backups because of retention limits,
+    /// so we only check whether the older side's latest backup is part of the newer side's history.
+    pub fn evaluate(local: Option<&IndividualMapping>, cloud: Option<&IndividualMapping>) -> Self {
+        fn history(mapping: Option<&IndividualMapping>) -> BTreeSet<(chrono::DateTime<chrono::Utc>, String)> {
+            mapping
+                .map(|mapping| {
+                    mapping
+                        .backups
+                        .iter()
+                        .flat_map(|full| {
+                            std::iter::once((full.when, full.name.clone()))
+                                .chain(full.children.iter().map(|diff| (diff.when, diff.name.clone())))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        let local = history(local);
+        let cloud = history(cloud);
+
+        match (local.last(), cloud.last()) {
+            (None, None) => Self::Same,
+            (Some(_), None) => Self::LocalNewer,
+            (None, Some(_)) => Self::CloudNewer,
+            (Some(latest_local), Some(latest_cloud)) => {
+                if latest_local == latest_cloud {
+                    Self::Same
+                } else if latest_local < latest_cloud && cloud.contains(latest_local) {
+                    Self::CloudNewer
+                } else if latest_cloud < latest_local && local.contains(latest_cloud) {
+                    Self::LocalNewer
+                } else {
+                    Self::Diverged
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum RcloneProcessEvent {
     Progress { current: f32, max: f32 },
@@ -676,6 +730,11 @@ impl Rclone {
 
         RcloneProcess::launch(self.app.path.raw(), self.args(&args))
     }
+
+    pub fn cat(&self, remote_path: &str) -> Result<String, CommandError> {
+        let out = self.run(&["cat".to_string(), self.path(remote_path)], &[0], Privacy::Public)?;
+        Ok(out.stdout)
+    }
 }
 
 pub mod rclone_monitor {
@@ -810,3 +869,91 @@ pub mod rclone_monitor {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::scan::layout::{DifferentialBackup, FullBackup};
+
+    /// A full backup's name and time, along with each of its differential backups' names and times.
+    type TestBackup<'a> = (&'a str, i64, &'a [(&'a str, i64)]);
+
+    fn mapping(backups: &[TestBackup]) -> IndividualMapping {
+        IndividualMapping {
+            backups: backups
+                .iter()
+                .map(|(name, when, children)| FullBackup {
+                    name: name.to_string(),
+                    when: chrono::DateTime::from_timestamp(*when, 0).unwrap(),
+                    children: children
+                        .iter()
+                        .map(|(name, when)| DifferentialBackup {
+                            name: name.to_string(),
+                            when: chrono::DateTime::from_timestamp(*when, 0).unwrap(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_evaluate_cloud_relation_without_backups() {
+        let some = mapping(&[("a", 1, &[])]);
+
+        assert_eq!(CloudRelation::Same, CloudRelation::evaluate(None, None));
+        assert_eq!(CloudRelation::LocalNewer, CloudRelation::evaluate(Some(&some), None));
+        assert_eq!(CloudRelation::CloudNewer, CloudRelation::evaluate(None, Some(&some)));
+    }
+
+    #[test]
+    fn can_evaluate_cloud_relation_with_shared_history() {
+        let old = mapping(&[("a", 1, &[("a1", 2)])]);
+        let new = mapping(&[("a", 1, &[("a1", 2)]), ("b", 3, &[])]);
+
+        assert_eq!(CloudRelation::Same, CloudRelation::evaluate(Some(&old), Some(&old)));
+        assert_eq!(
+            CloudRelation::CloudNewer,
+            CloudRelation::evaluate(Some(&old), Some(&new))
+        );
+        assert_eq!(
+            CloudRelation::LocalNewer,
+            CloudRelation::evaluate(Some(&new), Some(&old))
+        );
+    }
+
+    #[test]
+    fn can_evaluate_cloud_relation_after_retention() {
+        let old = mapping(&[("a", 1, &[]), ("b", 2, &[])]);
+        let new = mapping(&[("b", 2, &[]), ("c", 3, &[])]);
+
+        assert_eq!(
+            CloudRelation::CloudNewer,
+            CloudRelation::evaluate(Some(&old), Some(&new))
+        );
+        assert_eq!(
+            CloudRelation::LocalNewer,
+            CloudRelation::evaluate(Some(&new), Some(&old))
+        );
+    }
+
+    #[test]
+    fn can_evaluate_cloud_relation_when_diverged() {
+        let local = mapping(&[("a", 1, &[("a1", 2)])]);
+        let cloud = mapping(&[("a", 1, &[("a2", 3)])]);
+
+        assert_eq!(
+            CloudRelation::Diverged,
+            CloudRelation::evaluate(Some(&local), Some(&cloud))
+        );
+        assert_eq!(
+            CloudRelation::Diverged,
+            CloudRelation::evaluate(Some(&cloud), Some(&local))
+        );
+    }
+}
@@ -18,7 +18,9 @@ use crate::{
         widget::{id, Column, Container, Element, IcedParentExt, Progress, Row},
     },
     lang::TRANSLATOR,
-    prelude::{app_dir, get_threads_from_env, initialize_rayon, Error, Finality, StrictPath, SyncDirection},
+    prelude::{
+        app_dir, get_threads_from_env, initialize_rayon, Error, Finality, OperationLock, StrictPath, SyncDirection,
+    },
     resource::{
         cache::{self, Cache},
         config::{self, Config, CustomGame, CustomGameKind, Root},
@@ -27,7 +29,7 @@ use crate::{
     },
     scan::{
         layout::BackupLayout, prepare_backup_target, registry_compat::RegistryItem, scan_game_for_backup, BackupId,
-        Launchers, SteamShortcuts, TitleFinder,
+        DuplicateClaim, Launchers, ScanInfo, SteamShortcuts, TitleFinder,
     },
 };
 
@@ -77,6 +79,8 @@ pub struct App {
     manifest: LoadedManifest,
     cache: Cache,
     operation: Operation,
+    /// Held during a backup or restore, so that the daemon doesn't run at the same time.
+    operation_lock: Option<OperationLock>,
     screen: Screen,
     modal: Option<Modal>,
     backup_screen: screen::Backup,
@@ -84,6 +88,8 @@ pub struct App {
     operation_should_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
     operation_steps: Vec<Command<Message>>,
     operation_steps_active: usize,
+    /// Games scanned during a backup, waiting for the second pass to actually back them up.
+    pending_backups: Vec<ScanInfo>,
     progress: Progress,
     backups_to_restore: HashMap<String, BackupId>,
     updating_manifest: bool,
@@ -105,8 +111,10 @@ impl App {
         }
 
         self.operation = Operation::Idle;
+        self.operation_lock = None;
         self.operation_steps.clear();
         self.operation_steps_active = 0;
+        self.pending_backups.clear();
         self.modal = None;
         self.progress.reset();
         self.operation_should_cancel
@@ -269,6 +277,18 @@ impl App {
                     return Command::none();
                 }
 
+                if !preview {
+                    match OperationLock::try_acquire() {
+                        Some(lock) => self.operation_lock = Some(lock),
+                        None => {
+                            return self.show_modal(Modal::Error {
+                                variant: Error::OperationLocked,
+                            })
+                        }
+                    }
+                }
+
+                self.backup_screen.log.reload_sessions();
                 let mut cleared_log = false;
                 if games.is_none() {
                     self.backup_screen.log.clear();
@@ -368,15 +388,16 @@ impl App {
                         let steam = SteamShortcuts::scan();
                         let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
 
-                        (subjects, manifest, layout, steam, launchers)
+                        (subjects, manifest, layout, steam, launchers, roots)
                     },
-                    move |(subjects, manifest, layout, steam, heroic)| {
+                    move |(subjects, manifest, layout, steam, heroic, roots)| {
                         Message::Backup(BackupPhase::RegisterCommands {
                             subjects,
                             manifest,
                             layout: Box::new(layout),
                             steam,
                             launchers: heroic,
+                            roots,
                         })
                     },
                 )
@@ -387,10 +408,9 @@ impl App {
                 layout,
                 steam,
                 launchers,
+                roots,
             } => {
                 log::info!("beginning backup with {} steps", subjects.len());
-                let preview = self.operation.preview();
-                let full = self.operation.full();
 
                 if self.operation_should_cancel.load(std::sync::atomic::Ordering::Relaxed) {
                     self.go_idle();
@@ -419,8 +439,20 @@ impl App {
                 self.progress.set_max(subjects.len() as f32);
                 self.register_notify_on_single_game_scanned();
 
+                let detector = &mut self.backup_screen.duplicate_detector;
+                detector.set_rules(self.config.backup.duplicates.clone());
+                for key in &subjects {
+                    detector.add_claim(
+                        key,
+                        DuplicateClaim {
+                            detected: launchers.is_detected(key),
+                            manifest_paths: manifest.0[key].files.keys().cloned().collect(),
+                        },
+                    );
+                }
                 let config = std::sync::Arc::new(self.config.clone());
-                let roots = std::sync::Arc::new(config.expanded_roots());
+                let root_filters = std::sync::Arc::new(Root::path_filters(&roots));
+                let roots = std::sync::Arc::new(roots);
                 let layout = std::sync::Arc::new(*layout);
                 let launchers = std::sync::Arc::new(launchers);
                 let filter = std::sync::Arc::new(self.config.backup.filter.clone());
@@ -431,6 +463,7 @@ impl App {
                     let game = manifest.0[&key].clone();
                     let config = config.clone();
                     let roots = roots.clone();
+                    let root_filters = root_filters.clone();
                     let launchers = launchers.clone();
                     let layout = layout.clone();
                     let filter = filter.clone();
@@ -454,11 +487,21 @@ impl App {
                                 log::trace!("[{key}] excluded by backup filter");
                                 return (None, None);
                             }
+                            if filter.excludes_uninstalled(
+                                games_specified,
+                                previous.is_some(),
+                                config.is_game_customized(&key),
+                                launchers.is_detected(&key),
+                            ) {
+                                log::trace!("[{key}] excluded because it is not installed");
+                                return (None, None);
+                            }
 
                             let scan_info = scan_game_for_backup(
                                 &game,
                                 &key,
                                 &roots,
+                                &root_filters,
                                 &app_dir(),
                                 &launchers,
                                 &filter,
@@ -470,20 +513,8 @@ impl App {
                                 &config.redirects,
                                 &steam_shortcuts,
                             );
-                            if !config.is_game_enabled_for_backup(&key) && full {
-                                return (Some(scan_info), None);
-                            }
-
-                            let backup_info = if !preview {
-                                Some(layout.game_layout(&key).back_up(
-                                    &scan_info,
-                                    &chrono::Utc::now(),
-                                    &config.backup.format,
-                                ))
-                            } else {
-                                None
-                            };
-                            (Some(scan_info), backup_info)
+                            // The actual backup happens in `BackUpScanned`.
+                            (Some(scan_info), None)
                         },
                         move |(scan_info, backup_info)| {
                             Message::Backup(BackupPhase::GameScanned { scan_info, backup_info })
@@ -512,6 +543,12 @@ impl App {
                             self.config
                                 .is_game_enabled_for_operation(&scan_info.game_name, restoring),
                         );
+                        if !self.operation.preview()
+                            && scan_info.found_anything()
+                            && (!full || self.config.is_game_enabled_for_backup(&scan_info.game_name))
+                        {
+                            self.pending_backups.push(scan_info.clone());
+                        }
                         self.backup_screen.previewed_games.insert(scan_info.game_name.clone());
                         self.backup_screen.log.update_game(
                             scan_info,
@@ -543,6 +580,76 @@ impl App {
                     );
                 }
 
+                match self.operation_steps.pop() {
+                    Some(step) => step,
+                    None => {
+                        self.operation_steps_active -= 1;
+                        if self.operation_steps_active == 0 {
+                            self.handle_backup(BackupPhase::BackUpScanned)
+                        } else {
+                            Command::none()
+                        }
+                    }
+                }
+            }
+            BackupPhase::BackUpScanned => {
+                let pending = std::mem::take(&mut self.pending_backups);
+                if pending.is_empty() || self.operation_should_cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                    return self.handle_backup(BackupPhase::CloudSync);
+                }
+
+                self.progress.start();
+                self.progress.set_max(pending.len() as f32);
+
+                let mut retention = self.config.backup.retention.clone();
+                retention.force_new_full = self.operation.should_force_new_full_backups();
+                let layout = std::sync::Arc::new(BackupLayout::new(self.config.backup.path.clone(), retention));
+                let format = std::sync::Arc::new(self.config.backup.format.clone());
+                let duplicate_detector = std::sync::Arc::new(self.backup_screen.duplicate_detector.clone());
+
+                for mut scan_info in pending {
+                    let layout = layout.clone();
+                    let format = format.clone();
+                    let duplicate_detector = duplicate_detector.clone();
+                    let cancel_flag = self.operation_should_cancel.clone();
+                    self.operation_steps.push(Command::perform(
+                        async move {
+                            if cancel_flag.load(std::sync::atomic::Ordering::Relaxed) {
+                                // TODO: https://github.com/hecrj/iced/issues/436
+                                std::thread::sleep(Duration::from_millis(1));
+                                return (scan_info, None);
+                            }
+
+                            duplicate_detector.apply_ownership(&mut scan_info);
+                            let backup_info = layout.game_layout(&scan_info.game_name).back_up(
+                                &scan_info,
+                                &chrono::Utc::now(),
+                                &format,
+                            );
+                            (scan_info, Some(backup_info))
+                        },
+                        move |(scan_info, backup_info)| {
+                            Message::Backup(BackupPhase::GameBackedUp { scan_info, backup_info })
+                        },
+                    ));
+                }
+
+                self.operation_steps_active = 100.min(self.operation_steps.len());
+                Command::batch(self.operation_steps.drain(..self.operation_steps_active))
+            }
+            BackupPhase::GameBackedUp { scan_info, backup_info } => {
+                self.progress.step();
+                self.backup_screen.log.update_game(
+                    scan_info,
+                    backup_info,
+                    &self.config.backup.sort,
+                    &self.backup_screen.duplicate_detector,
+                    &Default::default(),
+                    None,
+                    &self.config,
+                    false,
+                );
+
                 match self.operation_steps.pop() {
                     Some(step) => step,
                     None => {
@@ -659,6 +766,18 @@ impl App {
                     });
                 }
 
+                if !preview {
+                    match OperationLock::try_acquire() {
+                        Some(lock) => self.operation_lock = Some(lock),
+                        None => {
+                            return self.show_modal(Modal::Error {
+                                variant: Error::OperationLocked,
+                            })
+                        }
+                    }
+                }
+
+                self.restore_screen.log.reload_sessions();
                 let mut cleared_log = false;
                 if games.is_none() {
                     self.restore_screen.log.clear();
@@ -712,16 +831,22 @@ impl App {
                     async move {
                         let layout = BackupLayout::new(restore_path, config.backup.retention.clone());
                         let restorables = layout.restorable_games();
-                        (layout, restorables)
+                        let roots = config.expanded_roots();
+                        (layout, restorables, roots)
                     },
-                    move |(layout, restorables)| {
-                        Message::Restore(RestorePhase::RegisterCommands { layout, restorables })
+                    move |(layout, restorables, roots)| {
+                        Message::Restore(RestorePhase::RegisterCommands {
+                            layout,
+                            restorables,
+                            roots,
+                        })
                     },
                 )
             }
             RestorePhase::RegisterCommands {
                 mut restorables,
                 layout,
+                roots,
             } => {
                 log::info!("beginning restore with {} steps", restorables.len());
                 let preview = self.operation.preview();
@@ -763,10 +888,14 @@ impl App {
 
                 let config = std::sync::Arc::new(self.config.clone());
                 let layout = std::sync::Arc::new(layout);
+                let roots = std::sync::Arc::new(roots);
+                let users = std::sync::Arc::new(self.config.users.restorable());
 
                 for name in restorables {
                     let config = config.clone();
                     let layout = layout.clone();
+                    let roots = roots.clone();
+                    let users = users.clone();
                     let cancel_flag = self.operation_should_cancel.clone();
                     let backup_id = self.backups_to_restore.get(&name).cloned().unwrap_or(BackupId::Latest);
                     self.operation_steps.push(Command::perform(
@@ -779,13 +908,15 @@ impl App {
                                 return (None, None, layout);
                             }
 
-                            let scan_info = layout.scan_for_restoration(
+                            let mut scan_info = layout.scan_for_restoration(
                                 &name,
                                 &backup_id,
                                 &config.redirects,
                                 &config.restore.toggled_paths,
                                 &config.restore.toggled_registry,
                             );
+                            scan_info.ignore_unselected_steam_accounts(&roots);
+                            scan_info.ignore_other_users(&users);
                             if !config.is_game_enabled_for_restore(&name) && full {
                                 return (Some(scan_info), None, layout);
                             }
@@ -1468,6 +1599,7 @@ impl Application for App {
                         self.text_histories.roots.push(RootHistory {
                             path: TextHistory::raw(&path),
                             lutris_database: TextHistory::raw(&lutris_database),
+                            ..Default::default()
                         });
                         self.config.roots.push(root);
                     }
@@ -1537,6 +1669,46 @@ impl Application for App {
                 self.save_config();
                 Command::none()
             }
+            Message::EditedRootEnabled(index, enabled) => {
+                self.config.roots[index].options_mut().enabled = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::EditedRootIgnoredPath(root_index, action) => {
+                let history = &mut self.text_histories.roots[root_index].ignored_paths;
+                let ignored_paths = &mut self.config.roots[root_index].options_mut().ignored_paths;
+                match action {
+                    EditAction::Add => {
+                        history.push(Default::default());
+                        ignored_paths.push("".to_string());
+                    }
+                    EditAction::Change(index, value) => {
+                        history[index].push(&value);
+                        ignored_paths[index] = value;
+                    }
+                    EditAction::Remove(index) => {
+                        history.remove(index);
+                        ignored_paths.remove(index);
+                    }
+                    EditAction::Move(index, direction) => {
+                        let offset = direction.shift(index);
+                        history.swap(index, offset);
+                        ignored_paths.swap(index, offset);
+                    }
+                }
+                self.save_config();
+                Command::none()
+            }
+            Message::EditedRootTimeout(index, timeout) => {
+                self.config.roots[index].options_mut().timeout = (timeout > 0).then_some(timeout);
+                self.save_config();
+                Command::none()
+            }
+            Message::EditedRootMaxDepth(index, max_depth) => {
+                self.config.roots[index].options_mut().max_depth = (max_depth > 0).then_some(max_depth);
+                self.save_config();
+                Command::none()
+            }
             Message::SelectedRedirectKind(index, kind) => {
                 self.config.redirects[index].kind = kind;
                 self.save_config();
@@ -1692,11 +1864,26 @@ impl Application for App {
                 self.save_config();
                 Command::none()
             }
+            Message::EditedInstalledOnly(enabled) => {
+                self.config.backup.filter.installed_only = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::SelectedSymlinkPolicy(policy) => {
+                self.config.backup.filter.symlinks = policy;
+                self.save_config();
+                Command::none()
+            }
             Message::EditedCloudFilter(filter) => {
                 self.config.backup.filter.cloud = filter;
                 self.save_config();
                 Command::none()
             }
+            Message::EditedTagFilter(filter) => {
+                self.config.backup.filter.tags = filter;
+                self.save_config();
+                Command::none()
+            }
             Message::EditedBackupFilterIgnoredPath(action) => {
                 match action {
                     EditAction::Add => {
@@ -2207,6 +2394,10 @@ impl Application for App {
                             )
                         }
                     }
+                    UndoSubject::RootIgnoredPath(i, j) => shortcut.apply_to_string_field(
+                        &mut self.config.roots[i].options_mut().ignored_paths[j],
+                        &mut self.text_histories.roots[i].ignored_paths[j],
+                    ),
                     UndoSubject::SecondaryManifest(i) => {
                         let history = &mut self.text_histories.secondary_manifests[i];
                         match shortcut {
@@ -12,8 +12,8 @@ use crate::{
     prelude::{CommandError, Error, Finality, Privacy, StrictPath, SyncDirection},
     resource::{
         config::{
-            BackupFormat, CloudFilter, CustomGameKind, RedirectKind, Root, SecondaryManifestConfigKind, SortKey, Theme,
-            ZipCompression,
+            BackupFormat, CloudFilter, CustomGameKind, RedirectKind, Root, SecondaryManifestConfigKind, SortKey,
+            SymlinkPolicy, TagFilter, Theme, ZipCompression,
         },
         manifest::{self, Manifest, ManifestUpdate, Store},
     },
@@ -49,11 +49,19 @@ pub enum BackupPhase {
         layout: Box<BackupLayout>,
         steam: SteamShortcuts,
         launchers: Launchers,
+        roots: Vec<Root>,
     },
     GameScanned {
         scan_info: Option<ScanInfo>,
         backup_info: Option<BackupInfo>,
     },
+    /// Ownership of duplicated files has to be decided before anything is backed up,
+    /// so we wait until every game has been scanned.
+    BackUpScanned,
+    GameBackedUp {
+        scan_info: ScanInfo,
+        backup_info: Option<BackupInfo>,
+    },
     CloudSync,
     Done,
 }
@@ -72,6 +80,7 @@ pub enum RestorePhase {
     RegisterCommands {
         restorables: Vec<String>,
         layout: BackupLayout,
+        roots: Vec<Root>,
     },
     GameScanned {
         scan_info: Option<ScanInfo>,
@@ -125,6 +134,10 @@ pub enum Message {
     EditedRootLutrisDatabase(usize, String),
     EditedSecondaryManifest(EditAction),
     SelectedRootStore(usize, Store),
+    EditedRootEnabled(usize, bool),
+    EditedRootIgnoredPath(usize, EditAction),
+    EditedRootTimeout(usize, u64),
+    EditedRootMaxDepth(usize, usize),
     SelectedRedirectKind(usize, RedirectKind),
     SelectedSecondaryManifestKind(usize, SecondaryManifestConfigKind),
     SelectedCustomGameKind(usize, CustomGameKind),
@@ -135,7 +148,10 @@ pub enum Message {
     EditedCustomGameFile(usize, EditAction),
     EditedCustomGameRegistry(usize, EditAction),
     EditedExcludeStoreScreenshots(bool),
+    EditedInstalledOnly(bool),
+    SelectedSymlinkPolicy(SymlinkPolicy),
     EditedCloudFilter(CloudFilter),
+    EditedTagFilter(TagFilter),
     EditedBackupFilterIgnoredPath(EditAction),
     EditedBackupFilterIgnoredRegistry(EditAction),
     SwitchScreen(Screen),
@@ -699,6 +715,7 @@ pub enum UndoSubject {
     RestoreSearchGameName,
     RootPath(usize),
     RootLutrisDatabase(usize),
+    RootIgnoredPath(usize, usize),
     SecondaryManifest(usize),
     RedirectSource(usize),
     RedirectTarget(usize),
@@ -725,6 +742,7 @@ impl UndoSubject {
             | UndoSubject::RestoreSearchGameName
             | UndoSubject::RootPath(_)
             | UndoSubject::RootLutrisDatabase(_)
+            | UndoSubject::RootIgnoredPath(_, _)
             | UndoSubject::SecondaryManifest(_)
             | UndoSubject::RedirectSource(_)
             | UndoSubject::RedirectTarget(_)
@@ -10,12 +10,12 @@ use crate::{
         common::{BackupPhase, BrowseFileSubject, BrowseSubject, Message, ScrollSubject, UndoSubject},
         shortcuts::TextHistories,
         style,
-        widget::{checkbox, pick_list, text, Column, Container, IcedParentExt, Row, Tooltip},
+        widget::{checkbox, number_input, pick_list, text, Column, Container, IcedParentExt, Row, Tooltip},
     },
     lang::TRANSLATOR,
     resource::{
         cache::Cache,
-        config::{Config, CustomGameKind, RedirectKind, SecondaryManifestConfigKind},
+        config::{Config, CustomGameKind, RedirectKind, Root, SecondaryManifestConfigKind},
         manifest::Store,
     },
 };
@@ -36,6 +36,19 @@ pub fn root<'a>(config: &Config, histories: &TextHistories, modifiers: &keyboard
                             .spacing(20)
                             .push(button::move_up(Message::EditedRoot, i))
                             .push(button::move_down(Message::EditedRoot, i, config.roots.len()))
+                            .push(
+                                Tooltip::new(
+                                    checkbox("", root.is_enabled(), move |enabled| {
+                                        Message::EditedRootEnabled(i, enabled)
+                                    })
+                                    .spacing(0)
+                                    .style(style::Checkbox),
+                                    text(TRANSLATOR.enabled_label()).size(16),
+                                    tooltip::Position::Top,
+                                )
+                                .gap(5)
+                                .style(style::Container::Tooltip),
+                            )
                             .push(histories.input(UndoSubject::RootPath(i)))
                             .push(
                                 pick_list(Store::ALL, Some(root.store()), move |v| {
@@ -50,26 +63,42 @@ pub fn root<'a>(config: &Config, histories: &TextHistories, modifiers: &keyboard
                         Row::new()
                             .spacing(20)
                             .align_items(Alignment::Center)
-                            .push(horizontal_space().width(70))
+                            .push(horizontal_space().width(106))
                             .push(text(TRANSLATOR.field("pga.db")))
                             .push(histories.input(UndoSubject::RootLutrisDatabase(i)))
                             .push(button::choose_file(BrowseFileSubject::RootLutrisDatabase(i), modifiers)),
-                    ),
-                _ => parent.push(
-                    Row::new()
-                        .spacing(20)
-                        .push(button::move_up(Message::EditedRoot, i))
-                        .push(button::move_down(Message::EditedRoot, i, config.roots.len()))
-                        .push(histories.input(UndoSubject::RootPath(i)))
-                        .push(
-                            pick_list(Store::ALL, Some(root.store()), move |v| {
-                                Message::SelectedRootStore(i, v)
-                            })
-                            .style(style::PickList::Primary),
-                        )
-                        .push(button::choose_folder(BrowseSubject::Root(i), modifiers))
-                        .push(button::remove(Message::EditedRoot, i)),
-                ),
+                    )
+                    .push(root_options(i, root, histories)),
+                _ => parent
+                    .push(
+                        Row::new()
+                            .spacing(20)
+                            .push(button::move_up(Message::EditedRoot, i))
+                            .push(button::move_down(Message::EditedRoot, i, config.roots.len()))
+                            .push(
+                                Tooltip::new(
+                                    checkbox("", root.is_enabled(), move |enabled| {
+                                        Message::EditedRootEnabled(i, enabled)
+                                    })
+                                    .spacing(0)
+                                    .style(style::Checkbox),
+                                    text(TRANSLATOR.enabled_label()).size(16),
+                                    tooltip::Position::Top,
+                                )
+                                .gap(5)
+                                .style(style::Container::Tooltip),
+                            )
+                            .push(histories.input(UndoSubject::RootPath(i)))
+                            .push(
+                                pick_list(Store::ALL, Some(root.store()), move |v| {
+                                    Message::SelectedRootStore(i, v)
+                                })
+                                .style(style::PickList::Primary),
+                            )
+                            .push(button::choose_folder(BrowseSubject::Root(i), modifiers))
+                            .push(button::remove(Message::EditedRoot, i)),
+                    )
+                    .push(root_options(i, root, histories)),
             });
     };
 
@@ -83,6 +112,60 @@ pub fn root<'a>(config: &Config, histories: &TextHistories, modifiers: &keyboard
     Container::new(content)
 }
 
+fn root_options<'a>(i: usize, root: &Root, histories: &TextHistories) -> Row<'a> {
+    let options = root.options();
+
+    Row::new().spacing(20).push(horizontal_space().width(106)).push(
+        Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .spacing(20)
+                    .align_items(Alignment::Center)
+                    .push(number_input(
+                        options.timeout.unwrap_or(0) as i32,
+                        TRANSLATOR.root_timeout_label(),
+                        0..=600,
+                        move |x| Message::EditedRootTimeout(i, x as u64),
+                    ))
+                    .push(number_input(
+                        options.max_depth.unwrap_or(0) as i32,
+                        TRANSLATOR.root_max_depth_label(),
+                        0..=100,
+                        move |x| Message::EditedRootMaxDepth(i, x as usize),
+                    )),
+            )
+            .push(
+                Row::new()
+                    .spacing(20)
+                    .push(text(TRANSLATOR.root_ignored_paths_label()))
+                    .push(
+                        options
+                            .ignored_paths
+                            .iter()
+                            .enumerate()
+                            .fold(Column::new().spacing(4), |column, (ii, _)| {
+                                column.push(
+                                    Row::new()
+                                        .align_items(Alignment::Center)
+                                        .spacing(20)
+                                        .push(button::move_up_nested(Message::EditedRootIgnoredPath, i, ii))
+                                        .push(button::move_down_nested(
+                                            Message::EditedRootIgnoredPath,
+                                            i,
+                                            ii,
+                                            options.ignored_paths.len(),
+                                        ))
+                                        .push(histories.input(UndoSubject::RootIgnoredPath(i, ii)))
+                                        .push(button::remove_nested(Message::EditedRootIgnoredPath, i, ii)),
+                                )
+                            })
+                            .push(button::add_nested(Message::EditedRootIgnoredPath, i)),
+                    ),
+            ),
+    )
+}
+
 pub fn manifest<'a>(
     config: &Config,
     cache: &'a Cache,
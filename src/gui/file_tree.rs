@@ -56,6 +56,7 @@ struct FileTreeNode {
     error: Option<String>,
     ignored: bool,
     duplicated: Duplication,
+    owner: Option<String>,
     change: ScanChange,
     scanned_file: Option<ScannedFile>,
     node_type: FileTreeNodeType,
@@ -78,6 +79,7 @@ impl FileTreeNode {
             error: None,
             ignored,
             duplicated: Default::default(),
+            owner: None,
             change: Default::default(),
             scanned_file: None,
             node_type,
@@ -171,6 +173,11 @@ impl FileTreeNode {
                             .faded(self.duplicated.resolved())
                             .view()
                     })
+                    .push_maybe(
+                        self.owner
+                            .as_ref()
+                            .map(|x| Badge::new(&TRANSLATOR.badge_owned_by(x)).view()),
+                    )
                     .push_maybe(
                         self.error
                             .as_ref()
@@ -189,6 +196,18 @@ impl FileTreeNode {
                             })
                         })
                     })
+                    .push_maybe({
+                        self.scanned_file
+                            .as_ref()
+                            .filter(|f| f.from_launcher)
+                            .map(|_| Badge::new(&TRANSLATOR.badge_from_launcher()).view())
+                    })
+                    .push_maybe({
+                        self.scanned_file.as_ref().filter(|f| !f.tags.is_empty()).map(|f| {
+                            let tags = f.tags.iter().map(|x| TRANSLATOR.tag(x)).collect::<Vec<_>>().join(", ");
+                            Badge::new(&tags).view()
+                        })
+                    })
                     .push_maybe({
                         self.scanned_file.as_ref().map(|f| {
                             let size = TRANSLATOR.adjusted_size(f.size);
@@ -332,6 +351,9 @@ impl FileTreeNode {
 
         node.error = error.map(|x| x.message());
         node.duplicated = duplicated;
+        node.owner = scanned_file
+            .as_ref()
+            .and_then(|x| duplicate_detector.file_owner(x).cloned());
         node.change = change;
         node.scanned_file = scanned_file;
 
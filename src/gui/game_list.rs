@@ -19,6 +19,8 @@ use crate::{
         cache::Cache,
         config::{Config, Sort},
         manifest::{self, Manifest, Os},
+        sessions::{Session, Sessions},
+        ResourceFile,
     },
     scan::{game_filter, layout::GameLayout, BackupInfo, DuplicateDetector, OperationStatus, ScanChange, ScanInfo},
 };
@@ -47,6 +49,7 @@ impl GameListEntry {
         modifiers: &Modifiers,
         filtering_duplicates: bool,
         histories: &TextHistories,
+        last_session: Option<&Session>,
     ) -> Container {
         let successful = match &self.backup_info {
             Some(x) => x.successful(),
@@ -169,6 +172,23 @@ impl GameListEntry {
                                     .style(style::Container::Tooltip)
                                 })
                         })
+                        .push_maybe({
+                            last_session.map(|session| {
+                                Tooltip::new(
+                                    Icon::PlayCircleOutline.text().width(Length::Shrink),
+                                    text(format!(
+                                        "{}: {} ({})",
+                                        TRANSLATOR.last_played_label(),
+                                        session.start.with_timezone(&chrono::Local).format("%Y-%m-%dT%H:%M:%S"),
+                                        TRANSLATOR.session_duration(session.duration()),
+                                    ))
+                                    .size(16),
+                                    tooltip::Position::Top,
+                                )
+                                .gap(5)
+                                .style(style::Container::Tooltip)
+                            })
+                        })
                         .push_maybe({
                             manifest.0.get(&name).and_then(|data| {
                                 (!restoring && !data.notes.is_empty())
@@ -184,6 +204,13 @@ impl GameListEntry {
                                     (os != Os::HOST && os != Os::Other).then(|| Badge::new(&format!("{os:?}")).view())
                                 })
                         })
+                        .push_maybe({
+                            self.scan_info.backup.as_ref().and_then(|backup| {
+                                backup
+                                    .snapshot()
+                                    .then(|| Badge::new(&TRANSLATOR.badge_snapshot()).view())
+                            })
+                        })
                         .push_maybe({
                             self.scan_info
                                 .backup
@@ -385,6 +412,7 @@ pub struct GameList {
     pub search: FilterComponent,
     expanded_games: HashSet<String>,
     pub filter_duplicates_of: Option<String>,
+    sessions: Sessions,
 }
 
 impl GameList {
@@ -460,6 +488,7 @@ impl GameList {
                                     modifiers,
                                     duplicatees.is_some(),
                                     histories,
+                                    self.sessions.latest(&x.scan_info.game_name),
                                 ))
                             },
                         );
@@ -539,6 +568,15 @@ impl GameList {
         self.expanded_games.clear();
     }
 
+    /// Play sessions are recorded by the `wrap` command in a separate process,
+    /// so we check for new ones whenever a scan starts.
+    pub fn reload_sessions(&mut self) {
+        match Sessions::load() {
+            Ok(sessions) => self.sessions = sessions,
+            Err(e) => log::warn!("Unable to load sessions: {:?}", e),
+        }
+    }
+
     pub fn with_recent_games(restoring: bool, config: &Config, cache: &Cache) -> Self {
         let games = if restoring {
             &cache.restore.recent_games
@@ -552,6 +590,7 @@ impl GameList {
         };
 
         let mut log = Self::default();
+        log.reload_sessions();
         for game in games {
             log.update_game(
                 ScanInfo {
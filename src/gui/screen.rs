@@ -19,8 +19,10 @@ use crate::{
     prelude::{AVAILABLE_PARALELLISM, STEAM_DECK},
     resource::{
         cache::Cache,
-        config::{BackupFormat, CloudFilter, Config, SortKey, Theme, ZipCompression},
-        manifest::{Manifest, Store},
+        config::{
+            BackupFormat, CloudFilter, Config, SortKey, SymlinkPolicy, TagFilter, Theme, UntaggedPolicy, ZipCompression,
+        },
+        manifest::{Manifest, Store, Tag},
     },
     scan::{DuplicateDetector, Duplication, OperationStatus},
 };
@@ -397,6 +399,28 @@ pub fn other<'a>(
                                     )
                                     .style(style::Checkbox),
                                 )
+                                .push(
+                                    checkbox(
+                                        TRANSLATOR.explanation_for_installed_only(),
+                                        config.backup.filter.installed_only,
+                                        Message::EditedInstalledOnly,
+                                    )
+                                    .style(style::Checkbox),
+                                )
+                                .push(
+                                    Row::new()
+                                        .spacing(5)
+                                        .align_items(Alignment::Center)
+                                        .push(text(TRANSLATOR.symlinks_field()))
+                                        .push(
+                                            pick_list(
+                                                SymlinkPolicy::ALL,
+                                                Some(config.backup.filter.symlinks),
+                                                Message::SelectedSymlinkPolicy,
+                                            )
+                                            .style(style::PickList::Primary),
+                                        ),
+                                )
                                 .push(checkbox(
                                     TRANSLATOR.show_deselected_games(),
                                     config.scan.show_deselected_games,
@@ -495,6 +519,41 @@ pub fn other<'a>(
                                             )
                                             .style(style::Checkbox),
                                         ),
+                                )
+                                .push(text(TRANSLATOR.field(&TRANSLATOR.explanation_for_exclude_tags())))
+                                .push(Tag::ALL.iter().fold(
+                                    Row::new().padding([0, 0, 0, 35]).spacing(10),
+                                    |row, tag| {
+                                        row.push(
+                                            checkbox(
+                                                TRANSLATOR.tag(tag),
+                                                config.backup.filter.tags.exclude.contains(tag),
+                                                move |excluded| {
+                                                    let mut filter = config.backup.filter.tags.clone();
+                                                    filter.set_excluded(*tag, excluded);
+                                                    Message::EditedTagFilter(filter)
+                                                },
+                                            )
+                                            .style(style::Checkbox),
+                                        )
+                                    },
+                                ))
+                                .push(
+                                    checkbox(
+                                        TRANSLATOR.explanation_for_exclude_untagged(),
+                                        config.backup.filter.tags.untagged == UntaggedPolicy::Exclude,
+                                        |exclude| {
+                                            Message::EditedTagFilter(TagFilter {
+                                                untagged: if exclude {
+                                                    UntaggedPolicy::Exclude
+                                                } else {
+                                                    UntaggedPolicy::Include
+                                                },
+                                                ..config.backup.filter.tags.clone()
+                                            })
+                                        },
+                                    )
+                                    .style(style::Checkbox),
                                 ),
                         )
                         .style(style::Container::GameListEntry),
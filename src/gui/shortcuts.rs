@@ -188,6 +188,7 @@ impl TextHistory {
 pub struct RootHistory {
     pub path: TextHistory,
     pub lutris_database: TextHistory,
+    pub ignored_paths: Vec<TextHistory>,
 }
 
 impl RootHistory {
@@ -256,6 +257,7 @@ impl TextHistories {
             histories.roots.push(RootHistory {
                 path: TextHistory::path(x.path()),
                 lutris_database: x.lutris_database().map(TextHistory::path).unwrap_or_default(),
+                ignored_paths: x.options().ignored_paths.iter().map(|x| TextHistory::raw(x)).collect(),
             });
         }
 
@@ -320,6 +322,11 @@ impl TextHistories {
                 .get(i)
                 .map(|x| x.lutris_database.current())
                 .unwrap_or_default(),
+            UndoSubject::RootIgnoredPath(i, j) => self
+                .roots
+                .get(i)
+                .and_then(|x| x.ignored_paths.get(j).map(|y| y.current()))
+                .unwrap_or_default(),
             UndoSubject::SecondaryManifest(i) => {
                 self.secondary_manifests.get(i).map(|x| x.current()).unwrap_or_default()
             }
@@ -376,6 +383,9 @@ impl TextHistories {
             }),
             UndoSubject::RootPath(i) => Box::new(move |value| Message::EditedRoot(EditAction::Change(i, value))),
             UndoSubject::RootLutrisDatabase(i) => Box::new(move |value| Message::EditedRootLutrisDatabase(i, value)),
+            UndoSubject::RootIgnoredPath(i, j) => {
+                Box::new(move |value| Message::EditedRootIgnoredPath(i, EditAction::Change(j, value)))
+            }
             UndoSubject::SecondaryManifest(i) => {
                 Box::new(move |value| Message::EditedSecondaryManifest(EditAction::Change(i, value)))
             }
@@ -427,6 +437,7 @@ impl TextHistories {
             UndoSubject::RestoreSearchGameName => TRANSLATOR.search_game_name_placeholder(),
             UndoSubject::RootPath(_) => "".to_string(),
             UndoSubject::RootLutrisDatabase(_) => "".to_string(),
+            UndoSubject::RootIgnoredPath(_, _) => "".to_string(),
             UndoSubject::SecondaryManifest(_) => "".to_string(),
             UndoSubject::RedirectSource(_) => TRANSLATOR.redirect_source_placeholder(),
             UndoSubject::RedirectTarget(_) => TRANSLATOR.redirect_target_placeholder(),
@@ -461,6 +472,7 @@ impl TextHistories {
                 side: text_input::Side::Right,
             }),
             UndoSubject::SecondaryManifest(_)
+            | UndoSubject::RootIgnoredPath(_, _)
             | UndoSubject::BackupSearchGameName
             | UndoSubject::RestoreSearchGameName
             | UndoSubject::CustomGameName(_)
@@ -1,4 +1,4 @@
-use std::sync::Mutex;
+use std::{collections::BTreeSet, sync::Mutex};
 
 use fluent::{bundle::FluentBundle, FluentArgs, FluentResource};
 use intl_memoizer::concurrent::IntlLangMemoizer;
@@ -9,8 +9,8 @@ use unic_langid::LanguageIdentifier;
 use crate::{
     prelude::{CommandError, Error, StrictPath, VARIANT},
     resource::{
-        config::{BackupFormat, CustomGameKind, RedirectKind, Root, SortKey, Theme, ZipCompression},
-        manifest::Store,
+        config::{BackupFormat, CustomGameKind, RedirectKind, Root, SortKey, SymlinkPolicy, Theme, ZipCompression},
+        manifest::{Store, Tag},
     },
     scan::{game_filter, BackupError, OperationStatus, OperationStepDecision, ScanChange},
 };
@@ -29,6 +29,7 @@ const CODE: &str = "code";
 const MESSAGE: &str = "message";
 const APP: &str = "app";
 const GAME: &str = "game";
+const ACCOUNT: &str = "account";
 const VERSION: &str = "version";
 
 pub const TRANSLATOR: Translator = Translator {};
@@ -383,6 +384,7 @@ impl Translator {
             Error::RegistryIssue => self.registry_issue(),
             Error::UnableToBrowseFileSystem => self.unable_to_browse_file_system(),
             Error::UnableToOpenDir(path) => self.unable_to_open_dir(path),
+            Error::UnableToWriteFile(path) => self.unable_to_write_file(path),
             Error::UnableToOpenUrl(url) => self.unable_to_open_url(url),
             Error::RcloneUnavailable => self.rclone_unavailable(),
             Error::CloudNotConfigured => self.cloud_not_configured(),
@@ -403,6 +405,11 @@ impl Translator {
             }
             Error::CloudConflict => TRANSLATOR.prefix_error(&TRANSLATOR.cloud_synchronize_conflict()),
             Error::GameDidNotLaunch { why } => format!("{}\n\n{}", self.game_did_not_launch(), self.prefix_error(why)),
+            Error::WrappedGameFailed { code } => self.game_exited_with_code(*code),
+            Error::OperationLocked => self.prefix_error(&self.operation_locked()),
+            Error::UnableToWatchFiles { why } => {
+                format!("{}\n\n{}", self.unable_to_watch_files(), self.prefix_error(why))
+            }
         }
     }
 
@@ -459,6 +466,12 @@ impl Translator {
         translate("cli-invalid-backup-id")
     }
 
+    pub fn cli_watching_games(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL_GAMES, total);
+        translate_args("cli-watching-games", &args)
+    }
+
     pub fn cloud_not_configured(&self) -> String {
         translate("cloud-not-configured")
     }
@@ -523,6 +536,14 @@ impl Translator {
         translate("badge-ignored")
     }
 
+    pub fn badge_from_launcher(&self) -> String {
+        translate("badge-from-launcher")
+    }
+
+    pub fn badge_snapshot(&self) -> String {
+        translate("badge-snapshot")
+    }
+
     pub fn badge_redirected_from(&self, original: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, original.render());
@@ -535,6 +556,12 @@ impl Translator {
         translate_args("badge-redirecting-to", &args)
     }
 
+    pub fn badge_owned_by(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("badge-owned-by", &args)
+    }
+
     pub fn cli_game_header(
         &self,
         name: &str,
@@ -601,6 +628,18 @@ impl Translator {
         }
     }
 
+    pub fn cli_game_line_item_tagged(&self, item: &str, tags: &BTreeSet<Tag>) -> String {
+        if tags.is_empty() {
+            item.to_string()
+        } else {
+            format!(
+                "{} ({})",
+                item,
+                tags.iter().map(|x| self.tag(x)).collect::<Vec<_>>().join(", ")
+            )
+        }
+    }
+
     pub fn cli_game_line_item_redirected(&self, item: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, item);
@@ -613,6 +652,26 @@ impl Translator {
         format!("    - {}", translate_args("cli-game-line-item-redirecting", &args),)
     }
 
+    pub fn cli_game_line_item_owned_by(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        format!("    - {}", translate_args("cli-game-line-item-owned-by", &args))
+    }
+
+    pub fn cli_game_line_item_steam_account(&self, account: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(ACCOUNT, account);
+        format!("    - {}", translate_args("cli-game-line-item-steam-account", &args))
+    }
+
+    pub fn cli_game_line_item_from_launcher(&self) -> String {
+        format!("    - {}", translate("cli-game-line-item-from-launcher"))
+    }
+
+    pub fn steam_account_most_recent(&self) -> String {
+        translate("steam-account-most-recent")
+    }
+
     pub fn cli_game_line_item_error(&self, error: &BackupError) -> String {
         format!("    - {}", error.message())
     }
@@ -842,6 +901,10 @@ impl Translator {
         format!("{}\n\n{}", translate("unable-to-open-directory"), path.resolve())
     }
 
+    pub fn unable_to_write_file(&self, path: &StrictPath) -> String {
+        format!("{}\n\n{}", translate("unable-to-write-file"), path.resolve())
+    }
+
     pub fn unable_to_open_url(&self, url: &str) -> String {
         format!("{}\n\n{}", translate("unable-to-open-url"), url)
     }
@@ -858,6 +921,14 @@ impl Translator {
         translate("cloud-synchronize-conflict")
     }
 
+    pub fn operation_locked(&self) -> String {
+        translate("operation-locked")
+    }
+
+    pub fn unable_to_watch_files(&self) -> String {
+        translate("unable-to-watch-files")
+    }
+
     pub fn adjusted_size(&self, bytes: u64) -> String {
         let byte = byte_unit::Byte::from(bytes);
         let adjusted_byte = byte.get_appropriate_unit(byte_unit::UnitType::Binary);
@@ -910,17 +981,36 @@ impl Translator {
         translate("field-custom-registry")
     }
 
+    pub fn root_ignored_paths_label(&self) -> String {
+        translate("field-root-ignored-paths")
+    }
+
+    pub fn root_timeout_label(&self) -> String {
+        translate("field-root-timeout")
+    }
+
+    pub fn root_max_depth_label(&self) -> String {
+        translate("field-root-max-depth")
+    }
+
+    pub fn enabled_label(&self) -> String {
+        translate("label-enabled")
+    }
+
     pub fn sort_label(&self) -> String {
         translate("field-sort")
     }
 
     pub fn store(&self, store: &Store) -> String {
         translate(match store {
+            Store::Bottles => "store-bottles",
             Store::Ea => "store-ea",
+            Store::Emulator => "store-emulator",
             Store::Epic => "store-epic",
             Store::Gog => "store-gog",
             Store::GogGalaxy => "store-gog-galaxy",
             Store::Heroic => "store-heroic",
+            Store::Itch => "store-itch",
             Store::Legendary => "store-legendary",
             Store::Lutris => "store-lutris",
             Store::Microsoft => "store-microsoft",
@@ -937,6 +1027,15 @@ impl Translator {
         })
     }
 
+    pub fn tag(&self, tag: &Tag) -> String {
+        translate(match tag {
+            Tag::Save => "tag-save",
+            Tag::Config => "tag-config",
+            Tag::Screenshot => "tag-screenshot",
+            Tag::Other => "tag-other",
+        })
+    }
+
     pub fn sort_key(&self, key: &SortKey) -> String {
         translate(match key {
             SortKey::Name => "game-name",
@@ -991,6 +1090,14 @@ impl Translator {
         })
     }
 
+    pub fn symlink_policy(&self, key: &SymlinkPolicy) -> String {
+        translate(match key {
+            SymlinkPolicy::Follow => "symlinks-follow",
+            SymlinkPolicy::Preserve => "symlinks-preserve",
+            SymlinkPolicy::Skip => "symlinks-skip",
+        })
+    }
+
     pub fn theme_name(&self, theme: &Theme) -> String {
         translate(match theme {
             Theme::Light => "theme-light",
@@ -1034,6 +1141,18 @@ impl Translator {
         translate("label-primary-manifest")
     }
 
+    pub fn restored_label(&self) -> String {
+        translate("label-restored")
+    }
+
+    pub fn backed_up_label(&self) -> String {
+        translate("label-backed-up")
+    }
+
+    pub fn last_played_label(&self) -> String {
+        translate("label-last-played")
+    }
+
     pub fn custom_game_kind(&self, kind: &CustomGameKind) -> String {
         match kind {
             CustomGameKind::Game => self.game_label(),
@@ -1085,6 +1204,18 @@ impl Translator {
         translate("explanation-for-exclude-cloud-games")
     }
 
+    pub fn explanation_for_exclude_tags(&self) -> String {
+        translate("explanation-for-exclude-tags")
+    }
+
+    pub fn explanation_for_exclude_untagged(&self) -> String {
+        translate("explanation-for-exclude-untagged")
+    }
+
+    pub fn explanation_for_installed_only(&self) -> String {
+        translate("explanation-for-installed-only")
+    }
+
     pub fn roots_label(&self) -> String {
         translate("field-roots")
     }
@@ -1117,6 +1248,10 @@ impl Translator {
         translate("field-backup-compression-level")
     }
 
+    pub fn symlinks_field(&self) -> String {
+        translate("field-symlinks")
+    }
+
     pub fn manifest_label(&self) -> String {
         self.field(&translate("label-manifest"))
     }
@@ -1173,6 +1308,10 @@ impl Translator {
         self.field(&translate("label-folder"))
     }
 
+    pub fn flatpak_label(&self) -> String {
+        translate("label-flatpak")
+    }
+
     pub fn executable_label(&self) -> String {
         translate("label-executable")
     }
@@ -1363,6 +1502,24 @@ impl Translator {
         translate("game-did-not-launch")
     }
 
+    pub fn game_exited_with_code(&self, code: i32) -> String {
+        let mut args = FluentArgs::new();
+        args.set(CODE, code);
+        translate_args("game-exited-with-code", &args)
+    }
+
+    pub fn session_exit_code(&self, code: i32) -> String {
+        let mut args = FluentArgs::new();
+        args.set(CODE, code);
+        translate_args("session-exit-code", &args)
+    }
+
+    /// Format a play session's duration as `H:MM:SS`.
+    pub fn session_duration(&self, duration: chrono::Duration) -> String {
+        let seconds = duration.num_seconds().max(0);
+        format!("{}:{:02}:{:02}", seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+    }
+
     pub fn back_up_one_game_confirm(&self, game: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(GAME, game);
@@ -1381,6 +1538,31 @@ impl Translator {
         translate_args("restore-specific-game.confirm", &args)
     }
 
+    pub fn restore_one_game_overwrite_newer_confirm(&self, game: &str, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        args.set(TOTAL, total);
+        translate_args("restore-specific-game.overwrite-newer-confirm", &args)
+    }
+
+    pub fn cloud_game_newer_confirm(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("cloud-specific-game.newer-confirm", &args)
+    }
+
+    pub fn cloud_game_diverged_confirm(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("cloud-specific-game.diverged-confirm", &args)
+    }
+
+    pub fn cloud_game_overwrite_confirm(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("cloud-specific-game.overwrite-confirm", &args)
+    }
+
     pub fn restore_one_game_failed(&self, game: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(GAME, game);
@@ -147,7 +147,7 @@ fn main() {
 
             if let Err(e) = cli::run(sub, args.no_manifest_update, args.try_manifest_update) {
                 eprintln!("{}", TRANSLATOR.handle_error(&e));
-                std::process::exit(1);
+                std::process::exit(e.exit_code());
             }
         }
     };
@@ -444,6 +444,72 @@ impl StrictPath {
         self.is_file() || self.is_dir()
     }
 
+    /// This does not follow the link, so it also works for dangling links.
+    pub fn is_symlink(&self) -> bool {
+        self.as_unresolved_std_path_buf()
+            .map(|x| x.is_symlink())
+            .unwrap_or_default()
+    }
+
+    /// Where a symbolic link points, exactly as stored in the link.
+    pub fn read_link(&self) -> Option<String> {
+        let target = std::fs::read_link(self.as_unresolved_std_path_buf()?).ok()?;
+        Some(target.to_string_lossy().to_string())
+    }
+
+    /// Unlike `as_std_path_buf`, this does not canonicalize the path,
+    /// so a symbolic link refers to the link itself rather than its target.
+    fn as_unresolved_std_path_buf(&self) -> Option<std::path::PathBuf> {
+        self.access().ok().map(std::path::PathBuf::from)
+    }
+
+    fn try_unresolved_std_path_buf(&self) -> Result<std::path::PathBuf, std::io::Error> {
+        self.as_unresolved_std_path_buf()
+            .ok_or_else(|| std::io::Error::other(format!("Cannot interpret path: {:?}", &self)))
+    }
+
+    /// Replace this path with a symbolic link.
+    /// An existing folder is only replaced if it is empty.
+    pub fn create_symlink(&self, target: &str) -> Result<(), AnyError> {
+        self.create_parent_dir()?;
+
+        // We replace whatever is at this exact path, so we must not follow an existing link.
+        let link = self.try_unresolved_std_path_buf()?;
+        match std::fs::symlink_metadata(&link) {
+            Ok(metadata) if metadata.is_symlink() => {
+                if std::fs::remove_file(&link).is_err() {
+                    // Directory links on Windows need to be removed as directories.
+                    std::fs::remove_dir(&link)?;
+                }
+            }
+            Ok(metadata) if metadata.is_dir() => {
+                std::fs::remove_dir(&link)?;
+            }
+            Ok(_) => {
+                self.unset_readonly()?;
+                std::fs::remove_file(&link)?;
+            }
+            Err(_) => {}
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let resolved = match link.parent() {
+                Some(parent) => parent.join(target),
+                None => std::path::PathBuf::from(target),
+            };
+            if resolved.is_dir() {
+                std::os::windows::fs::symlink_dir(target, &link)?;
+            } else {
+                std::os::windows::fs::symlink_file(target, &link)?;
+            }
+        }
+        #[cfg(not(target_os = "windows"))]
+        std::os::unix::fs::symlink(target, &link)?;
+
+        Ok(())
+    }
+
     pub fn metadata(&self) -> std::io::Result<std::fs::Metadata> {
         self.as_std_path_buf()?.metadata()
     }
@@ -500,7 +566,13 @@ impl StrictPath {
     }
 
     pub fn remove(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if self.is_file() {
+        if self.is_symlink() {
+            // Remove the link itself rather than its target.
+            let link = self.try_unresolved_std_path_buf()?;
+            if std::fs::remove_file(&link).is_err() {
+                std::fs::remove_dir(&link)?;
+            }
+        } else if self.is_file() {
             std::fs::remove_file(self.as_std_path_buf()?)?;
         } else if self.is_dir() {
             std::fs::remove_dir_all(self.as_std_path_buf()?)?;
@@ -645,8 +717,7 @@ impl StrictPath {
     }
 
     pub fn leaf(&self) -> Option<String> {
-        self.as_std_path_buf()
-            .ok()?
+        self.as_unresolved_std_path_buf()?
             .file_name()
             .map(|x| x.to_string_lossy().to_string())
     }
@@ -777,6 +848,54 @@ impl StrictPath {
         Ok(())
     }
 
+    /// Wine folds case, so a game may have created a folder in its prefix
+    /// with different capitalization than the manifest uses.
+    /// This returns the path using the case of any folders/files that already exist
+    /// inside of the Wine prefix, or an unchanged path if it's not in a prefix.
+    pub fn with_wine_prefix_case(&self) -> Self {
+        if !Os::HOST.is_case_sensitive() {
+            return self.clone();
+        }
+
+        let Ok(rendered) = self.interpret() else {
+            return self.clone();
+        };
+        let Some(index) = wine_drive_index(&rendered) else {
+            return self.clone();
+        };
+
+        let mut resolved = std::path::PathBuf::from(&rendered[..index]);
+        let mut components = rendered[index..].split('/').filter(|x| !x.is_empty());
+        for component in components.by_ref() {
+            let exact = resolved.join(component);
+            if exact.exists() {
+                resolved = exact;
+                continue;
+            }
+
+            let existing = std::fs::read_dir(&resolved).ok().and_then(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .find(|name| name.eq_ignore_ascii_case(component))
+            });
+            match existing {
+                Some(name) => {
+                    resolved.push(name);
+                }
+                None => {
+                    resolved.push(component);
+                    break;
+                }
+            }
+        }
+        for component in components {
+            resolved.push(component);
+        }
+
+        Self::from(resolved)
+    }
+
     pub fn is_prefix_of(&self, other: &Self) -> bool {
         let us = self.analyze();
         let them = other.analyze();
@@ -792,6 +911,14 @@ impl StrictPath {
         us.parts.iter().zip(them.parts.iter()).all(|(us, them)| us == them)
     }
 
+    /// How many levels below this path the other path is, if it is nested within this one.
+    pub fn depth_below(&self, other: &Self) -> Option<usize> {
+        if !self.is_prefix_of(other) {
+            return None;
+        }
+        Some(other.analyze().parts.len() - self.analyze().parts.len())
+    }
+
     pub fn nearest_prefix(&self, others: Vec<StrictPath>) -> Option<StrictPath> {
         let us = self.analyze();
         let us_count = us.parts.len();
@@ -1012,6 +1139,54 @@ pub fn is_raw_path_relative(path: &str) -> bool {
     path.is_empty() || path == "." || path == ".." || path.starts_with("./") || path.starts_with("../")
 }
 
+/// Find where a Wine drive folder's name (e.g., `drive_c`) begins in a path.
+/// Everything before this index is the prefix itself, including the trailing slash.
+pub fn wine_drive_index(path: &str) -> Option<usize> {
+    path.match_indices("/drive_").find_map(|(i, _)| {
+        let mut rest = path[i + 7..].chars();
+        match (rest.next(), rest.next()) {
+            (Some(letter), Some('/') | None) if letter.is_ascii_alphabetic() => Some(i + 1),
+            _ => None,
+        }
+    })
+}
+
+/// Make the part of a glob pattern starting at `from` match letters of either case,
+/// while the rest of the pattern keeps its normal case sensitivity.
+/// Existing character classes (like an escaped `[[]`) are left as-is.
+pub fn fold_glob_case(pattern: &str, from: usize) -> String {
+    let (kept, folded) = pattern.split_at(from);
+    let mut out = kept.to_string();
+
+    let mut chars = folded.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '[' {
+            out.push(c);
+            if let Some(negation) = chars.next_if_eq(&'!') {
+                out.push(negation);
+            }
+            if let Some(bracket) = chars.next_if_eq(&']') {
+                out.push(bracket);
+            }
+            for c in chars.by_ref() {
+                out.push(c);
+                if c == ']' {
+                    break;
+                }
+            }
+        } else if c.is_ascii_alphabetic() {
+            out.push('[');
+            out.push(c.to_ascii_lowercase());
+            out.push(c.to_ascii_uppercase());
+            out.push(']');
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1100,6 +1275,42 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_find_wine_drive_index() {
+            assert_eq!(Some(8), wine_drive_index("/prefix/drive_c/users"));
+            assert_eq!(Some(8), wine_drive_index("/prefix/drive_d"));
+            assert_eq!(None, wine_drive_index("/prefix/drive_cd/users"));
+            assert_eq!(None, wine_drive_index("/prefix/users"));
+        }
+
+        #[test]
+        fn can_fold_glob_case() {
+            assert_eq!(
+                "/Prefix/[dD][rR][iI][vV][eE]_*/[fF]1 [[][xX]]",
+                fold_glob_case("/Prefix/drive_*/f1 [[]x]", 8)
+            );
+            assert_eq!("/[aA][!b]/[cC]", fold_glob_case("/a[!b]/c", 0));
+        }
+
+        #[test]
+        #[cfg(target_os = "linux")]
+        fn can_use_existing_case_in_wine_prefix() {
+            let base = format!("{}/tests/wine-prefix-case", repo());
+            assert_eq!(
+                StrictPath::from(format!("{base}/drive_c/users/Me/my games/Foo/save.dat")),
+                StrictPath::new(format!("{base}/drive_c/USERS/me/My Games/foo/SAVE.dat")).with_wine_prefix_case(),
+            );
+            assert_eq!(
+                StrictPath::from(format!("{base}/drive_c/users/Me/my games/New/Folder/save.dat")),
+                StrictPath::new(format!("{base}/drive_c/users/me/My Games/New/Folder/save.dat"))
+                    .with_wine_prefix_case(),
+            );
+            assert_eq!(
+                StrictPath::new(format!("{base}/Drive/save.dat")),
+                StrictPath::new(format!("{base}/Drive/save.dat")).with_wine_prefix_case(),
+            );
+        }
+
         #[test]
         fn checks_if_files_are_identical() {
             assert!(StrictPath::new(format!("{}/tests/root2/game1/file1.txt", repo()))
@@ -1179,6 +1390,43 @@ mod tests {
                 StrictPath::new("/foo".into()).replace(&StrictPath::new("/foo".into()), &StrictPath::new("".into())),
             );
         }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_remove_symlink_without_removing_target() {
+            let dir = crate::testing::scratch_dir("remove-symlink");
+            let target = dir.joined("target.txt");
+            target.write_with_content("real").unwrap();
+            let link = dir.joined("link.txt");
+            link.create_symlink(&target.interpret().unwrap()).unwrap();
+
+            link.remove().unwrap();
+
+            assert!(!link.is_symlink());
+            assert_eq!(Some(s("real")), target.read());
+
+            let _ = dir.remove();
+        }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_replace_symlink_without_removing_target() {
+            let dir = crate::testing::scratch_dir("replace-symlink");
+            let old = dir.joined("old.txt");
+            old.write_with_content("old").unwrap();
+            let new = dir.joined("new.txt");
+            new.write_with_content("new").unwrap();
+            let link = dir.joined("link.txt");
+            link.create_symlink(&old.interpret().unwrap()).unwrap();
+
+            link.create_symlink(&new.interpret().unwrap()).unwrap();
+
+            assert_eq!(Some(new.interpret().unwrap()), link.read_link());
+            assert_eq!(Some(s("old")), old.read());
+            assert_eq!(Some(s("new")), new.read());
+
+            let _ = dir.remove();
+        }
     }
 
     mod strict_path_display_and_access {
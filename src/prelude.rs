@@ -30,6 +30,7 @@ pub type AnyError = Box<dyn std::error::Error>;
 pub const SKIP: &str = "<skip>";
 pub const APP_DIR_NAME: &str = "ludusavi";
 const PORTABLE_FLAG_FILE_NAME: &str = "ludusavi.portable";
+const LOCK_FILE_NAME: &str = "ludusavi.lock";
 pub const INVALID_FILE_CHARS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|', '\0'];
 
 pub static STEAM_DECK: Lazy<bool> =
@@ -70,6 +71,82 @@ impl Privacy {
     }
 }
 
+/// The thread holding the operation lock and how many times it has acquired it,
+/// so that nested operations (e.g., a scheduled backup in the daemon) don't lock themselves out.
+static OPERATION_LOCK_OWNER: Mutex<Option<(std::thread::ThreadId, usize)>> = Mutex::new(None);
+
+/// An exclusive lock on the application folder,
+/// held while backing up or restoring so that the daemon and GUI don't run at the same time.
+/// The lock is released when this is dropped.
+#[derive(Debug)]
+pub struct OperationLock(Option<std::fs::File>);
+
+impl OperationLock {
+    /// Returns `None` if another process or thread is holding the lock.
+    /// The same thread may acquire the lock again while it already holds it.
+    pub fn try_acquire() -> Option<Self> {
+        let thread = std::thread::current().id();
+        let mut owner = OPERATION_LOCK_OWNER.lock().unwrap();
+        if let Some((id, count)) = owner.as_mut() {
+            if *id == thread {
+                *count += 1;
+                return Some(Self(None));
+            }
+        }
+
+        let lock = Self::try_acquire_file()?;
+        *owner = Some((thread, 1));
+        Some(lock)
+    }
+
+    fn try_acquire_file() -> Option<Self> {
+        let path = app_dir().joined(LOCK_FILE_NAME);
+        if let Err(e) = path.create_parent_dir() {
+            log::warn!("Unable to prepare lock file {:?}: {:?}", &path, e);
+        }
+
+        let file = match path.as_std_path_buf().and_then(|path| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(path)
+        }) {
+            Ok(file) => file,
+            Err(e) => {
+                // If we can't even create the file, then no one else can hold the lock either.
+                log::warn!("Unable to open lock file {:?}: {:?}", &path, e);
+                return Some(Self(None));
+            }
+        };
+
+        match fs2::FileExt::try_lock_exclusive(&file) {
+            Ok(()) => Some(Self(Some(file))),
+            Err(e) => {
+                log::info!("Lock file {:?} is held by another process: {:?}", &path, e);
+                None
+            }
+        }
+    }
+}
+
+impl Drop for OperationLock {
+    fn drop(&mut self) {
+        if let Ok(mut owner) = OPERATION_LOCK_OWNER.lock() {
+            if let Some((_, count)) = owner.as_mut() {
+                *count -= 1;
+                if *count == 0 {
+                    *owner = None;
+                }
+            }
+        }
+
+        if let Some(file) = &self.0 {
+            let _ = fs2::FileExt::unlock(file);
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SyncDirection {
     Upload,
@@ -105,6 +182,7 @@ pub enum Error {
     RegistryIssue,
     UnableToBrowseFileSystem,
     UnableToOpenDir(StrictPath),
+    UnableToWriteFile(StrictPath),
     UnableToOpenUrl(String),
     RcloneUnavailable,
     CloudNotConfigured,
@@ -115,6 +193,22 @@ pub enum Error {
     GameDidNotLaunch {
         why: String,
     },
+    WrappedGameFailed {
+        code: i32,
+    },
+    OperationLocked,
+    UnableToWatchFiles {
+        why: String,
+    },
+}
+
+impl Error {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::WrappedGameFailed { code } => *code,
+            _ => 1,
+        }
+    }
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CommandError {
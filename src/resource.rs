@@ -1,6 +1,7 @@
 pub mod cache;
 pub mod config;
 pub mod manifest;
+pub mod sessions;
 
 use crate::prelude::{app_dir, AnyError, StrictPath};
 
@@ -6,16 +6,18 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use once_cell::sync::Lazy;
+
 use crate::{
     cloud::Remote,
     lang::{Language, TRANSLATOR},
     path::CommonPath,
     prelude::{app_dir, Error, StrictPath, AVAILABLE_PARALELLISM},
     resource::{
-        manifest::{self, CloudMetadata, Manifest, Store},
+        manifest::{self, CloudMetadata, Manifest, Store, Tag},
         ResourceFile, SaveableResourceFile,
     },
-    scan::registry_compat::RegistryItem,
+    scan::{registry_compat::RegistryItem, steam, users, wine},
 };
 
 pub const MANIFEST_URL: &str =
@@ -41,6 +43,9 @@ pub struct Config {
     pub scan: Scan,
     pub cloud: Cloud,
     pub apps: Apps,
+    pub users: Users,
+    pub wrap: WrapConfig,
+    pub daemon: DaemonConfig,
     pub custom_games: Vec<CustomGame>,
 }
 
@@ -269,11 +274,14 @@ impl ToString for Theme {
 )]
 #[serde(tag = "store", rename_all = "camelCase")]
 pub enum Root {
+    Bottles(root::Bottles),
     Ea(root::Ea),
+    Emulator(root::Emulator),
     Epic(root::Epic),
     Gog(root::Gog),
     GogGalaxy(root::GogGalaxy),
     Heroic(root::Heroic),
+    Itch(root::Itch),
     Legendary(root::Legendary),
     Lutris(root::Lutris),
     Microsoft(root::Microsoft),
@@ -295,40 +303,159 @@ impl Default for Root {
     }
 }
 
+/// How long to trust that a root responded before checking it again.
+const ROOT_PROBE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+enum RootProbe {
+    /// The last check finished in time.
+    Responsive(std::time::Instant),
+    /// The last check is still waiting for the root to respond.
+    Pending(std::sync::mpsc::Receiver<bool>),
+}
+
+static ROOT_PROBES: Lazy<Mutex<HashMap<StrictPath, RootProbe>>> = Lazy::new(Default::default);
+
+/// A root's path settings, compiled once so that checking many paths stays cheap.
+/// Create these once per scan, since the root's timeout starts counting down right away.
+#[derive(Clone, Debug)]
+pub struct RootPathFilter {
+    root: Root,
+    ignored_paths: Option<globset::GlobSet>,
+    /// When the root's timeout runs out for the current scan.
+    deadline: Option<std::time::Instant>,
+}
+
+impl RootPathFilter {
+    pub fn is_ignored(&self, path: &StrictPath) -> bool {
+        if self.root.is_steam_account_excluded(path) {
+            return true;
+        }
+
+        let max_depth = self.root.options().max_depth;
+        if self.ignored_paths.is_none() && max_depth.is_none() {
+            return false;
+        }
+
+        let Some(depth) = self.root.path().depth_below(path) else {
+            return false;
+        };
+
+        if max_depth.is_some_and(|max| depth > max) {
+            return true;
+        }
+
+        self.ignored_paths
+            .as_ref()
+            .is_some_and(|set| set.is_match(path.render()))
+    }
+
+    /// Whether we've spent longer than the root's timeout on a path within it.
+    pub fn is_expired(&self, path: &StrictPath) -> bool {
+        self.deadline
+            .is_some_and(|deadline| std::time::Instant::now() > deadline && self.root.path().is_prefix_of(path))
+    }
+}
+
 impl Root {
     pub fn new(path: impl Into<StrictPath>, store: Store) -> Self {
         match store {
-            Store::Ea => Self::Ea(root::Ea { path: path.into() }),
-            Store::Epic => Self::Epic(root::Epic { path: path.into() }),
-            Store::Gog => Self::Gog(root::Gog { path: path.into() }),
-            Store::GogGalaxy => Self::GogGalaxy(root::GogGalaxy { path: path.into() }),
-            Store::Heroic => Self::Heroic(root::Heroic { path: path.into() }),
-            Store::Legendary => Self::Legendary(root::Legendary { path: path.into() }),
+            Store::Bottles => Self::Bottles(root::Bottles {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Ea => Self::Ea(root::Ea {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Emulator => Self::Emulator(root::Emulator {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Epic => Self::Epic(root::Epic {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Gog => Self::Gog(root::Gog {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::GogGalaxy => Self::GogGalaxy(root::GogGalaxy {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Heroic => Self::Heroic(root::Heroic {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Itch => Self::Itch(root::Itch {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Legendary => Self::Legendary(root::Legendary {
+                path: path.into(),
+                ..Default::default()
+            }),
             Store::Lutris => Self::Lutris(root::Lutris {
                 path: path.into(),
-                database: None,
+                ..Default::default()
+            }),
+            Store::Microsoft => Self::Microsoft(root::Microsoft {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Origin => Self::Origin(root::Origin {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Prime => Self::Prime(root::Prime {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Steam => Self::Steam(root::Steam {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Uplay => Self::Uplay(root::Uplay {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::OtherHome => Self::OtherHome(root::OtherHome {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::OtherWine => Self::OtherWine(root::OtherWine {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::OtherWindows => Self::OtherWindows(root::OtherWindows {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::OtherLinux => Self::OtherLinux(root::OtherLinux {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::OtherMac => Self::OtherMac(root::OtherMac {
+                path: path.into(),
+                ..Default::default()
+            }),
+            Store::Other => Self::Other(root::Other {
+                path: path.into(),
+                ..Default::default()
             }),
-            Store::Microsoft => Self::Microsoft(root::Microsoft { path: path.into() }),
-            Store::Origin => Self::Origin(root::Origin { path: path.into() }),
-            Store::Prime => Self::Prime(root::Prime { path: path.into() }),
-            Store::Steam => Self::Steam(root::Steam { path: path.into() }),
-            Store::Uplay => Self::Uplay(root::Uplay { path: path.into() }),
-            Store::OtherHome => Self::OtherHome(root::OtherHome { path: path.into() }),
-            Store::OtherWine => Self::OtherWine(root::OtherWine { path: path.into() }),
-            Store::OtherWindows => Self::OtherWindows(root::OtherWindows { path: path.into() }),
-            Store::OtherLinux => Self::OtherLinux(root::OtherLinux { path: path.into() }),
-            Store::OtherMac => Self::OtherMac(root::OtherMac { path: path.into() }),
-            Store::Other => Self::Other(root::Other { path: path.into() }),
         }
     }
 
     pub fn store(&self) -> Store {
         match self {
+            Self::Bottles(_) => Store::Bottles,
             Self::Ea(_) => Store::Ea,
+            Self::Emulator(_) => Store::Emulator,
             Self::Epic(_) => Store::Epic,
             Self::Gog(_) => Store::Gog,
             Self::GogGalaxy(_) => Store::GogGalaxy,
             Self::Heroic(_) => Store::Heroic,
+            Self::Itch(_) => Store::Itch,
             Self::Legendary(_) => Store::Legendary,
             Self::Lutris(_) => Store::Lutris,
             Self::Microsoft(_) => Store::Microsoft,
@@ -347,58 +474,228 @@ impl Root {
 
     pub fn path(&self) -> &StrictPath {
         match self {
-            Self::Ea(root::Ea { path }) => path,
-            Self::Epic(root::Epic { path }) => path,
-            Self::Gog(root::Gog { path }) => path,
-            Self::GogGalaxy(root::GogGalaxy { path }) => path,
-            Self::Heroic(root::Heroic { path }) => path,
-            Self::Legendary(root::Legendary { path }) => path,
+            Self::Bottles(root::Bottles { path, .. }) => path,
+            Self::Ea(root::Ea { path, .. }) => path,
+            Self::Emulator(root::Emulator { path, .. }) => path,
+            Self::Epic(root::Epic { path, .. }) => path,
+            Self::Gog(root::Gog { path, .. }) => path,
+            Self::GogGalaxy(root::GogGalaxy { path, .. }) => path,
+            Self::Heroic(root::Heroic { path, .. }) => path,
+            Self::Itch(root::Itch { path, .. }) => path,
+            Self::Legendary(root::Legendary { path, .. }) => path,
             Self::Lutris(root::Lutris { path, .. }) => path,
-            Self::Microsoft(root::Microsoft { path }) => path,
-            Self::Origin(root::Origin { path }) => path,
-            Self::Prime(root::Prime { path }) => path,
-            Self::Steam(root::Steam { path }) => path,
-            Self::Uplay(root::Uplay { path }) => path,
-            Self::OtherHome(root::OtherHome { path }) => path,
-            Self::OtherWine(root::OtherWine { path }) => path,
-            Self::OtherWindows(root::OtherWindows { path }) => path,
-            Self::OtherLinux(root::OtherLinux { path }) => path,
-            Self::OtherMac(root::OtherMac { path }) => path,
-            Self::Other(root::Other { path }) => path,
+            Self::Microsoft(root::Microsoft { path, .. }) => path,
+            Self::Origin(root::Origin { path, .. }) => path,
+            Self::Prime(root::Prime { path, .. }) => path,
+            Self::Steam(root::Steam { path, .. }) => path,
+            Self::Uplay(root::Uplay { path, .. }) => path,
+            Self::OtherHome(root::OtherHome { path, .. }) => path,
+            Self::OtherWine(root::OtherWine { path, .. }) => path,
+            Self::OtherWindows(root::OtherWindows { path, .. }) => path,
+            Self::OtherLinux(root::OtherLinux { path, .. }) => path,
+            Self::OtherMac(root::OtherMac { path, .. }) => path,
+            Self::Other(root::Other { path, .. }) => path,
         }
     }
 
     pub fn path_mut(&mut self) -> &mut StrictPath {
         match self {
-            Self::Ea(root::Ea { path }) => path,
-            Self::Epic(root::Epic { path }) => path,
-            Self::Gog(root::Gog { path }) => path,
-            Self::GogGalaxy(root::GogGalaxy { path }) => path,
-            Self::Heroic(root::Heroic { path }) => path,
-            Self::Legendary(root::Legendary { path }) => path,
+            Self::Bottles(root::Bottles { path, .. }) => path,
+            Self::Ea(root::Ea { path, .. }) => path,
+            Self::Emulator(root::Emulator { path, .. }) => path,
+            Self::Epic(root::Epic { path, .. }) => path,
+            Self::Gog(root::Gog { path, .. }) => path,
+            Self::GogGalaxy(root::GogGalaxy { path, .. }) => path,
+            Self::Heroic(root::Heroic { path, .. }) => path,
+            Self::Itch(root::Itch { path, .. }) => path,
+            Self::Legendary(root::Legendary { path, .. }) => path,
             Self::Lutris(root::Lutris { path, .. }) => path,
-            Self::Microsoft(root::Microsoft { path }) => path,
-            Self::Origin(root::Origin { path }) => path,
-            Self::Prime(root::Prime { path }) => path,
-            Self::Steam(root::Steam { path }) => path,
-            Self::Uplay(root::Uplay { path }) => path,
-            Self::OtherHome(root::OtherHome { path }) => path,
-            Self::OtherWine(root::OtherWine { path }) => path,
-            Self::OtherWindows(root::OtherWindows { path }) => path,
-            Self::OtherLinux(root::OtherLinux { path }) => path,
-            Self::OtherMac(root::OtherMac { path }) => path,
-            Self::Other(root::Other { path }) => path,
+            Self::Microsoft(root::Microsoft { path, .. }) => path,
+            Self::Origin(root::Origin { path, .. }) => path,
+            Self::Prime(root::Prime { path, .. }) => path,
+            Self::Steam(root::Steam { path, .. }) => path,
+            Self::Uplay(root::Uplay { path, .. }) => path,
+            Self::OtherHome(root::OtherHome { path, .. }) => path,
+            Self::OtherWine(root::OtherWine { path, .. }) => path,
+            Self::OtherWindows(root::OtherWindows { path, .. }) => path,
+            Self::OtherLinux(root::OtherLinux { path, .. }) => path,
+            Self::OtherMac(root::OtherMac { path, .. }) => path,
+            Self::Other(root::Other { path, .. }) => path,
         }
     }
 
     pub fn with_path(&self, path: StrictPath) -> Self {
+        let mut root = self.clone();
+        *root.path_mut() = path;
+        root
+    }
+
+    pub fn options(&self) -> &root::Options {
         match self {
-            Self::Lutris(root::Lutris { database, .. }) => Self::Lutris(root::Lutris {
-                path,
-                database: database.clone(),
-            }),
-            _ => Self::new(path, self.store()),
+            Self::Bottles(root::Bottles { options, .. }) => options,
+            Self::Ea(root::Ea { options, .. }) => options,
+            Self::Emulator(root::Emulator { options, .. }) => options,
+            Self::Epic(root::Epic { options, .. }) => options,
+            Self::Gog(root::Gog { options, .. }) => options,
+            Self::GogGalaxy(root::GogGalaxy { options, .. }) => options,
+            Self::Heroic(root::Heroic { options, .. }) => options,
+            Self::Itch(root::Itch { options, .. }) => options,
+            Self::Legendary(root::Legendary { options, .. }) => options,
+            Self::Lutris(root::Lutris { options, .. }) => options,
+            Self::Microsoft(root::Microsoft { options, .. }) => options,
+            Self::Origin(root::Origin { options, .. }) => options,
+            Self::Prime(root::Prime { options, .. }) => options,
+            Self::Steam(root::Steam { options, .. }) => options,
+            Self::Uplay(root::Uplay { options, .. }) => options,
+            Self::OtherHome(root::OtherHome { options, .. }) => options,
+            Self::OtherWine(root::OtherWine { options, .. }) => options,
+            Self::OtherWindows(root::OtherWindows { options, .. }) => options,
+            Self::OtherLinux(root::OtherLinux { options, .. }) => options,
+            Self::OtherMac(root::OtherMac { options, .. }) => options,
+            Self::Other(root::Other { options, .. }) => options,
+        }
+    }
+
+    pub fn options_mut(&mut self) -> &mut root::Options {
+        match self {
+            Self::Bottles(root::Bottles { options, .. }) => options,
+            Self::Ea(root::Ea { options, .. }) => options,
+            Self::Emulator(root::Emulator { options, .. }) => options,
+            Self::Epic(root::Epic { options, .. }) => options,
+            Self::Gog(root::Gog { options, .. }) => options,
+            Self::GogGalaxy(root::GogGalaxy { options, .. }) => options,
+            Self::Heroic(root::Heroic { options, .. }) => options,
+            Self::Itch(root::Itch { options, .. }) => options,
+            Self::Legendary(root::Legendary { options, .. }) => options,
+            Self::Lutris(root::Lutris { options, .. }) => options,
+            Self::Microsoft(root::Microsoft { options, .. }) => options,
+            Self::Origin(root::Origin { options, .. }) => options,
+            Self::Prime(root::Prime { options, .. }) => options,
+            Self::Steam(root::Steam { options, .. }) => options,
+            Self::Uplay(root::Uplay { options, .. }) => options,
+            Self::OtherHome(root::OtherHome { options, .. }) => options,
+            Self::OtherWine(root::OtherWine { options, .. }) => options,
+            Self::OtherWindows(root::OtherWindows { options, .. }) => options,
+            Self::OtherLinux(root::OtherLinux { options, .. }) => options,
+            Self::OtherMac(root::OtherMac { options, .. }) => options,
+            Self::Other(root::Other { options, .. }) => options,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.options().enabled
+    }
+
+    /// Check whether the root responds within its configured timeout.
+    /// A sleeping drive or network share can otherwise block the whole scan.
+    ///
+    /// A root that did not respond is only checked once in the background
+    /// until it responds, so later calls return right away.
+    pub fn is_responsive(&self) -> bool {
+        let Some(timeout) = self.options().timeout else {
+            return true;
+        };
+        let path = self.path().clone();
+
+        {
+            let mut probes = ROOT_PROBES.lock().unwrap();
+            match probes.get(&path) {
+                Some(RootProbe::Responsive(checked)) if checked.elapsed() < ROOT_PROBE_TTL => return true,
+                Some(RootProbe::Pending(receiver)) => match receiver.try_recv() {
+                    Ok(_) => {
+                        probes.insert(path, RootProbe::Responsive(std::time::Instant::now()));
+                        return true;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                        log::debug!("Root is still not responding, skipping: {:?}", &path);
+                        return false;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
+                },
+                _ => {}
+            }
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let probed = path.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(probed.is_dir());
+        });
+
+        let probe = match receiver.recv_timeout(std::time::Duration::from_secs(timeout)) {
+            Ok(_) => RootProbe::Responsive(std::time::Instant::now()),
+            Err(_) => {
+                log::warn!("Root did not respond within {timeout} seconds, skipping: {:?}", &path);
+                RootProbe::Pending(receiver)
+            }
+        };
+        let responsive = matches!(probe, RootProbe::Responsive(_));
+        ROOT_PROBES.lock().unwrap().insert(path, probe);
+        responsive
+    }
+
+    /// Prepare to check many paths against the root's ignored paths, depth limit, and Steam accounts.
+    pub fn path_filter(&self) -> RootPathFilter {
+        let options = self.options();
+
+        let ignored_paths = if options.ignored_paths.iter().all(|x| x.trim().is_empty()) {
+            None
+        } else {
+            let root = self.path().globbable();
+            let mut builder = globset::GlobSetBuilder::new();
+            for item in options.ignored_paths.iter().filter(|x| !x.trim().is_empty()) {
+                let normalized = format!("{}/{}", root, item.replace('\\', "/").trim_start_matches('/'));
+                for variant in [normalized.clone(), format!("{}/**", normalized)] {
+                    if let Ok(glob) = globset::GlobBuilder::new(&variant)
+                        .literal_separator(true)
+                        .backslash_escape(false)
+                        .case_insensitive(true)
+                        .build()
+                    {
+                        builder.add(glob);
+                    }
+                }
+            }
+            builder.build().ok()
+        };
+
+        RootPathFilter {
+            root: self.clone(),
+            ignored_paths,
+            deadline: options
+                .timeout
+                .map(|timeout| std::time::Instant::now() + std::time::Duration::from_secs(timeout)),
+        }
+    }
+
+    /// Prepare the filters for all roots in a scan.
+    pub fn path_filters(roots: &[Root]) -> Vec<RootPathFilter> {
+        roots.iter().map(|root| root.path_filter()).collect()
+    }
+
+    /// For roots that represent another user's home folder, this is the user's name.
+    pub fn user(&self) -> Option<&str> {
+        match self {
+            Self::OtherHome(root) => root.user.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn steam_accounts(&self) -> &[String] {
+        match self {
+            Self::Steam(root) => &root.accounts,
+            _ => &[],
+        }
+    }
+
+    /// Whether a path is in the `userdata` folder of a Steam account
+    /// that was not selected for this root.
+    pub fn is_steam_account_excluded(&self, path: &StrictPath) -> bool {
+        let accounts = self.steam_accounts();
+        if accounts.is_empty() {
+            return false;
         }
+        steam::account_in_path(self.path(), path).is_some_and(|account| !accounts.contains(&account))
     }
 
     pub fn lutris_database(&self) -> Option<&StrictPath> {
@@ -410,7 +707,9 @@ impl Root {
 
     pub fn set_store(&mut self, store: Store) {
         if self.store() != store {
+            let options = self.options().clone();
             *self = Self::new(self.path().clone(), store);
+            *self.options_mut() = options;
         }
     }
 
@@ -516,12 +815,116 @@ impl CloudFilter {
     }
 }
 
+/// How to handle files that have no tags in the manifest.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum UntaggedPolicy {
+    #[default]
+    Include,
+    Exclude,
+}
+
+impl UntaggedPolicy {
+    pub const ALL_NAMES: &'static [&'static str] = &["include", "exclude"];
+}
+
+impl std::str::FromStr for UntaggedPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "include" => Ok(Self::Include),
+            "exclude" => Ok(Self::Exclude),
+            _ => Err(format!("invalid untagged policy: {}", s)),
+        }
+    }
+}
+
+/// How to handle symbolic links (and junctions on Windows) found during a backup.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// Follow links and back up the content that they point to.
+    #[default]
+    Follow,
+    /// Back up the links themselves, so that they can be recreated on restore.
+    Preserve,
+    /// Ignore links entirely.
+    Skip,
+}
+
+impl SymlinkPolicy {
+    pub const ALL: &'static [Self] = &[Self::Follow, Self::Preserve, Self::Skip];
+    pub const ALL_NAMES: &'static [&'static str] = &["follow", "preserve", "skip"];
+}
+
+impl std::str::FromStr for SymlinkPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "follow" => Ok(Self::Follow),
+            "preserve" => Ok(Self::Preserve),
+            "skip" => Ok(Self::Skip),
+            _ => Err(format!("invalid symlink policy: {}", s)),
+        }
+    }
+}
+
+impl ToString for SymlinkPolicy {
+    fn to_string(&self) -> String {
+        TRANSLATOR.symlink_policy(self)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TagFilter {
+    /// If not empty, only back up files that have at least one of these tags in the manifest.
+    pub include: BTreeSet<Tag>,
+    /// Don't back up files that have any of these tags in the manifest.
+    /// This takes precedence over `include`.
+    pub exclude: BTreeSet<Tag>,
+    /// How to handle files that have no tags in the manifest,
+    /// such as files from custom games.
+    pub untagged: UntaggedPolicy,
+}
+
+impl TagFilter {
+    pub fn allows(&self, tags: &BTreeSet<Tag>) -> bool {
+        if tags.is_empty() {
+            return self.untagged == UntaggedPolicy::Include;
+        }
+
+        if tags.iter().any(|tag| self.exclude.contains(tag)) {
+            return false;
+        }
+
+        self.include.is_empty() || tags.iter().any(|tag| self.include.contains(tag))
+    }
+
+    pub fn set_excluded(&mut self, tag: Tag, excluded: bool) {
+        if excluded {
+            self.exclude.insert(tag);
+        } else {
+            self.exclude.remove(&tag);
+        }
+    }
+}
+
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct BackupFilter {
     /// If true, then the backup should exclude screenshots from stores like Steam.
     pub exclude_store_screenshots: bool,
     pub cloud: CloudFilter,
+    /// Include or exclude files based on their tags in the manifest.
+    pub tags: TagFilter,
+    /// If true, then only back up games that were detected as installed by a launcher,
+    /// as well as custom games and games that already have a backup.
+    pub installed_only: bool,
+    /// How to handle symbolic links.
+    pub symlinks: SymlinkPolicy,
     /// Globally ignored paths.
     pub ignored_paths: Vec<StrictPath>,
     /// Globally ignored registry keys.
@@ -535,6 +938,9 @@ impl std::fmt::Debug for BackupFilter {
         f.debug_struct("BackupFilter")
             .field("exclude_store_screenshots", &self.exclude_store_screenshots)
             .field("cloud", &self.cloud)
+            .field("tags", &self.tags)
+            .field("installed_only", &self.installed_only)
+            .field("symlinks", &self.symlinks)
             .field("ignored_paths", &self.ignored_paths)
             .field("ignored_registry", &self.ignored_registry)
             .finish()
@@ -546,6 +952,9 @@ impl Eq for BackupFilter {}
 impl PartialEq for BackupFilter {
     fn eq(&self, other: &Self) -> bool {
         self.exclude_store_screenshots == other.exclude_store_screenshots
+            && self.tags == other.tags
+            && self.installed_only == other.installed_only
+            && self.symlinks == other.symlinks
             && self.ignored_paths == other.ignored_paths
             && self.ignored_registry == other.ignored_registry
     }
@@ -610,6 +1019,10 @@ impl BackupFilter {
     pub fn excludes(&self, explicit: bool, has_backup: bool, info: &CloudMetadata) -> bool {
         !explicit && self.cloud.excludes(info) && !has_backup
     }
+
+    pub fn excludes_uninstalled(&self, explicit: bool, has_backup: bool, custom: bool, installed: bool) -> bool {
+        self.installed_only && !explicit && !has_backup && !custom && !installed
+    }
 }
 
 /// Allows including/excluding specific file paths.
@@ -787,6 +1200,58 @@ impl Default for Retention {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DuplicateRules {
+    /// Explicit owners for specific paths.
+    /// These take precedence over the other rules.
+    pub owners: Vec<DuplicateOwner>,
+    /// Games that should own any files they share with other games,
+    /// in order of priority.
+    pub preferred_games: Vec<String>,
+    /// Prefer games that were detected as installed by a launcher.
+    pub prefer_detected: bool,
+    /// Prefer the game whose manifest path most specifically matches the file.
+    pub prefer_specific: bool,
+}
+
+impl DuplicateRules {
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty() && self.preferred_games.is_empty() && !self.prefer_detected && !self.prefer_specific
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DuplicateOwner {
+    /// Path to a file or folder. Glob syntax is supported.
+    pub path: StrictPath,
+    /// Name of the game that should own the path.
+    pub game: String,
+}
+
+impl DuplicateOwner {
+    pub fn matches(&self, item: &StrictPath) -> bool {
+        if self.path.raw().trim().is_empty() {
+            return false;
+        }
+
+        let normalized = self.path.globbable();
+        let rendered = item.render();
+        [normalized.clone(), format!("{}/**", &normalized)]
+            .iter()
+            .any(|variant| {
+                globset::GlobBuilder::new(variant)
+                    .literal_separator(true)
+                    .backslash_escape(false)
+                    .case_insensitive(true)
+                    .build()
+                    .map(|glob| glob.compile_matcher().is_match(&rendered))
+                    .unwrap_or(false)
+            })
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum BackupFormat {
@@ -1008,6 +1473,8 @@ pub struct BackupConfig {
     pub sort: Sort,
     pub retention: Retention,
     pub format: BackupFormats,
+    /// Rules for deciding which game owns a file when several games claim it.
+    pub duplicates: DuplicateRules,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -1043,6 +1510,206 @@ impl Default for Scan {
     }
 }
 
+/// Settings for the `wrap` command.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WrapConfig {
+    pub snapshots: Snapshots,
+    /// Decisions to make automatically for specific games, keyed by title.
+    /// Anything not specified here falls back to asking (or to `--force`).
+    pub games: BTreeMap<String, WrapGamePolicies>,
+}
+
+impl WrapConfig {
+    pub fn policies(&self, game: &str) -> WrapGamePolicies {
+        self.games.get(game).cloned().unwrap_or_default()
+    }
+}
+
+/// How `wrap` should handle a specific game.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WrapGamePolicies {
+    /// Whether to restore before launching.
+    /// `always` only applies when the backup is newer than your current saves;
+    /// otherwise, you will still be asked.
+    pub restore: WrapPolicy,
+    /// Whether to back up after the game exits.
+    pub backup: WrapPolicy,
+    /// Whether to launch the game anyway if there is a problem beforehand
+    /// (e.g., the game is unrecognized or has no backups).
+    pub launch_on_error: WrapPolicy,
+}
+
+/// How to answer a question during `wrap`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum WrapPolicy {
+    /// Ask for confirmation, unless `--force` is used.
+    #[default]
+    Ask,
+    /// Always proceed without asking.
+    Always,
+    /// Never proceed, without asking.
+    Never,
+}
+
+impl WrapPolicy {
+    pub const ALL_NAMES: &'static [&'static str] = &["ask", "always", "never"];
+
+    /// Decide the answer for a confirmation,
+    /// given the answer that `--force` would provide when asking.
+    pub fn decide(&self, forced: Option<bool>) -> Option<bool> {
+        match self {
+            Self::Ask => forced,
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+        }
+    }
+
+    /// Decide the answer for a confirmation that would overwrite newer data.
+    /// Only `ask` will prompt; otherwise, we skip the operation without asking,
+    /// because even `always` should not silently overwrite newer data.
+    pub fn decide_overwrite(&self, forced: Option<bool>) -> Option<bool> {
+        match self {
+            Self::Ask => forced,
+            Self::Always | Self::Never => Some(false),
+        }
+    }
+}
+
+impl std::str::FromStr for WrapPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ask" => Ok(Self::Ask),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!("invalid wrap policy: {}", s)),
+        }
+    }
+}
+
+/// Periodic backups while a wrapped game is running.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snapshots {
+    /// How often to take a snapshot, in minutes.
+    /// If unset, then snapshots are disabled.
+    pub interval: Option<u32>,
+    /// Snapshots to keep for each game after a session ends. Range: 0-255.
+    /// These do not count toward the normal differential backup limit.
+    pub retention: u8,
+}
+
+impl Default for Snapshots {
+    fn default() -> Self {
+        Self {
+            interval: None,
+            retention: 3,
+        }
+    }
+}
+
+/// Settings for the `daemon` command.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DaemonConfig {
+    /// When to run backups.
+    pub rules: Vec<DaemonRule>,
+    /// Whether to upload to the cloud after each scheduled backup.
+    /// This follows the same rules as `backup --cloud-sync`.
+    pub cloud_sync: bool,
+}
+
+/// A schedule for backing up some or all games.
+/// Set either `cron` or `interval`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DaemonRule {
+    /// A cron expression, like `0 3 * * *` for every day at 3 AM (local time).
+    /// This also accepts an optional seconds field at the start and a year field at the end.
+    pub cron: Option<String>,
+    /// How often to run, in minutes.
+    pub interval: Option<u32>,
+    /// Which games to back up.
+    /// If empty, then all games are backed up.
+    pub games: Vec<String>,
+}
+
+/// Back up and restore data for other users on this computer.
+/// This generally requires running Ludusavi as an administrator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Users {
+    /// If enabled, then backups will also scan the home folders of other users,
+    /// and restores will write each user's files back to that user's home folder.
+    /// If disabled, then restores will skip files that belong to other users.
+    pub enabled: bool,
+    /// Users to include.
+    /// If this is empty, then Ludusavi will look for users automatically
+    /// (e.g., from `/etc/passwd` on Linux).
+    pub list: Vec<User>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct User {
+    /// OS user name.
+    pub name: String,
+    /// The user's home folder.
+    pub home: StrictPath,
+}
+
+impl Users {
+    /// Users whose files we can restore, including the current user.
+    pub fn restorable(&self) -> Vec<User> {
+        let current = users::current();
+        if !self.enabled {
+            return current.into_iter().collect();
+        }
+
+        let mut users = self.others();
+        users.extend(current);
+        users
+    }
+
+    /// Users to scan in addition to the current user.
+    pub fn others(&self) -> Vec<User> {
+        if !self.enabled {
+            return vec![];
+        }
+
+        let users = if self.list.is_empty() {
+            users::discover()
+        } else {
+            self.list.clone()
+        };
+
+        let home = CommonPath::Home.get().map(|x| StrictPath::new(x.to_string()));
+        users
+            .into_iter()
+            .filter(|user| {
+                user.name != *crate::prelude::OS_USERNAME
+                    && !home.as_ref().is_some_and(|home| home.equivalent(&user.home))
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Cloud {
@@ -1183,6 +1850,7 @@ impl Default for BackupConfig {
             sort: Default::default(),
             retention: Retention::default(),
             format: Default::default(),
+            duplicates: Default::default(),
         }
     }
 }
@@ -1289,6 +1957,24 @@ impl Config {
                 "~/.var/app/com.heroicgameslauncher.hgl/config/heroic".to_string(),
                 Store::Heroic,
             ),
+            // Bottles:
+            (
+                "~/.var/app/com.usebottles.bottles/data/bottles".to_string(),
+                Store::Bottles,
+            ),
+            // Emulators:
+            ("~/.config/retroarch".to_string(), Store::Emulator),
+            (
+                "~/.var/app/org.libretro.RetroArch/config/retroarch".to_string(),
+                Store::Emulator,
+            ),
+            ("~/.local/share/dolphin-emu".to_string(), Store::Emulator),
+            (
+                "~/.var/app/org.DolphinEmu.dolphin-emu/data/dolphin-emu".to_string(),
+                Store::Emulator,
+            ),
+            ("~/.config/PCSX2".to_string(), Store::Emulator),
+            ("~/.var/app/net.pcsx2.PCSX2/config/PCSX2".to_string(), Store::Emulator),
             // Uplay:
             (format!("{}/Ubisoft/Ubisoft Game Launcher", pf32), Store::Uplay),
             (format!("{}/Ubisoft/Ubisoft Game Launcher", pf64), Store::Uplay),
@@ -1307,6 +1993,10 @@ impl Config {
 
         if let Some(data_dir) = CommonPath::Data.get() {
             candidates.push((format!("{}/heroic", data_dir), Store::Heroic));
+            candidates.push((format!("{}/bottles", data_dir), Store::Bottles));
+        }
+        if let Some(config_dir) = CommonPath::Config.get() {
+            candidates.push((format!("{}/itch", config_dir), Store::Itch));
         }
 
         let detected_steam = match steamlocate::SteamDir::locate() {
@@ -1410,10 +2100,16 @@ impl Config {
             roots.push(Root::Lutris(root::Lutris {
                 path: path.clone(),
                 database,
+                ..Default::default()
             }));
             checked.insert(path);
         }
 
+        let known: Vec<_> = self.roots.iter().chain(roots.iter()).cloned().collect();
+        for prefix in wine::discover_prefixes(&known) {
+            roots.push(Root::new(prefix, Store::OtherWine));
+        }
+
         roots
     }
 
@@ -1561,7 +2257,20 @@ impl Config {
             );
         }
 
-        let expanded: Vec<Root> = self.roots.iter().flat_map(|x| x.glob()).collect();
+        let mut expanded: Vec<Root> = self
+            .roots
+            .iter()
+            .filter(|x| x.is_enabled() && x.is_responsive())
+            .flat_map(|x| x.glob())
+            .collect();
+
+        for user in self.users.others() {
+            expanded.push(Root::OtherHome(root::OtherHome {
+                path: user.home,
+                user: Some(user.name),
+                ..Default::default()
+            }));
+        }
 
         for root in &expanded {
             log::trace!(
@@ -1929,6 +2638,7 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    duplicates: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2049,6 +2759,7 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    duplicates: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2078,6 +2789,9 @@ mod tests {
                         arguments: "".to_string(),
                     },
                 },
+                users: Default::default(),
+                wrap: Default::default(),
+                daemon: Default::default(),
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
@@ -2118,8 +2832,17 @@ theme: light
 roots:
   - store: steam
     path: ~/steam
+    accounts: []
+    enabled: true
+    ignoredPaths: []
+    timeout: ~
+    maxDepth: ~
   - store: other
     path: ~/other
+    enabled: true
+    ignoredPaths: []
+    timeout: ~
+    maxDepth: ~
 redirects:
   - kind: restore
     source: ~/old
@@ -2139,6 +2862,12 @@ backup:
       origin: false
       steam: false
       uplay: false
+    tags:
+      include: []
+      exclude: []
+      untagged: include
+    installedOnly: false
+    symlinks: follow
     ignoredPaths: []
     ignoredRegistry: []
   toggledPaths: {}
@@ -2160,6 +2889,11 @@ backup:
         level: 6
       zstd:
         level: 10
+  duplicates:
+    owners: []
+    preferredGames: []
+    preferDetected: false
+    preferSpecific: false
 restore:
   path: ~/restore
   ignoredGames:
@@ -2185,6 +2919,17 @@ apps:
   rclone:
     path: rclone.exe
     arguments: ""
+users:
+  enabled: false
+  list: []
+wrap:
+  snapshots:
+    interval: ~
+    retention: 3
+  games: {}
+daemon:
+  rules: []
+  cloudSync: false
 customGames:
   - name: Custom Game 1
     files: []
@@ -2236,6 +2981,7 @@ customGames:
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    duplicates: Default::default(),
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2266,6 +3012,9 @@ customGames:
                         arguments: "".to_string(),
                     }
                 },
+                users: Default::default(),
+                wrap: Default::default(),
+                daemon: Default::default(),
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
@@ -2298,6 +3047,163 @@ customGames:
         );
     }
 
+    mod tag_filter {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn allows_everything_by_default() {
+            let filter = TagFilter::default();
+            assert!(filter.allows(&btree_set! {}));
+            assert!(filter.allows(&btree_set! { Tag::Save }));
+            assert!(filter.allows(&btree_set! { Tag::Config, Tag::Other }));
+        }
+
+        #[test]
+        fn can_require_included_tags() {
+            let filter = TagFilter {
+                include: btree_set! { Tag::Save, Tag::Config },
+                ..Default::default()
+            };
+            assert!(filter.allows(&btree_set! { Tag::Save }));
+            assert!(filter.allows(&btree_set! { Tag::Config, Tag::Other }));
+            assert!(!filter.allows(&btree_set! { Tag::Other }));
+        }
+
+        #[test]
+        fn prefers_exclusion_over_inclusion() {
+            let filter = TagFilter {
+                include: btree_set! { Tag::Save },
+                exclude: btree_set! { Tag::Config },
+                ..Default::default()
+            };
+            assert!(filter.allows(&btree_set! { Tag::Save }));
+            assert!(!filter.allows(&btree_set! { Tag::Save, Tag::Config }));
+        }
+
+        #[test]
+        fn applies_untagged_policy() {
+            let mut filter = TagFilter {
+                include: btree_set! { Tag::Save },
+                ..Default::default()
+            };
+            assert!(filter.allows(&btree_set! {}));
+
+            filter.untagged = UntaggedPolicy::Exclude;
+            assert!(!filter.allows(&btree_set! {}));
+        }
+
+        #[test]
+        fn can_toggle_exclusion() {
+            let mut filter = TagFilter::default();
+            filter.set_excluded(Tag::Config, true);
+            assert_eq!(btree_set! { Tag::Config }, filter.exclude);
+            filter.set_excluded(Tag::Config, false);
+            assert_eq!(BTreeSet::<Tag>::new(), filter.exclude);
+        }
+    }
+
+    mod wrap_policies {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn can_be_deserialized() {
+            let config = Config::load_from_string(
+                r#"
+                roots: []
+                backup:
+                  path: ~/backup
+                restore:
+                  path: ~/restore
+                wrap:
+                  games:
+                    Foo:
+                      restore: always
+                      launchOnError: never
+                "#,
+            )
+            .unwrap();
+
+            assert_eq!(
+                WrapGamePolicies {
+                    restore: WrapPolicy::Always,
+                    backup: WrapPolicy::Ask,
+                    launch_on_error: WrapPolicy::Never,
+                },
+                config.wrap.policies("Foo"),
+            );
+            assert_eq!(WrapGamePolicies::default(), config.wrap.policies("Bar"));
+        }
+
+        #[test]
+        fn defers_to_force_when_asking() {
+            assert_eq!(None, WrapPolicy::Ask.decide(None));
+            assert_eq!(Some(false), WrapPolicy::Ask.decide(Some(false)));
+            assert_eq!(Some(true), WrapPolicy::Always.decide(Some(false)));
+            assert_eq!(Some(false), WrapPolicy::Never.decide(Some(true)));
+        }
+
+        #[test]
+        fn only_asks_to_overwrite_newer_data_when_asking() {
+            assert_eq!(None, WrapPolicy::Ask.decide_overwrite(None));
+            assert_eq!(Some(false), WrapPolicy::Ask.decide_overwrite(Some(false)));
+            assert_eq!(Some(false), WrapPolicy::Always.decide_overwrite(None));
+            assert_eq!(Some(false), WrapPolicy::Never.decide_overwrite(None));
+        }
+    }
+
+    mod root_options {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        fn root(ignored_paths: Vec<&str>, max_depth: Option<usize>) -> Root {
+            let mut root = Root::new("/games", Store::Other);
+            root.options_mut().ignored_paths = ignored_paths.into_iter().map(s).collect();
+            root.options_mut().max_depth = max_depth;
+            root
+        }
+
+        #[test]
+        fn ignores_nothing_by_default() {
+            let root = root(vec![], None);
+            assert!(!root
+                .path_filter()
+                .is_ignored(&StrictPath::new(s("/games/foo/bar/baz.sav"))));
+        }
+
+        #[test]
+        fn can_ignore_paths_relative_to_root() {
+            let root = root(vec!["foo", "*/cache"], None);
+            assert!(root.path_filter().is_ignored(&StrictPath::new(s("/games/foo"))));
+            assert!(root.path_filter().is_ignored(&StrictPath::new(s("/games/foo/bar.sav"))));
+            assert!(root
+                .path_filter()
+                .is_ignored(&StrictPath::new(s("/games/bar/cache/data.bin"))));
+            assert!(!root
+                .path_filter()
+                .is_ignored(&StrictPath::new(s("/games/bar/save.sav"))));
+            assert!(!root.path_filter().is_ignored(&StrictPath::new(s("/other/foo/bar.sav"))));
+        }
+
+        #[test]
+        fn can_limit_depth() {
+            let root = root(vec![], Some(2));
+            assert!(!root.path_filter().is_ignored(&StrictPath::new(s("/games/a/b"))));
+            assert!(root.path_filter().is_ignored(&StrictPath::new(s("/games/a/b/c"))));
+            assert!(!root.path_filter().is_ignored(&StrictPath::new(s("/other/a/b/c"))));
+        }
+
+        #[test]
+        fn keeps_options_when_changing_store() {
+            let mut root = root(vec!["foo"], Some(1));
+            root.options_mut().enabled = false;
+            root.set_store(Store::Steam);
+            assert!(!root.is_enabled());
+            assert_eq!(Some(1), root.options().max_depth);
+        }
+    }
+
     mod ignored_paths {
         use pretty_assertions::assert_eq;
 
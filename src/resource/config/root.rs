@@ -1,5 +1,58 @@
 use crate::path::StrictPath;
 
+/// Settings that apply to any kind of root.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Options {
+    /// Whether to scan this root.
+    /// You can disable a root temporarily instead of removing it.
+    pub enabled: bool,
+    /// Paths to ignore within this root, relative to the root.
+    /// Glob syntax is supported.
+    pub ignored_paths: Vec<String>,
+    /// If the root does not respond within this many seconds,
+    /// then it will be skipped for the rest of the scan.
+    /// This is useful for network shares and external drives that may be asleep.
+    pub timeout: Option<u64>,
+    /// Skip anything more than this many levels below the root.
+    /// For example, with a limit of 2, `<root>/a/b` is included, but not `<root>/a/b/c`.
+    pub max_depth: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignored_paths: vec![],
+            timeout: None,
+            max_depth: None,
+        }
+    }
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Bottles {
+    /// Where the root is located on your system.
+    pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
+}
+
 #[derive(
     Clone,
     Debug,
@@ -17,6 +70,29 @@ use crate::path::StrictPath;
 pub struct Ea {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Emulator {
+    /// Where the root is located on your system.
+    pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -36,6 +112,8 @@ pub struct Ea {
 pub struct Epic {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -55,6 +133,8 @@ pub struct Epic {
 pub struct Gog {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -74,6 +154,8 @@ pub struct Gog {
 pub struct GogGalaxy {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -93,6 +175,29 @@ pub struct GogGalaxy {
 pub struct Heroic {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Itch {
+    /// Where the root is located on your system.
+    pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -112,6 +217,8 @@ pub struct Heroic {
 pub struct Legendary {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -133,6 +240,8 @@ pub struct Lutris {
     pub path: StrictPath,
     /// Full path to the Lutris `pga.db` file, if not contained within the main `path`.
     pub database: Option<StrictPath>,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -152,6 +261,8 @@ pub struct Lutris {
 pub struct Microsoft {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -171,6 +282,8 @@ pub struct Microsoft {
 pub struct Origin {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -190,6 +303,8 @@ pub struct Origin {
 pub struct Prime {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -209,6 +324,12 @@ pub struct Prime {
 pub struct Steam {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    /// Only back up and restore data for these Steam accounts,
+    /// identified by their folder names in `userdata`.
+    /// If empty, then all accounts are included.
+    pub accounts: Vec<String>,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -228,6 +349,8 @@ pub struct Steam {
 pub struct Uplay {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -247,6 +370,11 @@ pub struct Uplay {
 pub struct OtherHome {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
+    /// Set for roots added automatically for other users' home folders.
+    #[serde(skip)]
+    pub user: Option<String>,
 }
 
 #[derive(
@@ -266,6 +394,8 @@ pub struct OtherHome {
 pub struct OtherWine {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -285,6 +415,8 @@ pub struct OtherWine {
 pub struct OtherWindows {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -304,6 +436,8 @@ pub struct OtherWindows {
 pub struct OtherLinux {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -323,6 +457,8 @@ pub struct OtherLinux {
 pub struct OtherMac {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 #[derive(
@@ -342,4 +478,6 @@ pub struct OtherMac {
 pub struct Other {
     /// Where the root is located on your system.
     pub path: StrictPath,
+    #[serde(flatten)]
+    pub options: Options,
 }
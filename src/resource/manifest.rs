@@ -5,10 +5,10 @@ use crate::{
     prelude::{app_dir, Error, StrictPath},
     resource::{
         cache::{self, Cache},
-        config::{Config, CustomGame, ManifestConfig},
+        config::{Config, CustomGame, ManifestConfig, Root},
         ResourceFile, SaveableResourceFile,
     },
-    scan::layout::escape_folder_name,
+    scan::{emulator, heroic, layout::escape_folder_name},
 };
 
 pub mod placeholder {
@@ -101,11 +101,14 @@ impl From<&str> for Os {
 )]
 #[serde(rename_all = "camelCase")]
 pub enum Store {
+    Bottles,
     Ea,
+    Emulator,
     Epic,
     Gog,
     GogGalaxy,
     Heroic,
+    Itch,
     Legendary,
     Lutris,
     Microsoft,
@@ -125,11 +128,14 @@ pub enum Store {
 
 impl Store {
     pub const ALL: &'static [Self] = &[
+        Store::Bottles,
         Store::Ea,
+        Store::Emulator,
         Store::Epic,
         Store::Gog,
         Store::GogGalaxy,
         Store::Heroic,
+        Store::Itch,
         Store::Legendary,
         Store::Lutris,
         Store::Microsoft,
@@ -152,16 +158,55 @@ impl ToString for Store {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum Tag {
     Save,
     Config,
+    Screenshot,
     #[default]
     #[serde(other)]
     Other,
 }
 
+impl Tag {
+    pub const ALL: &'static [Self] = &[Self::Save, Self::Config, Self::Screenshot, Self::Other];
+    pub const ALL_NAMES: &'static [&'static str] = &["save", "config", "screenshot", "other"];
+}
+
+impl std::str::FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "save" => Ok(Self::Save),
+            "config" => Ok(Self::Config),
+            "screenshot" => Ok(Self::Screenshot),
+            "other" => Ok(Self::Other),
+            _ => Err(format!("invalid tag: {}", s)),
+        }
+    }
+}
+
+impl ToString for Tag {
+    fn to_string(&self) -> String {
+        TRANSLATOR.tag(self)
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Secondary {
     pub id: String,
@@ -237,6 +282,9 @@ pub struct GameFileEntry {
     pub tags: BTreeSet<Tag>,
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     pub when: BTreeSet<GameFileConstraint>,
+    /// Whether this entry was added from a launcher's own save locations.
+    #[serde(skip)]
+    pub from_launcher: bool,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -564,12 +612,21 @@ impl Manifest {
             .collect()
     }
 
+    pub fn map_flatpak_ids_to_names(&self) -> HashMap<String, String> {
+        self.0
+            .iter()
+            .filter_map(|(k, v)| v.id.flatpak.as_ref().map(|id| (id.to_string(), k.to_owned())))
+            .collect()
+    }
+
     pub fn incorporate_extensions(&mut self, config: &Config) {
         if !config.manifest.enable {
             self.0.clear();
         }
 
         self.load_secondary_manifests(config);
+        self.add_emulated_games(config);
+        self.add_launcher_save_locations(config);
         self.add_custom_games(config);
     }
 
@@ -618,6 +675,77 @@ impl Manifest {
         self.0.insert(name, game);
     }
 
+    /// Emulator roots produce a pseudo-game for each ROM with save data.
+    fn add_emulated_games(&mut self, config: &Config) {
+        for root in config.expanded_roots() {
+            let Root::Emulator(root) = root else {
+                continue;
+            };
+            let source = Source::Secondary(root.path.render());
+
+            for (name, mut game) in emulator::find_games(&root).0 {
+                if let Some(standard) = self.0.get_mut(&name) {
+                    log::debug!("adding emulated files to existing game: {name}");
+                    standard.files.extend(game.files);
+                    standard.sources.insert(source.clone());
+                } else {
+                    log::debug!("adding emulated game: {name}");
+                    game.sources.insert(source.clone());
+                    self.0.insert(name, game);
+                }
+            }
+        }
+    }
+
+    /// Some launchers know where a game keeps its saves,
+    /// which lets us cover games that are missing from the manifest.
+    fn add_launcher_save_locations(&mut self, config: &Config) {
+        for root in config.expanded_roots() {
+            let Root::Heroic(root) = root else {
+                continue;
+            };
+            let source = Source::Secondary(root.path.render());
+
+            for found in heroic::gog::find_save_locations(&root) {
+                let files: BTreeMap<_, _> = found
+                    .paths
+                    .iter()
+                    .map(|path| {
+                        (
+                            globetter::Pattern::escape(&path.render()),
+                            GameFileEntry {
+                                tags: [Tag::Save].into(),
+                                from_launcher: true,
+                                ..Default::default()
+                            },
+                        )
+                    })
+                    .collect();
+                if files.is_empty() {
+                    continue;
+                }
+
+                let name = found
+                    .gog_id
+                    .and_then(|id| {
+                        self.0
+                            .iter()
+                            .find(|(_, game)| game.gog.id == Some(id))
+                            .map(|(name, _)| name.clone())
+                    })
+                    .unwrap_or(found.title);
+
+                log::debug!("adding launcher save locations for game: {name}");
+                let game = self.0.entry(name).or_insert_with(|| Game {
+                    gog: GogMetadata { id: found.gog_id },
+                    ..Default::default()
+                });
+                game.files.extend(files);
+                game.sources.insert(source.clone());
+            }
+        }
+    }
+
     fn load_secondary_manifests(&mut self, config: &Config) {
         for secondary in config.manifest.load_secondary_manifests() {
             self.incorporate_secondary_manifest(secondary);
@@ -839,6 +967,7 @@ mod tests {
                             }
                         ],
                         tags: btree_set![Tag::Save],
+                        from_launcher: false,
                     }
                 },
                 install_dir: btree_map! {
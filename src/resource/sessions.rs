@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    prelude::app_dir,
+    resource::{ResourceFile, SaveableResourceFile},
+};
+
+/// How many sessions to remember for each game.
+/// Older sessions are dropped when a new one is recorded.
+pub const MAX_SESSIONS_PER_GAME: usize = 100;
+
+/// Play sessions recorded by the `wrap` command.
+/// This is kept separate from the cache so that the GUI and a wrapped game
+/// don't overwrite each other's changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Sessions {
+    /// Each key is the name of a game, and each list is in chronological order.
+    pub games: BTreeMap<String, Vec<Session>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// When the game was launched.
+    pub start: chrono::DateTime<chrono::Utc>,
+    /// When the game exited.
+    pub end: chrono::DateTime<chrono::Utc>,
+    /// The game's exit code.
+    /// If the game was killed by a signal, then this is 128 plus the signal number.
+    pub exit_code: i32,
+    /// Whether save data was restored before launching.
+    pub restored: bool,
+    /// Whether save data was backed up after the game exited.
+    pub backed_up: bool,
+    /// Name of the backup created after the game exited, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+}
+
+impl Session {
+    pub fn duration(&self) -> chrono::Duration {
+        self.end - self.start
+    }
+}
+
+impl ResourceFile for Sessions {
+    const FILE_NAME: &'static str = "sessions.yaml";
+}
+
+impl SaveableResourceFile for Sessions {
+    /// Several wrapped games may exit around the same time,
+    /// so we write a temporary file and then swap it into place.
+    /// That way, nobody ever reads a partially written file.
+    fn save(&self) {
+        let path = Self::path();
+        if path.create_parent_dir().is_err() {
+            return;
+        }
+
+        let content = serde_yaml::to_string(&self).unwrap();
+        let temp = app_dir().joined(&format!("{}.{}.tmp", Self::FILE_NAME, std::process::id()));
+        if let Err(e) = temp.write_with_content(&content).and_then(|_| temp.move_to(&path)) {
+            log::error!("Unable to save sessions: {:?}", e);
+            let _ = temp.remove();
+        }
+    }
+}
+
+impl Sessions {
+    /// Add a session to the file on disk.
+    /// We reload first in case another instance recorded a session in the meantime.
+    pub fn record(game: &str, session: Session) {
+        let mut sessions = match Self::load() {
+            Ok(x) => x,
+            Err(e) => {
+                log::error!("Unable to load sessions, so this one will not be recorded: {:?}", e);
+                return;
+            }
+        };
+        sessions.add(game, session);
+        sessions.save();
+    }
+
+    fn add(&mut self, game: &str, session: Session) {
+        let sessions = self.games.entry(game.to_string()).or_default();
+        sessions.push(session);
+        if sessions.len() > MAX_SESSIONS_PER_GAME {
+            sessions.drain(..sessions.len() - MAX_SESSIONS_PER_GAME);
+        }
+    }
+
+    pub fn latest(&self, game: &str) -> Option<&Session> {
+        self.games.get(game).and_then(|sessions| sessions.last())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn session(minute: u32) -> Session {
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.with_ymd_and_hms(2024, 1, 2, 3, minute, 0).unwrap();
+        Session {
+            start,
+            end: start + chrono::TimeDelta::try_seconds(90).unwrap(),
+            exit_code: 0,
+            restored: false,
+            backed_up: true,
+            backup: Some(".".to_string()),
+        }
+    }
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let mut sessions = Sessions::default();
+        sessions.add("foo", session(4));
+
+        let serialized = serde_yaml::to_string(&sessions).unwrap();
+        assert_eq!(
+            r#"
+---
+games:
+  foo:
+    - start: "2024-01-02T03:04:00Z"
+      end: "2024-01-02T03:05:30Z"
+      exitCode: 0
+      restored: false
+      backedUp: true
+      backup: "."
+"#
+            .trim(),
+            serialized.trim(),
+        );
+        assert_eq!(sessions, Sessions::load_from_string(&serialized).unwrap());
+    }
+
+    #[test]
+    fn can_calculate_duration() {
+        assert_eq!(chrono::TimeDelta::try_seconds(90).unwrap(), session(4).duration());
+    }
+
+    #[test]
+    fn drops_oldest_sessions_past_limit() {
+        let mut sessions = Sessions::default();
+        for i in 0..=MAX_SESSIONS_PER_GAME {
+            sessions.add("foo", session((i % 60) as u32));
+        }
+
+        assert_eq!(MAX_SESSIONS_PER_GAME, sessions.games["foo"].len());
+        assert_eq!(
+            Some(&session((MAX_SESSIONS_PER_GAME % 60) as u32)),
+            sessions.latest("foo")
+        );
+        assert_eq!(session(1), sessions.games["foo"][0]);
+    }
+}
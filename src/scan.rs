@@ -7,22 +7,29 @@ pub mod layout;
 mod preview;
 pub mod registry_compat;
 mod saves;
-mod steam;
+pub mod steam;
 mod title;
+pub mod users;
+pub mod wine;
 
 #[cfg(target_os = "windows")]
 pub mod registry;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
 
 pub use self::{backup::*, change::*, duplicate::*, launchers::*, preview::*, saves::*, steam::*, title::*};
 
 use crate::{
-    path::{CommonPath, StrictPath},
+    path::{fold_glob_case, wine_drive_index, CommonPath, StrictPath},
     prelude::{filter_map_walkdir, Error, SKIP},
     resource::{
-        config::{BackupFilter, RedirectConfig, RedirectKind, Root, SortKey, ToggledPaths, ToggledRegistry},
-        manifest::{Game, GameFileEntry, IdMetadata, Os, Store},
+        config::{
+            BackupFilter, RedirectConfig, RedirectKind, Root, RootPathFilter, SortKey, SymlinkPolicy, ToggledPaths,
+            ToggledRegistry, User,
+        },
+        manifest::{Game, GameFileEntry, IdMetadata, Os, Store, Tag},
     },
     scan::layout::LatestBackup,
 };
@@ -102,6 +109,29 @@ pub fn parse_paths(
 ) -> HashSet<(StrictPath, bool)> {
     use crate::resource::manifest::placeholder::*;
 
+    // Steam roots may be limited to specific accounts.
+    let steam_userdata = format!("{ROOT}/userdata/{STORE_USER_ID}");
+    if !root.steam_accounts().is_empty() && path.contains(&steam_userdata) {
+        return root
+            .steam_accounts()
+            .iter()
+            .flat_map(|account| {
+                parse_paths(
+                    &path.replace(&steam_userdata, &format!("{ROOT}/userdata/{account}")),
+                    data,
+                    root,
+                    install_dir,
+                    full_install_dir,
+                    steam_ids,
+                    ids,
+                    manifest_dir,
+                    steam_shortcut,
+                    platform,
+                )
+            })
+            .collect();
+    }
+
     let mut paths = HashSet::new();
 
     // Since STORE_USER_ID becomes `*`, we don't want to end up with an invalid `**`.
@@ -135,10 +165,11 @@ pub fn parse_paths(
                 BASE,
                 &match root.store() {
                     Store::Steam => format!("{}/steamapps/common/{}", &root_interpreted, install_dir),
-                    Store::Heroic | Store::Legendary | Store::Lutris => full_install_dir
+                    Store::Bottles | Store::Heroic | Store::Itch | Store::Legendary | Store::Lutris => full_install_dir
                         .and_then(|x| x.interpret().ok())
                         .unwrap_or_else(|| SKIP.to_string()),
                     Store::Ea
+                    | Store::Emulator
                     | Store::Epic
                     | Store::Gog
                     | Store::GogGalaxy
@@ -243,7 +274,7 @@ pub fn parse_paths(
                 .replace(GAME, install_dir)
                 .replace(BASE, &format!("{}/{}", &root_interpreted, install_dir))
                 .replace(STORE_USER_ID, SKIP)
-                .replace(OS_USER_NAME, &crate::prelude::OS_USERNAME)
+                .replace(OS_USER_NAME, root.user().unwrap_or(&crate::prelude::OS_USERNAME))
                 .replace(WIN_APP_DATA, check_windows_path("<home>/AppData/Roaming"))
                 .replace(WIN_LOCAL_APP_DATA, check_windows_path("<home>/AppData/Local"))
                 .replace(WIN_DOCUMENTS, check_windows_path("<home>/Documents"))
@@ -296,7 +327,7 @@ pub fn parse_paths(
                     .replace(WIN_DOCUMENTS, &format!("{}/users/steamuser/Documents", prefix))
                     .replace(WIN_APP_DATA, &format!("{}/users/steamuser/AppData/Roaming", prefix))
                     .replace(WIN_LOCAL_APP_DATA, &format!("{}/users/steamuser/AppData/Local", prefix)),
-                false,
+                platform.is_case_sensitive(),
             ));
             paths.insert((
                 path2
@@ -306,7 +337,7 @@ pub fn parse_paths(
                         WIN_LOCAL_APP_DATA,
                         &format!("{}/users/steamuser/Local Settings/Application Data", prefix),
                     ),
-                false,
+                platform.is_case_sensitive(),
             ));
 
             if data.when.iter().any(|x| x.store == Some(Store::Uplay)) {
@@ -341,7 +372,7 @@ pub fn parse_paths(
                 .replace(WIN_DOCUMENTS, &format!("{}/users/*/Documents", prefix))
                 .replace(WIN_APP_DATA, &format!("{}/users/*/AppData/Roaming", prefix))
                 .replace(WIN_LOCAL_APP_DATA, &format!("{}/users/*/AppData/Local", prefix)),
-            false,
+            platform.is_case_sensitive(),
         ));
         paths.insert((
             path2
@@ -351,7 +382,7 @@ pub fn parse_paths(
                     WIN_LOCAL_APP_DATA,
                     &format!("{}/users/*/Local Settings/Application Data", prefix),
                 ),
-            false,
+            platform.is_case_sensitive(),
         ));
     }
 
@@ -398,36 +429,63 @@ pub fn parse_paths(
 
     if Os::HOST != Os::Windows {
         if let Some(flatpak_id) = ids.flatpak.as_ref() {
-            paths.insert((
-                path.replace(HOME, home)
-                    .replace(STORE_USER_ID, "*")
-                    .replace(OS_USER_NAME, "*")
-                    .replace(XDG_DATA, &format!("{home}/.var/app/{flatpak_id}/data"))
-                    .replace(XDG_CONFIG, &format!("{home}/.var/app/{flatpak_id}/config")),
-                platform.is_case_sensitive(),
-            ));
-
+            let mut homes = vec![home];
             if root.store() == Store::OtherHome {
-                let home = &root_interpreted;
-                paths.insert((
-                    path.replace(HOME, home)
-                        .replace(STORE_USER_ID, "*")
-                        .replace(OS_USER_NAME, "*")
-                        .replace(XDG_DATA, &format!("{home}/.var/app/{flatpak_id}/data"))
-                        .replace(XDG_CONFIG, &format!("{home}/.var/app/{flatpak_id}/config")),
-                    platform.is_case_sensitive(),
-                ));
+                homes.push(&root_interpreted);
+            }
+
+            for home in homes {
+                let sandbox = format!("{home}/.var/app/{flatpak_id}");
+                for sandboxed_home in [home, sandbox.as_str()] {
+                    paths.insert((
+                        path.replace(HOME, sandboxed_home)
+                            .replace(STORE_USER_ID, "*")
+                            .replace(OS_USER_NAME, "*")
+                            .replace(XDG_DATA, &format!("{sandbox}/data"))
+                            .replace(XDG_CONFIG, &format!("{sandbox}/config")),
+                        platform.is_case_sensitive(),
+                    ));
+                }
             }
         }
     }
 
+    // Wine folds case, so Windows games don't care how folders in a prefix are capitalized.
+    // The prefix's own location still follows the host's rules.
+    let wine_prefix_end = |path: &str| {
+        if root.store() == Store::OtherWine && path.starts_with(&root_interpreted) {
+            Some(root_interpreted.len())
+        } else {
+            wine_drive_index(path)
+        }
+    };
+
     paths
         .iter()
-        .map(|(x, y)| (StrictPath::relative(x.to_string(), manifest_dir.interpret().ok()), *y))
+        .map(|(x, case_sensitive)| {
+            let x = match wine_prefix_end(x) {
+                Some(index) if *case_sensitive => fold_glob_case(x, index),
+                _ => x.to_string(),
+            };
+            (StrictPath::relative(x, manifest_dir.interpret().ok()), *case_sensitive)
+        })
         .collect()
 }
 
-pub fn scan_game_for_backup(
+/// A preserved link's content is its target, so that's what we compare between backups.
+fn hash_link_target(target: &str) -> String {
+    use sha1::Digest;
+    let mut hasher = sha1::Sha1::new();
+    hasher.update(target);
+    format!("{:x}", hasher.finalize())
+}
+
+/// A path to check, along with whether it should be matched case-sensitively.
+type CandidatePath = (StrictPath, Option<bool>);
+
+/// Each path is mapped to the tags of the manifest entries that produced it.
+/// Also returns the paths that came from a launcher's own save locations rather than the manifest.
+fn collect_backup_candidates(
     game: &Game,
     name: &str,
     roots: &[Root],
@@ -435,20 +493,13 @@ pub fn scan_game_for_backup(
     launchers: &Launchers,
     filter: &BackupFilter,
     wine_prefix: &Option<StrictPath>,
-    ignored_paths: &ToggledPaths,
-    #[allow(unused_variables)] ignored_registry: &ToggledRegistry,
-    previous: Option<LatestBackup>,
-    redirects: &[RedirectConfig],
     steam_shortcuts: &SteamShortcuts,
-) -> ScanInfo {
-    log::trace!("[{name}] beginning scan for backup");
-
-    let mut found_files = HashSet::new();
-    #[allow(unused_mut)]
-    let mut found_registry_keys = HashSet::new();
-    let has_backups = previous.is_some();
-
-    let mut paths_to_check = HashSet::<(StrictPath, Option<bool>)>::new();
+) -> (HashMap<CandidatePath, BTreeSet<Tag>>, HashSet<CandidatePath>) {
+    // Each path is mapped to the tags of the manifest entries that produced it.
+    let mut paths_to_check = HashMap::<(StrictPath, Option<bool>), BTreeSet<Tag>>::new();
+    // Paths that came from a launcher's own save locations rather than the manifest.
+    let mut launcher_paths = HashSet::<(StrictPath, Option<bool>)>::new();
+    let registry_tags: BTreeSet<Tag> = game.registry.values().flat_map(|x| x.tags.iter().copied()).collect();
 
     // Add a dummy root for checking paths without `<root>`.
     let mut roots_to_check: Vec<Root> = vec![Root::new(SKIP, Store::Other)];
@@ -462,7 +513,25 @@ pub fn scan_game_for_backup(
     // For other Wine roots, it would trigger for every game.
     if let Some(wp) = wine_prefix {
         log::trace!("[{name}] adding extra Wine prefix: {wp:?}");
-        scan_game_for_backup_add_prefix(&mut roots_to_check, &mut paths_to_check, wp, !game.registry.is_empty());
+        scan_game_for_backup_add_prefix(
+            &mut roots_to_check,
+            &mut paths_to_check,
+            wp,
+            !game.registry.is_empty(),
+            &registry_tags,
+        );
+    }
+
+    // Non-Steam shortcuts run with Proton have their own prefix.
+    if let Some(wp) = steam_shortcuts.get(name).and_then(|x| x.prefix.as_ref()) {
+        log::trace!("[{name}] adding Proton prefix from Steam shortcut: {wp:?}");
+        scan_game_for_backup_add_prefix(
+            &mut roots_to_check,
+            &mut paths_to_check,
+            wp,
+            !game.registry.is_empty(),
+            &registry_tags,
+        );
     }
 
     // handle what was found for heroic
@@ -474,6 +543,7 @@ pub fn scan_game_for_backup(
                 &mut paths_to_check,
                 if with_pfx.exists() { &with_pfx } else { wp },
                 !game.registry.is_empty(),
+                &registry_tags,
             );
         }
     }
@@ -542,43 +612,132 @@ pub fn scan_game_for_backup(
                     // This covers `SKIP` and any other unmatched placeholders.
                     continue;
                 }
-                paths_to_check.insert((candidate, Some(case_sensitive)));
+                if path_data.from_launcher {
+                    launcher_paths.insert((candidate.clone(), Some(case_sensitive)));
+                }
+                paths_to_check
+                    .entry((candidate, Some(case_sensitive)))
+                    .or_default()
+                    .extend(path_data.tags.iter().copied());
             }
         }
         if root.store() == Store::Steam {
-            for id in &steam_ids {
+            let accounts = match root.steam_accounts() {
+                [] => vec!["*"],
+                accounts => accounts.iter().map(|x| x.as_str()).collect(),
+            };
+            for (id, account) in steam_ids.iter().cartesian_product(&accounts) {
                 // Cloud saves:
-                paths_to_check.insert((
-                    StrictPath::relative(
-                        format!("{}/userdata/*/{}/remote/", root_interpreted.clone(), id),
-                        Some(manifest_dir_interpreted.clone()),
-                    ),
-                    None,
-                ));
-
-                // Screenshots:
-                if !filter.exclude_store_screenshots {
-                    paths_to_check.insert((
+                paths_to_check
+                    .entry((
                         StrictPath::relative(
-                            format!("{}/userdata/*/760/remote/{}/screenshots/*.*", &root_interpreted, id),
+                            format!("{}/userdata/{}/{}/remote/", root_interpreted.clone(), account, id),
                             Some(manifest_dir_interpreted.clone()),
                         ),
                         None,
-                    ));
-                }
+                    ))
+                    .or_default()
+                    .insert(Tag::Save);
 
+                // Screenshots:
+                if !filter.exclude_store_screenshots {
+                    paths_to_check
+                        .entry((
+                            StrictPath::relative(
+                                format!(
+                                    "{}/userdata/{}/760/remote/{}/screenshots/*.*",
+                                    &root_interpreted, account, id
+                                ),
+                                Some(manifest_dir_interpreted.clone()),
+                            ),
+                            None,
+                        ))
+                        .or_default()
+                        .insert(Tag::Screenshot);
+                }
+            }
+            for id in &steam_ids {
                 // Registry:
                 if !game.registry.is_empty() {
                     let prefix = format!("{}/steamapps/compatdata/{}/pfx", &root_interpreted, id);
-                    paths_to_check.insert((
-                        StrictPath::relative(format!("{}/*.reg", prefix), Some(manifest_dir_interpreted.clone())),
-                        None,
-                    ));
+                    paths_to_check
+                        .entry((
+                            StrictPath::relative(format!("{}/*.reg", prefix), Some(manifest_dir_interpreted.clone())),
+                            None,
+                        ))
+                        .or_default()
+                        .extend(registry_tags.iter().copied());
                 }
             }
         }
     }
 
+    (paths_to_check, launcher_paths)
+}
+
+/// Find every path where a game's save files could be, whether or not anything is there yet.
+/// The paths may contain glob patterns.
+pub fn backup_candidates(
+    game: &Game,
+    name: &str,
+    roots: &[Root],
+    manifest_dir: &StrictPath,
+    launchers: &Launchers,
+    filter: &BackupFilter,
+    steam_shortcuts: &SteamShortcuts,
+) -> Vec<StrictPath> {
+    let (paths_to_check, _) = collect_backup_candidates(
+        game,
+        name,
+        roots,
+        manifest_dir,
+        launchers,
+        filter,
+        &None,
+        steam_shortcuts,
+    );
+
+    paths_to_check
+        .into_keys()
+        .map(|(path, _)| path)
+        .filter(|path| !filter.is_path_ignored(path))
+        .collect()
+}
+
+pub fn scan_game_for_backup(
+    game: &Game,
+    name: &str,
+    roots: &[Root],
+    root_filters: &[RootPathFilter],
+    manifest_dir: &StrictPath,
+    launchers: &Launchers,
+    filter: &BackupFilter,
+    wine_prefix: &Option<StrictPath>,
+    ignored_paths: &ToggledPaths,
+    #[allow(unused_variables)] ignored_registry: &ToggledRegistry,
+    previous: Option<LatestBackup>,
+    redirects: &[RedirectConfig],
+    steam_shortcuts: &SteamShortcuts,
+) -> ScanInfo {
+    log::trace!("[{name}] beginning scan for backup");
+
+    let mut found_files = HashMap::<StrictPath, ScannedFile>::new();
+    let mut found_links = HashMap::<StrictPath, BTreeSet<Tag>>::new();
+    #[allow(unused_mut)]
+    let mut found_registry_keys = HashSet::new();
+    let has_backups = previous.is_some();
+
+    let (paths_to_check, launcher_paths) = collect_backup_candidates(
+        game,
+        name,
+        roots,
+        manifest_dir,
+        launchers,
+        filter,
+        wine_prefix,
+        steam_shortcuts,
+    );
+
     let previous_files: HashMap<&StrictPath, &String> = previous
         .as_ref()
         .map(|previous| {
@@ -591,17 +750,43 @@ pub fn scan_game_for_backup(
         })
         .unwrap_or_default();
 
-    for (path, case_sensitive) in paths_to_check {
+    // Root settings are checked as we go, so that we don't walk into ignored folders at all.
+    let excluded_by_root = |path: &StrictPath| root_filters.iter().any(|filter| filter.is_ignored(path));
+
+    for ((path, case_sensitive), tags) in paths_to_check {
         log::trace!("[{name}] checking: {path:?}");
+        let from_launcher = launcher_paths.contains(&(path.clone(), case_sensitive));
         if filter.is_path_ignored(&path) {
             log::debug!("[{name}] excluded: {path:?}");
             continue;
         }
+        // A root that has timed out may be unresponsive, so we don't even glob inside of it.
+        let expired_by_root = |path: &StrictPath| root_filters.iter().any(|filter| filter.is_expired(path));
+        if expired_by_root(&path) {
+            log::warn!("[{name}] root timed out, skipping: {path:?}");
+            continue;
+        }
         let paths = match case_sensitive {
             None => path.glob(),
             Some(cs) => path.glob_case_sensitive(cs),
         };
         for p in paths {
+            if excluded_by_root(&p) {
+                log::debug!("[{name}] excluded by root settings: {p:?}");
+                continue;
+            }
+            if expired_by_root(&p) {
+                log::warn!("[{name}] root timed out, skipping: {p:?}");
+                continue;
+            }
+            if filter.symlinks != SymlinkPolicy::Follow && p.is_symlink() {
+                if filter.symlinks == SymlinkPolicy::Preserve {
+                    found_links.entry(p).or_default().extend(tags.iter().copied());
+                } else {
+                    log::debug!("[{name}] skipped link: {p:?}");
+                }
+                continue;
+            }
             if p.is_file() {
                 let Ok(p) = p.interpreted().map(|x| x.rendered()) else {
                     continue;
@@ -610,34 +795,71 @@ pub fn scan_game_for_backup(
                     log::debug!("[{name}] excluded: {p:?}");
                     continue;
                 }
+                if let Some(existing) = found_files.get_mut(&p) {
+                    existing.tags.extend(tags.iter().copied());
+                    existing.from_launcher |= from_launcher;
+                    continue;
+                }
                 let ignored = ignored_paths.is_ignored(name, &p);
                 log::debug!("[{name}] found: {p:?}");
                 let hash = p.sha1();
                 let redirected = game_file_target(&p, redirects, false);
-                found_files.insert(ScannedFile {
-                    change: ScanChange::evaluate_backup(&hash, previous_files.get(redirected.as_ref().unwrap_or(&p))),
-                    size: p.size(),
-                    hash,
-                    redirected,
-                    path: p,
-                    original_path: None,
-                    ignored,
-                    container: None,
-                });
+                found_files.insert(
+                    p.clone(),
+                    ScannedFile {
+                        change: ScanChange::evaluate_backup(
+                            &hash,
+                            previous_files.get(redirected.as_ref().unwrap_or(&p)),
+                        ),
+                        size: p.size(),
+                        hash,
+                        redirected,
+                        path: p,
+                        original_path: None,
+                        ignored,
+                        container: None,
+                        tags: tags.clone(),
+                        link: None,
+                        from_launcher,
+                        user: None,
+                    },
+                );
             } else if p.is_dir() {
                 log::trace!("[{name}] looking for files in: {p:?}");
                 for child in walkdir::WalkDir::new(p.as_std_path_buf().unwrap())
                     .max_depth(100)
-                    .follow_links(true)
+                    .follow_links(filter.symlinks == SymlinkPolicy::Follow)
                     .into_iter()
+                    .filter_entry(|entry| {
+                        let excluded = excluded_by_root(&StrictPath::from(entry));
+                        if excluded {
+                            log::debug!("[{name}] excluded by root settings: {:?}", entry.path());
+                        }
+                        !excluded
+                    })
                     .filter_map(filter_map_walkdir)
                 {
+                    if expired_by_root(&StrictPath::from(&child)) {
+                        log::warn!("[{name}] root timed out, stopping search in: {p:?}");
+                        break;
+                    }
+
                     #[cfg(not(target_os = "windows"))]
                     if child.path().to_string_lossy().contains('\\') {
                         // TODO: Support names containing a slash.
                         continue;
                     }
 
+                    if child.file_type().is_symlink() {
+                        let child = StrictPath::from(&child);
+                        if filter.symlinks == SymlinkPolicy::Preserve {
+                            found_links.entry(child).or_default().extend(tags.iter().copied());
+                        } else {
+                            log::debug!("[{name}] skipped link: {child:?}");
+                        }
+                        continue;
+                    }
+
                     if child.file_type().is_file() {
                         let Ok(child) = StrictPath::from(&child).interpreted().map(|x| x.rendered()) else {
                             continue;
@@ -647,29 +869,116 @@ pub fn scan_game_for_backup(
                             log::debug!("[{name}] excluded: {child:?}");
                             continue;
                         }
+                        if let Some(existing) = found_files.get_mut(&child) {
+                            existing.tags.extend(tags.iter().copied());
+                            existing.from_launcher |= from_launcher;
+                            continue;
+                        }
                         let ignored = ignored_paths.is_ignored(name, &child);
                         log::debug!("[{name}] found: {child:?}");
                         let hash = child.sha1();
                         let redirected = game_file_target(&child, redirects, false);
-                        found_files.insert(ScannedFile {
-                            change: ScanChange::evaluate_backup(
-                                &hash,
-                                previous_files.get(redirected.as_ref().unwrap_or(&child)),
-                            ),
-                            size: child.size(),
-                            hash,
-                            redirected,
-                            path: child,
-                            original_path: None,
-                            ignored,
-                            container: None,
-                        });
+                        found_files.insert(
+                            child.clone(),
+                            ScannedFile {
+                                change: ScanChange::evaluate_backup(
+                                    &hash,
+                                    previous_files.get(redirected.as_ref().unwrap_or(&child)),
+                                ),
+                                size: child.size(),
+                                hash,
+                                redirected,
+                                path: child,
+                                original_path: None,
+                                ignored,
+                                container: None,
+                                tags: tags.clone(),
+                                link: None,
+                                from_launcher,
+                                user: None,
+                            },
+                        );
                     }
                 }
             }
         }
     }
 
+    for (link, tags) in found_links {
+        let Some(target) = link.read_link() else {
+            continue;
+        };
+        // We resolve the parent folder, but not the link itself.
+        let Some(p) = link
+            .parent()
+            .and_then(|parent| parent.interpreted().ok())
+            .zip(link.leaf())
+            .map(|(parent, leaf)| parent.joined(&leaf).rendered())
+        else {
+            continue;
+        };
+        if filter.is_path_ignored(&p) {
+            log::debug!("[{name}] excluded: {p:?}");
+            continue;
+        }
+        if excluded_by_root(&p) {
+            log::debug!("[{name}] excluded by root settings: {p:?}");
+            continue;
+        }
+        if let Some(existing) = found_files.get_mut(&p) {
+            existing.tags.extend(tags);
+            continue;
+        }
+        let ignored = ignored_paths.is_ignored(name, &p);
+        log::debug!("[{name}] found link: {p:?} -> {target}");
+        let hash = hash_link_target(&target);
+        let redirected = game_file_target(&p, redirects, false);
+        found_files.insert(
+            p.clone(),
+            ScannedFile {
+                change: ScanChange::evaluate_backup(&hash, previous_files.get(redirected.as_ref().unwrap_or(&p))),
+                size: target.len() as u64,
+                hash,
+                redirected,
+                path: p,
+                original_path: None,
+                ignored,
+                container: None,
+                tags,
+                link: Some(target),
+                from_launcher: false,
+                user: None,
+            },
+        );
+    }
+
+    // When scanning for other users, record whose home folder each file came from.
+    let users: Vec<_> = roots
+        .iter()
+        .filter_map(|root| {
+            root.user().map(|user| User {
+                name: user.to_string(),
+                home: root.path().clone(),
+            })
+        })
+        .collect();
+    if !users.is_empty() {
+        let users: Vec<_> = users.into_iter().chain(users::current()).collect();
+        for file in found_files.values_mut() {
+            file.user = users::owner(&users, &file.path).cloned();
+        }
+    }
+
+    // A file may match several manifest entries, so we check the tags once they've all been merged.
+    found_files.retain(|path, file| {
+        let allowed = filter.tags.allows(&file.tags);
+        if !allowed {
+            log::debug!("[{name}] excluded by tags {:?}: {path:?}", &file.tags);
+        }
+        allowed
+    });
+    let mut found_files: HashSet<_> = found_files.into_values().collect();
+
     // Mark removed files.
     let current_files: Vec<_> = found_files
         .iter()
@@ -697,6 +1006,10 @@ pub fn scan_game_for_backup(
                 original_path: None,
                 ignored: ignored_paths.is_ignored(name, previous_file),
                 container: None,
+                tags: Default::default(),
+                link: None,
+                from_launcher: false,
+                user: None,
             });
         }
     }
@@ -708,10 +1021,14 @@ pub fn scan_game_for_backup(
             _ => None,
         };
 
-        for key in game.registry.keys() {
+        for (key, entry) in &game.registry {
             if key.trim().is_empty() {
                 continue;
             }
+            if !filter.tags.allows(&entry.tags) {
+                log::debug!("[{name}] excluded registry by tags {:?}: {key}", &entry.tags);
+                continue;
+            }
 
             log::trace!("[{name}] computing candidates for registry: {key}");
             let mut candidates = vec![key.clone()];
@@ -798,13 +1115,17 @@ pub fn scan_game_for_backup(
 
 fn scan_game_for_backup_add_prefix(
     roots_to_check: &mut Vec<Root>,
-    paths_to_check: &mut HashSet<(StrictPath, Option<bool>)>,
+    paths_to_check: &mut HashMap<(StrictPath, Option<bool>), BTreeSet<Tag>>,
     wp: &StrictPath,
     has_registry: bool,
+    registry_tags: &BTreeSet<Tag>,
 ) {
     roots_to_check.push(Root::new(wp.clone(), Store::OtherWine));
     if has_registry {
-        paths_to_check.insert((wp.joined("*.reg"), None));
+        paths_to_check
+            .entry((wp.joined("*.reg"), None))
+            .or_default()
+            .extend(registry_tags.iter().copied());
     }
 }
 
@@ -864,13 +1185,17 @@ fn compare_games_by_status(scan_info1: &ScanInfo, scan_info2: &ScanInfo) -> std:
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use velcro::{btree_map, hash_set};
+    use velcro::{btree_map, btree_set, hash_set};
 
     use super::*;
     #[cfg(target_os = "windows")]
     use crate::resource::config::ToggledRegistryEntry;
     use crate::{
-        resource::{config::Config, manifest::Manifest, ResourceFile},
+        resource::{
+            config::{Config, TagFilter, UntaggedPolicy},
+            manifest::Manifest,
+            ResourceFile,
+        },
         testing::{repo, s, EMPTY_HASH},
     };
 
@@ -1005,6 +1330,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_skip_paths_under_expired_root() {
+        let mut roots = config().roots;
+        roots[0].options_mut().timeout = Some(0);
+        let root_filters = Root::path_filters(&roots);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &roots,
+                &root_filters,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            ),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_file_matches() {
         assert_eq!(
@@ -1021,6 +1380,7 @@ mod tests {
                 &manifest().0["game1"],
                 "game1",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
                 &BackupFilter::default(),
@@ -1046,6 +1406,7 @@ mod tests {
                 &manifest().0["game 2"],
                 "game 2",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game 2".to_string()]),
                 &BackupFilter::default(),
@@ -1059,6 +1420,133 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_scan_game_for_backup_with_tag_filter() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt:
+                  tags: [config]
+                <base>/subdir:
+                  tags: [save]
+            "#,
+        )
+        .unwrap();
+        let scan = |tags: TagFilter| {
+            scan_game_for_backup(
+                &manifest.0["game1"],
+                "game1",
+                &config().roots,
+                &Root::path_filters(&config().roots),
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest, &["game1".to_string()]),
+                &BackupFilter {
+                    tags,
+                    ..Default::default()
+                },
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            )
+        };
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root1/game1/subdir/file2.txt", repo()), 2, "9d891e731f75deae56884d79e9816736b7488080").change_new().tagged(&[Tag::Save]),
+                    ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new().tagged(&[Tag::Config]),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan(TagFilter::default()),
+        );
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root1/game1/subdir/file2.txt", repo()), 2, "9d891e731f75deae56884d79e9816736b7488080").change_new().tagged(&[Tag::Save]),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan(TagFilter {
+                include: btree_set! { Tag::Save },
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new().tagged(&[Tag::Config]),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan(TagFilter {
+                exclude: btree_set! { Tag::Save },
+                ..Default::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_steam_cloud_tags() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              steam:
+                id: 101
+            "#,
+        )
+        .unwrap();
+        let roots = &[Root::new(format!("{}/tests/root-steam", repo()), Store::Steam)];
+        let scan = |tags: TagFilter| {
+            scan_game_for_backup(
+                &manifest.0["game1"],
+                "game1",
+                roots,
+                &Root::path_filters(roots),
+                &StrictPath::new(repo()),
+                &Launchers::default(),
+                &BackupFilter {
+                    tags,
+                    ..Default::default()
+                },
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            )
+        };
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root-steam/userdata/1/101/remote/save.dat", repo()), 0, EMPTY_HASH).change_new().tagged(&[Tag::Save]),
+                    ScannedFile::new(format!("{}/tests/root-steam/userdata/1/760/remote/101/screenshots/shot.jpg", repo()), 0, EMPTY_HASH).change_new().tagged(&[Tag::Screenshot]),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan(TagFilter {
+                untagged: UntaggedPolicy::Exclude,
+                ..Default::default()
+            }),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_deduplicating_symlinks() {
         let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
@@ -1075,6 +1563,7 @@ mod tests {
                 &manifest().0["game5"],
                 "game5",
                 roots,
+                &Root::path_filters(roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
                 &BackupFilter::default(),
@@ -1088,6 +1577,74 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(not(target_os = "windows"))] // The Windows setup uses a junction, which stores an absolute target.
+    fn can_scan_game_for_backup_preserving_symlinks() {
+        let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root3/game5/data/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                    ScannedFile::new(format!("{}/tests/root3/game5/data-symlink", repo()), 4, hash_link_target("data")).change_new().linked_to("data"),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game5"],
+                "game5",
+                roots,
+                &Root::path_filters(roots),
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
+                &BackupFilter {
+                    symlinks: SymlinkPolicy::Preserve,
+                    ..Default::default()
+                },
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_skipping_symlinks() {
+        let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root3/game5/data/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game5"],
+                "game5",
+                roots,
+                &Root::path_filters(roots),
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
+                &BackupFilter {
+                    symlinks: SymlinkPolicy::Skip,
+                    ..Default::default()
+                },
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            ),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_redirect_to_symlink() {
         let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
@@ -1104,6 +1661,10 @@ mod tests {
                         change: ScanChange::New,
                         container: None,
                         redirected: Some(StrictPath::new(format!("{}/tests/root3/game5/data-symlink/file1.txt", repo()))),
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 found_registry_keys: hash_set! {},
@@ -1113,6 +1674,7 @@ mod tests {
                 &manifest().0["game5"],
                 "game5",
                 roots,
+                &Root::path_filters(roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
                 &BackupFilter::default(),
@@ -1146,6 +1708,7 @@ mod tests {
                 &manifest().0["game 2"],
                 "game 2",
                 roots,
+                &Root::path_filters(roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game 2".to_string()]),
                 &BackupFilter::default(),
@@ -1179,6 +1742,7 @@ mod tests {
                 &manifest().0["game4"],
                 "game4",
                 roots,
+                &Root::path_filters(roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
                 &BackupFilter::default(),
@@ -1211,6 +1775,7 @@ mod tests {
                 &manifest().0["game4"],
                 "game4",
                 roots,
+                &Root::path_filters(roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
                 &BackupFilter::default(),
@@ -1239,6 +1804,7 @@ mod tests {
                 &manifest().0["game4"],
                 "game4",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game4".to_string()]),
                 &BackupFilter::default(),
@@ -1252,6 +1818,47 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn can_scan_game_for_backup_with_different_case_in_wine_prefix() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            wine-case:
+              files:
+                <home>/My Games/foo/SAVE.dat: {}
+            "#,
+        )
+        .unwrap();
+        let scan = |root: &str| {
+            let roots = &[Root::new(format!("{}/tests/{}", repo(), root), Store::OtherWine)];
+            scan_game_for_backup(
+                &manifest.0["wine-case"],
+                "wine-case",
+                roots,
+                &Root::path_filters(roots),
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest, &["wine-case".to_string()]),
+                &BackupFilter::default(),
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            )
+            .found_files
+        };
+
+        assert_eq!(
+            hash_set! {
+                ScannedFile::new(format!("{}/tests/wine-prefix-case/drive_c/users/Me/my games/Foo/save.dat", repo()), 1, "11f6ad8ec52a2984abaafd7c3b516503785c2072").change_new(),
+            },
+            scan("wine-prefix-case"),
+        );
+        // Only the part inside of the prefix ignores case.
+        assert_eq!(hash_set! {}, scan("WINE-prefix-case"));
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_registry_files_in_wine_prefix() {
         assert_eq!(
@@ -1267,6 +1874,7 @@ mod tests {
                 &manifest().0["fake-registry"],
                 "fake-registry",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["fake-registry".to_string()]),
                 &BackupFilter::default(),
@@ -1303,6 +1911,7 @@ mod tests {
                 &manifest().0["game1"],
                 "game1",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
                 &filter,
@@ -1316,6 +1925,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_scan_game_for_backup_with_root_ignored_paths() {
+        let mut roots = config().roots;
+        for root in &mut roots {
+            if root.path().raw().ends_with("tests/root1") {
+                root.options_mut().ignored_paths = vec![s("game1/subdir")];
+            }
+        }
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &roots,
+                &Root::path_filters(&roots),
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                &None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                &Default::default(),
+            ),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_file_matches_and_toggled_directory() {
         let mut filter = BackupFilter::default();
@@ -1341,6 +1986,7 @@ mod tests {
                 &manifest().0["game1"],
                 "game1",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
                 &filter,
@@ -1379,6 +2025,7 @@ mod tests {
                 &manifest().0["game1"],
                 "game1",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
                 &filter,
@@ -1414,6 +2061,7 @@ mod tests {
                 &manifest().0["game3"],
                 "game3",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game3".to_string()]),
                 &BackupFilter::default(),
@@ -1453,6 +2101,7 @@ mod tests {
                 &manifest().0["game3-outer"],
                 "game3-outer",
                 &config().roots,
+                &Root::path_filters(&config().roots),
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game3-outer".to_string()]),
                 &BackupFilter::default(),
@@ -1552,6 +2201,7 @@ mod tests {
                     &manifest().0["game3-outer"],
                     "game3-outer",
                     &config().roots,
+                    &Root::path_filters(&config().roots),
                     &StrictPath::new(repo()),
                     &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
                     &filter,
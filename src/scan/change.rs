@@ -148,6 +148,15 @@ impl ScanChange {
         }
     }
 
+    pub fn evaluate_restore_link(original_path: &StrictPath, link: &str) -> Self {
+        match original_path.read_link() {
+            Some(current) if current == link => Self::Same,
+            Some(_) => Self::Different,
+            None if original_path.exists() => Self::Different,
+            None => Self::New,
+        }
+    }
+
     pub fn evaluate_restore(original_path: &StrictPath, previous_hash: &str) -> Self {
         match original_path.try_sha1() {
             Err(_) => Self::New,
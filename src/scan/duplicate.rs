@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     prelude::StrictPath,
+    resource::config::DuplicateRules,
     scan::{registry_compat::RegistryItem, ScanChange, ScanInfo, ScannedFile},
 };
 
@@ -82,8 +83,47 @@ impl DuplicateDetectorCount {
     }
 }
 
+/// Information about a game that the ownership rules can take into account.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DuplicateClaim {
+    /// Whether a launcher detected the game as installed.
+    pub detected: bool,
+    /// Raw file paths from the game's manifest entry.
+    pub manifest_paths: Vec<String>,
+}
+
+impl DuplicateClaim {
+    /// How many literal components of the best matching manifest path appear in the file path.
+    fn specificity(&self, file: &StrictPath) -> usize {
+        let file_parts: Vec<_> = file.render().to_lowercase().split('/').map(|x| x.to_string()).collect();
+
+        self.manifest_paths
+            .iter()
+            .filter_map(|manifest_path| {
+                let literals: Vec<_> = manifest_path
+                    .to_lowercase()
+                    .split('/')
+                    .filter(|part| !part.is_empty() && !part.contains(['<', '*', '?', '[', '{']))
+                    .map(|x| x.to_string())
+                    .collect();
+
+                let mut remaining = file_parts.iter();
+                literals
+                    .iter()
+                    .all(|literal| remaining.any(|part| part == literal))
+                    .then_some(literals.len())
+            })
+            .max()
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct DuplicateDetector {
+    rules: DuplicateRules,
+    claims: HashMap<String, DuplicateClaim>,
+    /// Owners of duplicated files, as decided by the rules.
+    file_owners: HashMap<StrictPath, String>,
     files: HashMap<StrictPath, HashMap<String, DuplicateDetectorEntry>>,
     registry: HashMap<RegistryItem, HashMap<String, DuplicateDetectorEntry>>,
     registry_values: HashMap<RegistryItem, HashMap<String, HashMap<String, DuplicateDetectorEntry>>>,
@@ -94,6 +134,15 @@ pub struct DuplicateDetector {
 }
 
 impl DuplicateDetector {
+    pub fn set_rules(&mut self, rules: DuplicateRules) {
+        self.rules = rules;
+    }
+
+    /// This should be called before `add_game` so that the rules can take the claim into account.
+    pub fn add_claim(&mut self, game: &str, claim: DuplicateClaim) {
+        self.claims.insert(game.to_string(), claim);
+    }
+
     pub fn add_game(&mut self, scan_info: &ScanInfo, game_enabled: bool) -> HashSet<String> {
         let mut stale = self.remove_game_and_refresh(&scan_info.game_name, false);
         stale.insert(scan_info.game_name.clone());
@@ -117,7 +166,8 @@ impl DuplicateDetector {
             self.game_files
                 .entry(scan_info.game_name.clone())
                 .or_default()
-                .insert(path);
+                .insert(path.clone());
+            self.resolve_file(&path);
         }
 
         for item in scan_info.found_registry_keys.iter() {
@@ -188,6 +238,7 @@ impl DuplicateDetector {
                         stale.insert(duplicate.clone());
                     }
                 }
+                self.resolve_file(&file);
             }
         }
         if let Some(registry_keys) = self.game_registry.remove(game) {
@@ -246,7 +297,103 @@ impl DuplicateDetector {
     }
 
     pub fn is_file_duplicated(&self, file: &ScannedFile) -> Duplication {
-        Duplication::evaluate(self.file(file).values())
+        match Duplication::evaluate(self.file(file).values()) {
+            Duplication::Duplicate if self.file_owners.contains_key(&self.pick_path(file)) => Duplication::Resolved,
+            x => x,
+        }
+    }
+
+    /// The game that owns a duplicated file, if the rules could decide.
+    pub fn file_owner(&self, file: &ScannedFile) -> Option<&String> {
+        self.file_owners.get(&self.pick_path(file))
+    }
+
+    /// Ignore any files that the rules assigned to a different game.
+    pub fn apply_ownership(&self, scan_info: &mut ScanInfo) {
+        if self.file_owners.is_empty() {
+            return;
+        }
+
+        scan_info.found_files = scan_info
+            .found_files
+            .drain()
+            .map(|mut file| {
+                if self
+                    .file_owner(&file)
+                    .is_some_and(|owner| owner != &scan_info.game_name)
+                {
+                    log::debug!(
+                        "[{}] ignoring file owned by another game: {:?}",
+                        &scan_info.game_name,
+                        &file.path
+                    );
+                    file.ignored = true;
+                }
+                file
+            })
+            .collect();
+    }
+
+    fn resolve_file(&mut self, path: &StrictPath) {
+        let owner = match self.files.get(path) {
+            Some(games) if games.len() > 1 => {
+                let mut candidates: Vec<_> = games.keys().cloned().collect();
+                candidates.sort();
+                self.pick_owner(path, candidates)
+            }
+            _ => None,
+        };
+
+        match owner {
+            Some(owner) => {
+                self.file_owners.insert(path.clone(), owner);
+            }
+            None => {
+                self.file_owners.remove(path);
+            }
+        }
+    }
+
+    fn pick_owner(&self, path: &StrictPath, mut candidates: Vec<String>) -> Option<String> {
+        if self.rules.is_empty() {
+            return None;
+        }
+
+        for owner in &self.rules.owners {
+            if candidates.contains(&owner.game) && owner.matches(path) {
+                return Some(owner.game.clone());
+            }
+        }
+
+        for game in &self.rules.preferred_games {
+            if candidates.contains(game) {
+                return Some(game.clone());
+            }
+        }
+
+        if self.rules.prefer_detected {
+            let detected: Vec<_> = candidates
+                .iter()
+                .filter(|game| self.claims.get(*game).is_some_and(|claim| claim.detected))
+                .cloned()
+                .collect();
+            if !detected.is_empty() {
+                candidates = detected;
+            }
+        }
+
+        if self.rules.prefer_specific && candidates.len() > 1 {
+            let specificity = |game: &String| {
+                self.claims
+                    .get(game)
+                    .map(|claim| claim.specificity(path))
+                    .unwrap_or_default()
+            };
+            let max = candidates.iter().map(specificity).max().unwrap_or_default();
+            candidates.retain(|game| specificity(game) == max);
+        }
+
+        (candidates.len() == 1).then(|| candidates.remove(0))
     }
 
     pub fn registry(&self, path: &RegistryItem) -> HashMap<String, DuplicateDetectorEntry> {
@@ -272,6 +419,7 @@ impl DuplicateDetector {
     }
 
     pub fn clear(&mut self) {
+        self.file_owners.clear();
         self.files.clear();
         self.registry.clear();
         self.registry_values.clear();
@@ -290,10 +438,10 @@ impl DuplicateDetector {
 
     fn count_duplicated_items_for(&self, game: &str) -> DuplicateDetectorCount {
         let mut tally = DuplicateDetectorCount::default();
-        for item in self.files.values() {
+        for (path, item) in &self.files {
             if item.contains_key(game) && item.len() > 1 {
                 tally.non_unique += 1;
-                if item.values().filter(|x| !x.is_inert()).count() <= 1 {
+                if item.values().filter(|x| !x.is_inert()).count() <= 1 || self.file_owners.contains_key(path) {
                     tally.resolved += 1;
                 }
             }
@@ -380,7 +528,7 @@ mod tests {
     use velcro::{hash_map, hash_set};
 
     use super::*;
-    use crate::{scan::ScannedRegistry, testing::s};
+    use crate::{resource::config::DuplicateOwner, scan::ScannedRegistry, testing::s};
 
     #[test]
     fn can_add_games_in_backup_mode() {
@@ -468,6 +616,10 @@ mod tests {
             change: Default::default(),
             container: None,
             redirected: None,
+            tags: Default::default(),
+            link: None,
+            from_launcher: false,
+            user: None,
         };
         let file1b = ScannedFile {
             path: StrictPath::new(s("file1b.txt")),
@@ -478,6 +630,10 @@ mod tests {
             change: Default::default(),
             container: None,
             redirected: None,
+            tags: Default::default(),
+            link: None,
+            from_launcher: false,
+            user: None,
         };
 
         detector.add_game(
@@ -516,6 +672,10 @@ mod tests {
                 change: Default::default(),
                 container: None,
                 redirected: None,
+                tags: Default::default(),
+                link: None,
+                from_launcher: false,
+                user: None,
             })
         );
 
@@ -538,6 +698,10 @@ mod tests {
                 change: Default::default(),
                 container: None,
                 redirected: None,
+                tags: Default::default(),
+                link: None,
+                from_launcher: false,
+                user: None,
             })
         );
     }
@@ -609,4 +773,140 @@ mod tests {
             detector.is_file_duplicated(&ScannedFile::with_name("file1"))
         );
     }
+
+    fn add_shared_file(detector: &mut DuplicateDetector, path: &str) {
+        for game in ["game1", "game2"] {
+            detector.add_game(
+                &ScanInfo {
+                    game_name: game.into(),
+                    found_files: hash_set! { ScannedFile::with_name(path) },
+                    ..Default::default()
+                },
+                true,
+            );
+        }
+    }
+
+    #[test]
+    fn leaves_duplicates_unresolved_without_rules() {
+        let mut detector = DuplicateDetector::default();
+        add_shared_file(&mut detector, "/shared/file.txt");
+
+        let file = ScannedFile::with_name("/shared/file.txt");
+        assert_eq!(Duplication::Duplicate, detector.is_file_duplicated(&file));
+        assert_eq!(None, detector.file_owner(&file));
+    }
+
+    #[test]
+    fn can_resolve_duplicates_with_explicit_owner() {
+        let mut detector = DuplicateDetector::default();
+        detector.set_rules(DuplicateRules {
+            owners: vec![DuplicateOwner {
+                path: StrictPath::new(s("/shared")),
+                game: s("game2"),
+            }],
+            preferred_games: vec![s("game1")],
+            ..Default::default()
+        });
+        add_shared_file(&mut detector, "/shared/file.txt");
+
+        let file = ScannedFile::with_name("/shared/file.txt");
+        assert_eq!(Duplication::Resolved, detector.is_file_duplicated(&file));
+        assert_eq!(Duplication::Resolved, detector.is_game_duplicated("game1"));
+        assert_eq!(Some(&s("game2")), detector.file_owner(&file));
+    }
+
+    #[test]
+    fn can_resolve_duplicates_with_preferred_game() {
+        let mut detector = DuplicateDetector::default();
+        detector.set_rules(DuplicateRules {
+            preferred_games: vec![s("game3"), s("game1")],
+            ..Default::default()
+        });
+        add_shared_file(&mut detector, "/shared/file.txt");
+
+        let file = ScannedFile::with_name("/shared/file.txt");
+        assert_eq!(Some(&s("game1")), detector.file_owner(&file));
+
+        detector.remove_game("game1");
+        assert_eq!(None, detector.file_owner(&file));
+    }
+
+    #[test]
+    fn can_resolve_duplicates_by_launcher_detection() {
+        let mut detector = DuplicateDetector::default();
+        detector.set_rules(DuplicateRules {
+            prefer_detected: true,
+            ..Default::default()
+        });
+        detector.add_claim(
+            "game2",
+            DuplicateClaim {
+                detected: true,
+                ..Default::default()
+            },
+        );
+        add_shared_file(&mut detector, "/shared/file.txt");
+
+        assert_eq!(
+            Some(&s("game2")),
+            detector.file_owner(&ScannedFile::with_name("/shared/file.txt"))
+        );
+    }
+
+    #[test]
+    fn can_resolve_duplicates_by_specific_manifest_path() {
+        let mut detector = DuplicateDetector::default();
+        detector.set_rules(DuplicateRules {
+            prefer_specific: true,
+            ..Default::default()
+        });
+        detector.add_claim(
+            "game1",
+            DuplicateClaim {
+                manifest_paths: vec![s("<winDocuments>/My Games")],
+                ..Default::default()
+            },
+        );
+        detector.add_claim(
+            "game2",
+            DuplicateClaim {
+                manifest_paths: vec![s("<winDocuments>/My Games/Game 2/*.sav"), s("<base>/unrelated")],
+                ..Default::default()
+            },
+        );
+        add_shared_file(&mut detector, "/home/user/Documents/My Games/Game 2/save.sav");
+
+        assert_eq!(
+            Some(&s("game2")),
+            detector.file_owner(&ScannedFile::with_name("/home/user/Documents/My Games/Game 2/save.sav"))
+        );
+    }
+
+    #[test]
+    fn can_apply_ownership_to_scan() {
+        let mut detector = DuplicateDetector::default();
+        detector.set_rules(DuplicateRules {
+            preferred_games: vec![s("game2")],
+            ..Default::default()
+        });
+        add_shared_file(&mut detector, "/shared/file.txt");
+
+        let mut scan_info = ScanInfo {
+            game_name: s("game1"),
+            found_files: hash_set! {
+                ScannedFile::with_name("/shared/file.txt"),
+                ScannedFile::with_name("/game1/file.txt"),
+            },
+            ..Default::default()
+        };
+        detector.apply_ownership(&mut scan_info);
+        assert_eq!(
+            hash_set! {
+                ScannedFile::with_name("/shared/file.txt").ignored(),
+                ScannedFile::with_name("/game1/file.txt"),
+            },
+            scan_info.found_files
+        );
+    }
 }
@@ -1,9 +1,13 @@
+pub mod bottles;
+pub mod emulator;
+mod flatpak;
 mod generic;
 pub mod heroic;
-mod legendary;
-mod lutris;
+pub mod itch;
+pub mod legendary;
+pub mod lutris;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::{
     prelude::StrictPath,
@@ -17,6 +21,8 @@ use crate::{
 #[derive(Clone, Default, Debug)]
 pub struct Launchers {
     games: HashMap<Root, HashMap<String, HashSet<LauncherGame>>>,
+    /// Games installed as Flatpak apps, which are not tied to any root.
+    flatpak: HashMap<String, HashSet<LauncherGame>>,
     empty: HashSet<LauncherGame>,
 }
 
@@ -42,6 +48,40 @@ impl Launchers {
             .iter()
     }
 
+    /// Each detected game, along with the roots that detected it.
+    /// Games without a root were detected as Flatpak apps.
+    pub fn installed(&self) -> BTreeMap<&String, Vec<(Option<&Root>, &LauncherGame)>> {
+        let mut installed = BTreeMap::<_, Vec<_>>::new();
+        for (root, games) in &self.games {
+            for (game, entries) in games {
+                installed
+                    .entry(game)
+                    .or_default()
+                    .extend(entries.iter().map(|entry| (Some(root), entry)));
+            }
+        }
+        for (game, entries) in &self.flatpak {
+            installed
+                .entry(game)
+                .or_default()
+                .extend(entries.iter().map(|entry| (None, entry)));
+        }
+        for entries in installed.values_mut() {
+            entries.sort_by_key(|(root, entry)| {
+                (
+                    root.map(|x| x.path().raw()),
+                    entry.install_dir.as_ref().map(|x| x.raw()),
+                )
+            });
+        }
+        installed
+    }
+
+    /// Whether any root's launcher detected the game as installed.
+    pub fn is_detected(&self, game: &str) -> bool {
+        self.games.values().any(|games| games.contains_key(game)) || self.flatpak.contains_key(game)
+    }
+
     pub fn scan(
         roots: &[Root],
         manifest: &Manifest,
@@ -54,13 +94,17 @@ impl Launchers {
         for root in roots {
             log::debug!("Scanning launcher info: {:?}", &root);
             let mut found = match root {
+                Root::Bottles(root) => bottles::scan(root, title_finder),
+                Root::Emulator(root) => emulator::scan(root),
                 Root::Heroic(root) => heroic::scan(root, title_finder, legendary.as_ref()),
+                Root::Itch(root) => itch::scan(root, title_finder),
                 Root::Legendary(root) => legendary::scan(root, title_finder),
                 Root::Lutris(root) => lutris::scan(root, title_finder),
                 _ => generic::scan(root, manifest, subjects),
             };
+            let root_filter = root.path_filter();
             found.retain(|_k, v| {
-                v.retain(|x| !x.is_empty());
+                v.retain(|x| !x.is_empty() && !x.install_dir.as_ref().is_some_and(|x| root_filter.is_ignored(x)));
                 !v.is_empty()
             });
             log::debug!("launcher games found ({:?}): {:#?}", &root, &found);
@@ -69,6 +113,9 @@ impl Launchers {
             }
         }
 
+        instance.flatpak = flatpak::scan(title_finder);
+        log::debug!("launcher games found (Flatpak): {:#?}", &instance.flatpak);
+
         instance
     }
 
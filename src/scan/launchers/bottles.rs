@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    prelude::{StrictPath, ENV_DEBUG},
+    resource::{config::root, manifest::Os},
+    scan::{launchers::LauncherGame, TitleFinder},
+};
+
+pub mod bottle {
+    use std::collections::BTreeMap;
+
+    pub const FILE: &str = "bottle.yml";
+
+    #[derive(Clone, Debug, Default, serde::Deserialize)]
+    pub struct Data {
+        #[serde(rename = "Name")]
+        pub name: String,
+        /// Programs added in Bottles, keyed by an opaque ID.
+        #[serde(rename = "External_Programs", default)]
+        pub external_programs: BTreeMap<String, Program>,
+    }
+
+    #[derive(Clone, Debug, Default, serde::Deserialize)]
+    pub struct Program {
+        pub name: String,
+        /// Full path to the executable.
+        #[serde(default)]
+        pub path: String,
+        /// Folder containing the executable.
+        /// Older versions of Bottles do not set this.
+        #[serde(default)]
+        pub folder: Option<String>,
+    }
+
+    impl Program {
+        pub fn install_dir(&self) -> Option<String> {
+            match &self.folder {
+                Some(folder) if !folder.trim().is_empty() => Some(folder.clone()),
+                _ => {
+                    let normalized = self.path.replace('\\', "/");
+                    let (parent, _) = normalized.rsplit_once('/')?;
+                    (!parent.is_empty()).then(|| parent.to_string())
+                }
+            }
+        }
+    }
+}
+
+/// A bottle and the folder that contains it, which acts as its Wine prefix.
+pub struct Bottle {
+    pub prefix: StrictPath,
+    pub data: bottle::Data,
+}
+
+pub fn scan(root: &root::Bottles, title_finder: &TitleFinder) -> HashMap<String, HashSet<LauncherGame>> {
+    let mut out = HashMap::<String, HashSet<LauncherGame>>::new();
+
+    for bottle in get_bottles(&root.path) {
+        for program in bottle.data.external_programs.values() {
+            let Some(official_title) = title_finder.find_one_by_normalized_name(&program.name) else {
+                log::trace!("Ignoring unrecognized game: {}", &program.name);
+                if std::env::var(ENV_DEBUG).is_ok() {
+                    eprintln!(
+                        "Ignoring unrecognized game from Bottles: {} (bottle = {})",
+                        &program.name, &bottle.data.name
+                    );
+                }
+                continue;
+            };
+
+            log::trace!(
+                "Detected game: {} | bottle: {}, raw title: {}",
+                &official_title,
+                &bottle.data.name,
+                &program.name
+            );
+            out.entry(official_title).or_default().insert(LauncherGame {
+                install_dir: program.install_dir().map(StrictPath::new),
+                prefix: Some(bottle.prefix.clone()),
+                platform: Some(Os::Windows),
+            });
+        }
+    }
+
+    out
+}
+
+pub fn get_bottles(source: &StrictPath) -> Vec<Bottle> {
+    let mut out = vec![];
+
+    for file in source.joined("bottles/*").joined(bottle::FILE).glob() {
+        let content = match file.try_read() {
+            Ok(content) => content,
+            Err(e) => {
+                log::debug!("In Bottles source '{:?}', unable to read {:?} | {:?}", source, &file, e);
+                continue;
+            }
+        };
+
+        match serde_yaml::from_str::<bottle::Data>(&content) {
+            Ok(data) => {
+                let Some(prefix) = file.parent() else {
+                    continue;
+                };
+                out.push(Bottle { prefix, data });
+            }
+            Err(e) => {
+                log::debug!(
+                    "In Bottles source '{:?}', unable to parse {:?} | {:?}",
+                    source,
+                    &file,
+                    e
+                );
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::{hash_map, hash_set};
+
+    use super::*;
+    use crate::{
+        resource::{manifest::Manifest, ResourceFile},
+        testing::repo,
+    };
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            game-1:
+              files:
+                <base>/file1.txt: {}
+            game-2:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&Default::default(), &manifest(), Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let root = root::Bottles {
+            path: format!("{}/tests/nonexistent", repo()).into(),
+            ..Default::default()
+        };
+        let games = scan(&root, &title_finder());
+        assert_eq!(HashMap::new(), games);
+    }
+
+    #[test]
+    fn scan_finds_all_games() {
+        let root = root::Bottles {
+            path: format!("{}/tests/launchers/bottles", repo()).into(),
+            ..Default::default()
+        };
+        let games = scan(&root, &title_finder());
+        assert_eq!(
+            hash_map! {
+                "game-1".to_string(): hash_set![LauncherGame {
+                    install_dir: Some(StrictPath::new("/games/game-1".to_string())),
+                    prefix: Some(StrictPath::new(format!("{}/tests/launchers/bottles/bottles/Gaming", repo()))),
+                    platform: Some(Os::Windows),
+                }],
+                "game-2".to_string(): hash_set![LauncherGame {
+                    install_dir: Some(StrictPath::new("/games/game-2".to_string())),
+                    prefix: Some(StrictPath::new(format!("{}/tests/launchers/bottles/bottles/Gaming", repo()))),
+                    platform: Some(Os::Windows),
+                }],
+            },
+            games,
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    prelude::StrictPath,
+    resource::{
+        config::root,
+        manifest::{Game, GameFileEntry, Manifest, Os, Tag},
+    },
+    scan::LauncherGame,
+};
+
+static RE_RETROARCH_STATE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.state(\d+|\.auto)?(\.png)?$").unwrap());
+static RE_DOLPHIN_STATE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([A-Z0-9]{4})[A-Z0-9]{2}\.s\d+$").unwrap());
+static RE_DOLPHIN_GCI: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Z0-9]{2}-([A-Z0-9]{4})-.*\.gci$").unwrap());
+static RE_PCSX2_STATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(.+?) \([0-9A-Fa-f]{8}\)\.\d+\.p2s(\.backup)?$").unwrap());
+
+/// Emulators whose folder layout we recognize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Emulator {
+    RetroArch,
+    Dolphin,
+    Pcsx2,
+}
+
+impl Emulator {
+    const ALL: &'static [Self] = &[Self::RetroArch, Self::Dolphin, Self::Pcsx2];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::RetroArch => "RetroArch",
+            Self::Dolphin => "Dolphin",
+            Self::Pcsx2 => "PCSX2",
+        }
+    }
+
+    /// Folders that only exist in this emulator's data folder.
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            Self::RetroArch => &["retroarch.cfg", "saves", "states"],
+            Self::Dolphin => &["StateSaves", "GC", "Wii"],
+            Self::Pcsx2 => &["sstates", "memcards"],
+        }
+    }
+
+    fn detect(path: &StrictPath) -> Vec<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|emulator| emulator.markers().iter().any(|marker| path.joined(marker).exists()))
+            .collect()
+    }
+}
+
+/// Save data found for one piece of content (usually one ROM).
+#[derive(Debug, Default)]
+struct Content {
+    files: BTreeMap<String, Tag>,
+}
+
+impl Content {
+    fn add(&mut self, pattern: String, tag: Tag) {
+        self.files.insert(pattern, tag);
+    }
+}
+
+fn escape(raw: &str) -> String {
+    globetter::Pattern::escape(raw)
+}
+
+fn files(root: &str, pattern: &str) -> Vec<StrictPath> {
+    StrictPath::new(format!("{root}/{pattern}"))
+        .glob()
+        .into_iter()
+        .filter(|x| x.is_file())
+        .collect()
+}
+
+fn find_retroarch(root: &str, out: &mut BTreeMap<String, Content>) {
+    for file in files(root, "saves/**/*") {
+        let Some(leaf) = file.leaf() else { continue };
+        let Some((name, _)) = leaf.rsplit_once('.') else {
+            continue;
+        };
+        out.entry(name.to_string())
+            .or_default()
+            .add(format!("{root}/saves/**/{}.*", escape(name)), Tag::Save);
+    }
+
+    for file in files(root, "states/**/*") {
+        let Some(leaf) = file.leaf() else { continue };
+        let Some(found) = RE_RETROARCH_STATE.find(&leaf) else {
+            continue;
+        };
+        let name = &leaf[..found.start()];
+        out.entry(name.to_string())
+            .or_default()
+            .add(format!("{root}/states/**/{}.state*", escape(name)), Tag::Save);
+    }
+}
+
+fn find_dolphin(root: &str, out: &mut BTreeMap<String, Content>) {
+    for file in files(root, "StateSaves/*") {
+        let Some(leaf) = file.leaf() else { continue };
+        let Some(captures) = RE_DOLPHIN_STATE.captures(&leaf) else {
+            continue;
+        };
+        let code = &captures[1];
+        out.entry(code.to_string())
+            .or_default()
+            .add(format!("{root}/StateSaves/{code}??.s*"), Tag::Save);
+    }
+
+    for file in files(root, "GC/*/Card ?/*.gci") {
+        let Some(leaf) = file.leaf() else { continue };
+        let Some(captures) = RE_DOLPHIN_GCI.captures(&leaf) else {
+            continue;
+        };
+        let code = &captures[1];
+        out.entry(code.to_string())
+            .or_default()
+            .add(format!("{root}/GC/*/Card ?/??-{code}-*.gci"), Tag::Save);
+    }
+
+    for folder in StrictPath::new(format!("{root}/Wii/title/00010000/*/data")).glob() {
+        let Some(title_id) = folder.parent().and_then(|x| x.leaf()) else {
+            continue;
+        };
+        let Some(code) = decode_wii_title(&title_id) else {
+            continue;
+        };
+        out.entry(code.clone())
+            .or_default()
+            .add(format!("{root}/Wii/title/00010000/{title_id}/data"), Tag::Save);
+    }
+
+    for file in files(root, "GC/MemoryCard*.raw") {
+        let Some(leaf) = file.leaf() else { continue };
+        let name = leaf.trim_end_matches(".raw");
+        out.entry(format!("{} - {}", Emulator::Dolphin.label(), name))
+            .or_default()
+            .add(format!("{root}/GC/{}", escape(&leaf)), Tag::Save);
+    }
+}
+
+/// Wii title IDs are the hex-encoded ASCII game code (e.g., `524d4745` -> `RMGE`).
+fn decode_wii_title(title_id: &str) -> Option<String> {
+    if title_id.len() != 8 {
+        return None;
+    }
+    let mut code = String::new();
+    for i in (0..8).step_by(2) {
+        let byte = u8::from_str_radix(title_id.get(i..i + 2)?, 16).ok()?;
+        if !byte.is_ascii_alphanumeric() {
+            return None;
+        }
+        code.push(byte.to_ascii_uppercase() as char);
+    }
+    Some(code)
+}
+
+fn find_pcsx2(root: &str, out: &mut BTreeMap<String, Content>) {
+    for file in files(root, "sstates/*") {
+        let Some(leaf) = file.leaf() else { continue };
+        let Some(captures) = RE_PCSX2_STATE.captures(&leaf) else {
+            continue;
+        };
+        let serial = &captures[1];
+        out.entry(serial.to_string())
+            .or_default()
+            .add(format!("{root}/sstates/{} (*).*", escape(serial)), Tag::Save);
+    }
+
+    for card in StrictPath::new(format!("{root}/memcards/*")).glob() {
+        let Some(leaf) = card.leaf() else { continue };
+        let name = if card.is_dir() {
+            leaf.as_str()
+        } else if let Some(name) = leaf.strip_suffix(".ps2") {
+            name
+        } else {
+            continue;
+        };
+        out.entry(format!("{} - {}", Emulator::Pcsx2.label(), name))
+            .or_default()
+            .add(format!("{root}/memcards/{}", escape(&leaf)), Tag::Save);
+    }
+}
+
+fn find_content(root: &root::Emulator) -> BTreeMap<String, Content> {
+    let mut out = BTreeMap::new();
+
+    let Ok(interpreted) = root.path.interpret() else {
+        return out;
+    };
+    let escaped = escape(&interpreted);
+
+    for emulator in Emulator::detect(&root.path) {
+        log::debug!(
+            "Detected {} layout in emulator root: {:?}",
+            emulator.label(),
+            &root.path
+        );
+        match emulator {
+            Emulator::RetroArch => find_retroarch(&escaped, &mut out),
+            Emulator::Dolphin => find_dolphin(&escaped, &mut out),
+            Emulator::Pcsx2 => find_pcsx2(&escaped, &mut out),
+        }
+    }
+
+    out
+}
+
+/// Build pseudo-games for each piece of content with save data in the root.
+pub fn find_games(root: &root::Emulator) -> Manifest {
+    let mut manifest = Manifest::default();
+
+    for (name, content) in find_content(root) {
+        let game = Game {
+            files: content
+                .files
+                .into_iter()
+                .map(|(path, tag)| {
+                    (
+                        path,
+                        GameFileEntry {
+                            tags: [tag].into(),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        manifest.0.insert(name, game);
+    }
+
+    manifest
+}
+
+/// Content with save data counts as installed,
+/// since we have no other way to know which ROMs are available.
+pub fn scan(root: &root::Emulator) -> HashMap<String, HashSet<LauncherGame>> {
+    find_content(root)
+        .into_keys()
+        .map(|name| {
+            (
+                name,
+                HashSet::from([LauncherGame {
+                    install_dir: None,
+                    prefix: None,
+                    platform: Some(Os::HOST),
+                }]),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::repo;
+
+    fn root() -> root::Emulator {
+        root::Emulator {
+            path: format!("{}/tests/launchers/emulator", repo()).into(),
+            ..Default::default()
+        }
+    }
+
+    fn base() -> String {
+        escape(
+            &StrictPath::new(format!("{}/tests/launchers/emulator", repo()))
+                .interpret()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn can_decode_wii_title() {
+        assert_eq!(Some("RMGE".to_string()), decode_wii_title("524d4745"));
+        assert_eq!(None, decode_wii_title("00000000"));
+        assert_eq!(None, decode_wii_title("524d47"));
+    }
+
+    #[test]
+    fn finds_nothing_when_folder_does_not_exist() {
+        let root = root::Emulator {
+            path: format!("{}/tests/nonexistent", repo()).into(),
+            ..Default::default()
+        };
+        assert_eq!(Manifest::default(), find_games(&root));
+    }
+
+    #[test]
+    fn finds_all_content() {
+        let manifest = find_games(&root());
+        let base = base();
+
+        let mut names: Vec<_> = manifest.0.keys().cloned().collect();
+        names.sort();
+        assert_eq!(
+            vec![
+                "Dolphin - MemoryCardA.USA".to_string(),
+                "GALE".to_string(),
+                "PCSX2 - Mcd001".to_string(),
+                "RMGE".to_string(),
+                "SLUS-20312".to_string(),
+                "Super Game (USA)".to_string(),
+            ],
+            names,
+        );
+
+        let files = |name: &str| -> Vec<String> { manifest.0[name].files.keys().cloned().collect() };
+        assert_eq!(
+            vec![
+                format!("{base}/saves/**/Super Game (USA).*"),
+                format!("{base}/states/**/Super Game (USA).state*"),
+            ],
+            files("Super Game (USA)"),
+        );
+        assert_eq!(
+            vec![
+                format!("{base}/GC/*/Card ?/??-GALE-*.gci"),
+                format!("{base}/StateSaves/GALE??.s*"),
+            ],
+            files("GALE"),
+        );
+        assert_eq!(vec![format!("{base}/sstates/SLUS-20312 (*).*")], files("SLUS-20312"));
+    }
+
+    #[test]
+    fn content_counts_as_installed() {
+        let games = scan(&root());
+        assert!(games.contains_key("Super Game (USA)"));
+        assert!(games.contains_key("SLUS-20312"));
+    }
+}
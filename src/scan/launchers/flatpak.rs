@@ -0,0 +1,116 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    path::CommonPath,
+    prelude::{StrictPath, ENV_DEBUG},
+    resource::manifest::Os,
+    scan::{launchers::LauncherGame, TitleFinder, TitleQuery},
+};
+
+/// Standard locations of the user and system Flatpak installations.
+pub fn installations() -> Vec<StrictPath> {
+    let mut out = vec![];
+
+    if let Some(data) = CommonPath::Data.get() {
+        out.push(StrictPath::new(format!("{data}/flatpak")));
+    }
+    out.push(StrictPath::new("/var/lib/flatpak".to_string()));
+
+    out
+}
+
+pub fn scan(title_finder: &TitleFinder) -> HashMap<String, HashSet<LauncherGame>> {
+    if Os::HOST != Os::Linux {
+        return HashMap::new();
+    }
+    scan_installations(&installations(), title_finder)
+}
+
+pub fn scan_installations(
+    installations: &[StrictPath],
+    title_finder: &TitleFinder,
+) -> HashMap<String, HashSet<LauncherGame>> {
+    let mut out = HashMap::<String, HashSet<LauncherGame>>::new();
+
+    for installation in installations {
+        let apps = installation.joined("app");
+        let Ok(entries) = apps.read_dir() else {
+            log::trace!("Flatpak installation does not have any apps: {:?}", &apps);
+            continue;
+        };
+
+        for entry in entries.filter_map(|x| x.ok()) {
+            let app_id = entry.file_name().to_string_lossy().to_string();
+
+            let Some(official_title) = title_finder.find_one(TitleQuery {
+                flatpak_id: Some(app_id.clone()),
+                ..Default::default()
+            }) else {
+                log::trace!("Ignoring unrecognized Flatpak app: {}", &app_id);
+                if std::env::var(ENV_DEBUG).is_ok() {
+                    eprintln!("Ignoring unrecognized Flatpak app: {}", &app_id);
+                }
+                continue;
+            };
+
+            log::trace!("Detected game: {} | Flatpak app: {}", &official_title, &app_id);
+            out.entry(official_title).or_default().insert(LauncherGame {
+                install_dir: Some(apps.joined(&app_id).joined("current/active/files")),
+                prefix: None,
+                platform: Some(Os::Linux),
+            });
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::{hash_map, hash_set};
+
+    use super::*;
+    use crate::{
+        resource::{manifest::Manifest, ResourceFile},
+        testing::repo,
+    };
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            game-1:
+              id:
+                flatpak: com.example.Game
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&Default::default(), &manifest(), Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let installations = vec![StrictPath::new(format!("{}/tests/nonexistent", repo()))];
+        let games = scan_installations(&installations, &title_finder());
+        assert_eq!(HashMap::new(), games);
+    }
+
+    #[test]
+    fn scan_finds_all_games() {
+        let installations = vec![StrictPath::new(format!("{}/tests/launchers/flatpak", repo()))];
+        let games = scan_installations(&installations, &title_finder());
+        assert_eq!(
+            hash_map! {
+                "game-1".to_string(): hash_set![LauncherGame {
+                    install_dir: Some(StrictPath::new(format!("{}/tests/launchers/flatpak/app/com.example.Game/current/active/files", repo()))),
+                    prefix: None,
+                    platform: Some(Os::Linux),
+                }],
+            },
+            games,
+        );
+    }
+}
@@ -66,6 +66,19 @@ pub fn scan(
     games
 }
 
+/// All Wine/Proton prefixes configured for Heroic games.
+pub fn find_prefixes(root: &root::Heroic) -> Vec<StrictPath> {
+    root.path
+        .joined("GamesConfig/*.json")
+        .glob()
+        .into_iter()
+        .filter_map(|file| {
+            let app_name = file.file_stem()?;
+            find_prefix(&root.path, &app_name, Some("windows"), &app_name)
+        })
+        .collect()
+}
+
 fn find_prefix(
     heroic_path: &StrictPath,
     game_name: &str,
@@ -6,7 +6,10 @@ use crate::{
     prelude::ENV_DEBUG,
     resource::{config::root, manifest::Os},
     scan::{
-        launchers::{heroic::find_prefix, LauncherGame},
+        launchers::{
+            heroic::{find_prefix, games_config},
+            LauncherGame,
+        },
         TitleFinder, TitleQuery,
     },
 };
@@ -46,6 +49,39 @@ pub mod library {
     }
 }
 
+/// Heroic keeps GOG's cloud save definitions in each game's config.
+pub mod saves {
+    use std::collections::HashMap;
+
+    #[derive(serde::Deserialize, Debug)]
+    pub struct Data(pub HashMap<String, Entry>);
+
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(untagged)]
+    pub enum Entry {
+        #[serde(rename_all = "camelCase")]
+        Config {
+            #[serde(alias = "cloudSaves")]
+            gog_saves: Vec<Location>,
+        },
+        IgnoreOther(serde::de::IgnoredAny),
+    }
+
+    #[derive(serde::Deserialize, Debug)]
+    pub struct Location {
+        pub location: String,
+    }
+}
+
+/// Save folders that Heroic knows about for an installed GOG game.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SaveLocations {
+    /// Title from Heroic's library, which may not match the manifest.
+    pub title: String,
+    pub gog_id: Option<u64>,
+    pub paths: Vec<StrictPath>,
+}
+
 pub fn scan(root: &root::Heroic, title_finder: &TitleFinder) -> HashMap<String, HashSet<LauncherGame>> {
     let mut games = HashMap::<String, HashSet<LauncherGame>>::new();
 
@@ -59,55 +95,136 @@ pub fn scan(root: &root::Heroic, title_finder: &TitleFinder) -> HashMap<String,
     }
 
     // iterate over all games found in HEROCONFIGDIR/gog_store/installed.json and call find_prefix
+    for game in get_installed(root) {
+        let Some(game_title) = game_titles.get(&game.app_name) else {
+            continue;
+        };
+
+        let gog_id: Option<u64> = game.app_name.parse().ok();
+
+        let query = TitleQuery {
+            names: vec![game_title.to_owned()],
+            gog_id,
+            normalized: true,
+            ..Default::default()
+        };
+        let Some(official_title) = title_finder.find_one(query) else {
+            log::trace!("Ignoring unrecognized game: {}, app: {}", &game_title, &game.app_name);
+            if std::env::var(ENV_DEBUG).is_ok() {
+                eprintln!(
+                    "Ignoring unrecognized game from Heroic/GOG: {} (app = {})",
+                    &game_title, &game.app_name
+                );
+            }
+            continue;
+        };
+
+        log::trace!(
+            "Detected game: {} | app: {}, raw title: {}",
+            &official_title,
+            &game.app_name,
+            &game_title
+        );
+        let prefix = find_prefix(&root.path, game_title, Some(&game.platform), &game.app_name);
+        games.entry(official_title).or_default().insert(LauncherGame {
+            install_dir: Some(StrictPath::new(game.install_path.clone())),
+            prefix,
+            platform: Some(Os::from(game.platform.as_str())),
+        });
+    }
+
+    games
+}
+
+fn get_installed(root: &root::Heroic) -> Vec<installed::Game> {
     let installed_path = root.path.joined(installed::PATH);
     let content = installed_path.read();
 
     match serde_json::from_str::<installed::Data>(&content.unwrap_or_default()) {
-        Ok(installed_games) => {
-            for game in installed_games.installed {
-                let Some(game_title) = game_titles.get(&game.app_name) else {
-                    continue;
-                };
-
-                let gog_id: Option<u64> = game.app_name.parse().ok();
-
-                let query = TitleQuery {
-                    names: vec![game_title.to_owned()],
-                    gog_id,
-                    normalized: true,
-                    ..Default::default()
-                };
-                let Some(official_title) = title_finder.find_one(query) else {
-                    log::trace!("Ignoring unrecognized game: {}, app: {}", &game_title, &game.app_name);
-                    if std::env::var(ENV_DEBUG).is_ok() {
-                        eprintln!(
-                            "Ignoring unrecognized game from Heroic/GOG: {} (app = {})",
-                            &game_title, &game.app_name
-                        );
-                    }
-                    continue;
-                };
-
-                log::trace!(
-                    "Detected game: {} | app: {}, raw title: {}",
-                    &official_title,
-                    &game.app_name,
-                    &game_title
-                );
-                let prefix = find_prefix(&root.path, game_title, Some(&game.platform), &game.app_name);
-                games.entry(official_title).or_default().insert(LauncherGame {
-                    install_dir: Some(StrictPath::new(game.install_path.clone())),
-                    prefix,
-                    platform: Some(Os::from(game.platform.as_str())),
-                });
-            }
-        }
+        Ok(installed_games) => installed_games.installed,
         Err(e) => {
             log::warn!("Unable to parse installed list from {:?}: {}", &installed_path, e);
+            vec![]
         }
     }
+}
 
-    games
+/// Find the save folders from GOG's cloud save definitions for each installed game.
+pub fn find_save_locations(root: &root::Heroic) -> Vec<SaveLocations> {
+    let mut out = vec![];
+
+    let game_titles: HashMap<String, String> = get_library(root)
+        .into_iter()
+        .map(|game| (game.app_name, game.title))
+        .collect();
+
+    for game in get_installed(root) {
+        let Some(title) = game_titles.get(&game.app_name) else {
+            continue;
+        };
+
+        let config_path = root.path.joined(&games_config::path(&game.app_name));
+        let Some(content) = config_path.read() else {
+            continue;
+        };
+        let locations = match serde_json::from_str::<saves::Data>(&content) {
+            Ok(data) => match data.0.get(&game.app_name) {
+                Some(saves::Entry::Config { gog_saves }) => gog_saves.iter().map(|x| x.location.clone()).collect(),
+                _ => vec![],
+            },
+            Err(e) => {
+                log::trace!("Failed to read {:?}: {}", &config_path, e);
+                continue;
+            }
+        };
+        if locations.is_empty() {
+            continue;
+        }
+
+        let prefix = find_prefix(&root.path, title, Some(&game.platform), &game.app_name);
+        let paths: Vec<_> = locations
+            .iter()
+            .filter_map(|location| {
+                let resolved = resolve_save_location(location, &game.install_path, prefix.as_ref());
+                if resolved.is_none() {
+                    log::debug!("Unable to resolve Heroic/GOG save location for {}: {}", title, location);
+                }
+                resolved
+            })
+            .collect();
+
+        log::trace!("Found Heroic/GOG save locations for {}: {:?}", title, &paths);
+        out.push(SaveLocations {
+            title: title.clone(),
+            gog_id: game.app_name.parse().ok(),
+            paths,
+        });
+    }
+
+    out
+}
+
+/// Locations may be Linux paths, Windows paths (inside of the game's prefix when running through Wine),
+/// or relative to the install folder.
+fn resolve_save_location(location: &str, install_dir: &str, prefix: Option<&StrictPath>) -> Option<StrictPath> {
+    let location = location
+        .replace("<?INSTALL?>", install_dir.trim_end_matches(['/', '\\']))
+        .replace('\\', "/");
+
+    let mut chars = location.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(drive), Some(':'), Some('/')) if drive.is_ascii_alphabetic() => match prefix {
+            Some(prefix) if !cfg!(target_os = "windows") => Some(StrictPath::new(format!(
+                "{}/drive_{}/{}",
+                prefix.render(),
+                drive.to_ascii_lowercase(),
+                &location[3..]
+            ))),
+            _ => Some(StrictPath::new(location)),
+        },
+        (Some('/'), ..) => Some(StrictPath::new(location)),
+        _ => None,
+    }
 }
 
 pub fn get_library(root: &root::Heroic) -> Vec<library::Game> {
@@ -169,6 +286,7 @@ mod tests {
     fn scan_finds_all_games_without_store_cache() {
         let root = root::Heroic {
             path: format!("{}/tests/launchers/heroic-gog-without-store-cache", repo()).into(),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -187,6 +305,7 @@ mod tests {
     fn scan_finds_all_games_with_store_cache() {
         let root = root::Heroic {
             path: format!("{}/tests/launchers/heroic-gog-with-store-cache", repo()).into(),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -200,4 +319,53 @@ mod tests {
             games,
         );
     }
+
+    #[test]
+    fn can_find_save_locations() {
+        let root = root::Heroic {
+            path: format!("{}/tests/launchers/heroic-gog-with-store-cache", repo()).into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![SaveLocations {
+                title: "game-1".to_string(),
+                gog_id: None,
+                paths: vec![
+                    StrictPath::new("/prefixes/game-1/drive_c/users/user/Saved Games/Game 1".to_string()),
+                    StrictPath::new("/games/game-1/settings".to_string()),
+                ],
+            }],
+            find_save_locations(&root),
+        );
+    }
+
+    #[test]
+    fn can_resolve_save_location() {
+        let prefix = StrictPath::new("/prefix".to_string());
+        assert_eq!(
+            Some(StrictPath::new(if cfg!(target_os = "windows") {
+                "C:/users/foo/AppData".to_string()
+            } else {
+                "/prefix/drive_c/users/foo/AppData".to_string()
+            })),
+            resolve_save_location("C:\\users\\foo\\AppData", "/games/game", Some(&prefix)),
+        );
+        assert_eq!(
+            Some(StrictPath::new("C:/users/foo/AppData".to_string())),
+            resolve_save_location("C:\\users\\foo\\AppData", "/games/game", None),
+        );
+        assert_eq!(
+            Some(StrictPath::new("C:/games/game/saves".to_string())),
+            resolve_save_location("<?INSTALL?>\\saves", "C:\\games\\game", None),
+        );
+        assert_eq!(
+            Some(StrictPath::new("/games/game/saves".to_string())),
+            resolve_save_location("<?INSTALL?>/saves", "/games/game/", None),
+        );
+        assert_eq!(
+            Some(StrictPath::new("/home/foo/.config/game".to_string())),
+            resolve_save_location("/home/foo/.config/game", "/games/game", None),
+        );
+        assert_eq!(None, resolve_save_location("<?DOCUMENTS?>/game", "/games/game", None));
+    }
 }
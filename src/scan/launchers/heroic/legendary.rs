@@ -102,6 +102,7 @@ mod tests {
     fn scan_finds_all_games() {
         let root = root::Heroic {
             path: format!("{}/tests/launchers/heroic-gog-without-store-cache", repo()).into(),
+            ..Default::default()
         };
         let legendary = Some(StrictPath::new(format!("{}/tests/launchers/legendary", repo())));
         let games = scan(&root, &title_finder(), legendary.as_ref());
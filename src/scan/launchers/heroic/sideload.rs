@@ -123,6 +123,7 @@ mod tests {
     fn scan_finds_all_games() {
         let root = root::Heroic {
             path: format!("{}/tests/launchers/heroic-sideload", repo()).into(),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
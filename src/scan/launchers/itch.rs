@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    prelude::{StrictPath, ENV_DEBUG},
+    resource::{config::root, manifest::Os},
+    scan::{LauncherGame, TitleFinder},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    NoDatabase,
+    #[allow(unused)]
+    Sql(rusqlite::Error),
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(value: rusqlite::Error) -> Self {
+        Self::Sql(value)
+    }
+}
+
+/// https://github.com/itchio/butler/blob/master/butlerd/types.go
+mod verdict {
+    #[derive(Debug, Default, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Data {
+        #[serde(default)]
+        pub base_path: String,
+        #[serde(default)]
+        pub candidates: Vec<Candidate>,
+    }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    pub struct Candidate {
+        #[serde(default)]
+        pub flavor: String,
+    }
+}
+
+/// An installed game, known to butler as a "cave".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cave {
+    pub title: String,
+    pub install_dir: Option<StrictPath>,
+    pub platform: Option<Os>,
+}
+
+impl Cave {
+    /// If the game is installed inside of a Wine prefix,
+    /// then it is most likely a Windows build that the user runs with Wine.
+    pub fn prefix(&self) -> Option<StrictPath> {
+        let install_dir = self.install_dir.as_ref()?.raw().replace('\\', "/");
+        let (prefix, _) = install_dir.split_once("/drive_c/")?;
+        (!prefix.is_empty()).then(|| StrictPath::new(prefix.to_string()))
+    }
+
+    pub fn launcher_game(&self) -> LauncherGame {
+        let prefix = self.prefix();
+        LauncherGame {
+            install_dir: self.install_dir.clone(),
+            platform: if prefix.is_some() {
+                Some(Os::Windows)
+            } else {
+                self.platform
+            },
+            prefix,
+        }
+    }
+}
+
+pub fn scan(root: &root::Itch, title_finder: &TitleFinder) -> HashMap<String, HashSet<LauncherGame>> {
+    let mut out = HashMap::<String, HashSet<LauncherGame>>::new();
+
+    let caves = match get_caves(&root.path) {
+        Ok(caves) => caves,
+        Err(e) => {
+            log::info!("Unable to read itch database in {:?}: {:?}", &root.path, e);
+            return out;
+        }
+    };
+
+    for cave in caves {
+        let Some(official_title) = title_finder.find_one_by_normalized_name(&cave.title) else {
+            log::trace!("Ignoring unrecognized game: {}", &cave.title);
+            if std::env::var(ENV_DEBUG).is_ok() {
+                eprintln!("Ignoring unrecognized game from itch: {}", &cave.title);
+            }
+            continue;
+        };
+
+        log::trace!("Detected game: {} | raw title: {}", &official_title, &cave.title);
+        out.entry(official_title).or_default().insert(cave.launcher_game());
+    }
+
+    out
+}
+
+pub fn get_caves(source: &StrictPath) -> Result<Vec<Cave>, Error> {
+    #[derive(Debug)]
+    struct Row {
+        title: Option<String>,
+        install_folder_name: Option<String>,
+        custom_install_folder: Option<String>,
+        location: Option<String>,
+        verdict: Option<String>,
+    }
+
+    let db_file = source.joined("db/butler.db");
+    if !db_file.is_file() {
+        return Err(Error::NoDatabase);
+    }
+
+    let mut caves = vec![];
+
+    let Ok(file) = db_file.as_std_path_buf() else {
+        return Ok(caves);
+    };
+    let conn = rusqlite::Connection::open_with_flags(file, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT games.title, caves.install_folder_name, caves.custom_install_folder, install_locations.path, caves.verdict
+        FROM caves
+        LEFT JOIN games ON caves.game_id = games.id
+        LEFT JOIN install_locations ON caves.install_location_id = install_locations.id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Row {
+            title: row.get(0)?,
+            install_folder_name: row.get(1)?,
+            custom_install_folder: row.get(2)?,
+            location: row.get(3)?,
+            verdict: row.get(4)?,
+        })
+    })?;
+
+    for row in rows {
+        match row {
+            Ok(row) => {
+                log::trace!("Row = {row:?}");
+
+                let Some(title) = row.title.filter(|x| !x.trim().is_empty()) else {
+                    log::warn!("Ignoring itch cave without a game title");
+                    continue;
+                };
+
+                let verdict = row
+                    .verdict
+                    .and_then(|x| serde_json::from_str::<verdict::Data>(&x).ok())
+                    .unwrap_or_default();
+
+                let install_dir = match (row.custom_install_folder, row.location, row.install_folder_name) {
+                    (Some(custom), _, _) if !custom.trim().is_empty() => Some(custom),
+                    (_, Some(location), Some(folder)) if !location.is_empty() && !folder.is_empty() => {
+                        Some(format!("{}/{}", location.trim_end_matches(['/', '\\']), folder))
+                    }
+                    _ => (!verdict.base_path.is_empty()).then_some(verdict.base_path.clone()),
+                };
+
+                let platform = verdict
+                    .candidates
+                    .iter()
+                    .find_map(|candidate| match candidate.flavor.as_str() {
+                        "windows" | "windows-script" | "msi" => Some(Os::Windows),
+                        "linux" => Some(Os::Linux),
+                        "macos" | "app-macos" => Some(Os::Mac),
+                        _ => None,
+                    });
+
+                caves.push(Cave {
+                    title,
+                    install_dir: install_dir.map(StrictPath::new),
+                    platform,
+                });
+            }
+            Err(e) => {
+                log::warn!("Row error: {e:?}");
+            }
+        }
+    }
+
+    Ok(caves)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::{hash_map, hash_set};
+
+    use super::*;
+    use crate::{
+        resource::{manifest::Manifest, ResourceFile},
+        testing::repo,
+    };
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            game-1:
+              files:
+                <base>/file1.txt: {}
+            game-2:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn title_finder() -> TitleFinder {
+        TitleFinder::new(&Default::default(), &manifest(), Default::default())
+    }
+
+    #[test]
+    fn scan_finds_nothing_when_folder_does_not_exist() {
+        let root = root::Itch {
+            path: format!("{}/tests/nonexistent", repo()).into(),
+            ..Default::default()
+        };
+        let games = scan(&root, &title_finder());
+        assert_eq!(HashMap::new(), games);
+    }
+
+    #[test]
+    fn scan_finds_all_games() {
+        let root = root::Itch {
+            path: format!("{}/tests/launchers/itch", repo()).into(),
+            ..Default::default()
+        };
+        let games = scan(&root, &title_finder());
+        assert_eq!(
+            hash_map! {
+                "game-1".to_string(): hash_set![LauncherGame {
+                    install_dir: Some(StrictPath::new("/home/user/.config/itch/apps/game-1".to_string())),
+                    prefix: None,
+                    platform: Some(Os::Linux),
+                }],
+                "game-2".to_string(): hash_set![LauncherGame {
+                    install_dir: Some(StrictPath::new("/home/user/.wine/drive_c/itch/game-2".to_string())),
+                    prefix: Some(StrictPath::new("/home/user/.wine".to_string())),
+                    platform: Some(Os::Windows),
+                }],
+            },
+            games,
+        );
+    }
+
+    #[test]
+    fn can_read_custom_install_folder() {
+        let caves = get_caves(&StrictPath::new(format!("{}/tests/launchers/itch", repo()))).unwrap();
+        assert_eq!(
+            Some(&Cave {
+                title: "Unknown".to_string(),
+                install_dir: Some(StrictPath::new("/custom/unknown".to_string())),
+                platform: Some(Os::Windows),
+            }),
+            caves.iter().find(|x| x.title == "Unknown"),
+        );
+    }
+}
@@ -109,6 +109,7 @@ mod tests {
     fn scan_finds_nothing_when_folder_does_not_exist() {
         let root = root::Legendary {
             path: format!("{}/tests/nonexistent", repo()).into(),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(HashMap::new(), games);
@@ -118,6 +119,7 @@ mod tests {
     fn scan_finds_all_games() {
         let root = root::Legendary {
             path: format!("{}/tests/launchers/legendary", repo()).into(),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
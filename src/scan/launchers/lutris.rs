@@ -168,6 +168,22 @@ pub fn scan(root: &root::Lutris, title_finder: &TitleFinder) -> HashMap<String,
     games
 }
 
+/// All Wine prefixes configured for Lutris games.
+pub fn find_prefixes(root: &root::Lutris) -> Vec<StrictPath> {
+    let mut prefixes = vec![];
+
+    if let Ok(db_games) = scan_db(root) {
+        prefixes.extend(db_games.into_values().filter_map(|pending| pending.prefix));
+    }
+    for spec_path in root.path.joined("games/*.y*ml").glob() {
+        if let Some(prefix) = read_spec(&spec_path).and_then(|pending| pending.prefix) {
+            prefixes.push(prefix);
+        }
+    }
+
+    prefixes
+}
+
 fn scan_db(root: &root::Lutris) -> Result<HashMap<spec::Id, Pending>, Error> {
     #[derive(Debug)]
     struct Row {
@@ -349,6 +365,7 @@ mod tests {
         let root = root::Lutris {
             path: format!("{}/tests/nonexistent", repo()).into(),
             database: None,
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(HashMap::new(), games);
@@ -359,6 +376,7 @@ mod tests {
         let root = root::Lutris {
             path: format!("{}/tests/launchers/lutris-spec", repo()).into(),
             database: None,
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -378,6 +396,7 @@ mod tests {
         let root = root::Lutris {
             path: format!("{}/tests/launchers/lutris-db", repo()).into(),
             database: None,
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -397,6 +416,7 @@ mod tests {
         let root = root::Lutris {
             path: format!("{}/tests/launchers/lutris-merged", repo()).into(),
             database: None,
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -421,6 +441,7 @@ mod tests {
         let root = root::Lutris {
             path: format!("{}/tests/launchers/lutris-split/config", repo()).into(),
             database: Some(format!("{}/tests/launchers/lutris-split/data/pga.db", repo()).into()),
+            ..Default::default()
         };
         let games = scan(&root, &title_finder());
         assert_eq!(
@@ -10,7 +10,7 @@ use crate::{
     prelude::{AnyError, INVALID_FILE_CHARS},
     resource::{
         config::{
-            BackupFormat, BackupFormats, RedirectConfig, Retention, ToggledPaths, ToggledRegistry, ZipCompression,
+            BackupFormat, BackupFormats, RedirectConfig, Retention, ToggledPaths, ToggledRegistry, User, ZipCompression,
         },
         manifest::Os,
     },
@@ -128,6 +128,20 @@ impl Backup {
         }
     }
 
+    pub fn snapshot(&self) -> bool {
+        match self {
+            Self::Full(x) => x.snapshot,
+            Self::Differential(x) => x.snapshot,
+        }
+    }
+
+    pub fn set_snapshot(&mut self, snapshot: bool) {
+        match self {
+            Self::Full(x) => x.snapshot = snapshot,
+            Self::Differential(x) => x.snapshot = snapshot,
+        }
+    }
+
     pub fn label(&self) -> String {
         match self {
             Self::Full(x) => x.label(),
@@ -242,6 +256,10 @@ pub struct FullBackup {
     /// Locked backups do not count toward retention limits and are never deleted.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub locked: bool,
+    /// Snapshots are taken periodically while a wrapped game is running
+    /// and have their own retention limit.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub snapshot: bool,
     pub files: BTreeMap<String, IndividualMappingFile>,
     pub registry: IndividualMappingRegistry,
     pub children: VecDeque<DifferentialBackup>,
@@ -282,6 +300,10 @@ pub struct DifferentialBackup {
     /// Locked backups do not count toward retention limits and are never deleted.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub locked: bool,
+    /// Snapshots are taken periodically while a wrapped game is running
+    /// and have their own retention limit.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub snapshot: bool,
     pub files: BTreeMap<String, Option<IndividualMappingFile>>,
     pub registry: Option<IndividualMappingRegistry>,
 }
@@ -322,6 +344,21 @@ impl DifferentialBackup {
 pub struct IndividualMappingFile {
     pub hash: String,
     pub size: u64,
+    /// If set, the file was backed up as a symbolic link to this target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// If set, the file belongs to this OS user's home folder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+impl IndividualMappingFile {
+    pub fn evaluate_restore(&self, original_path: &StrictPath) -> ScanChange {
+        match &self.link {
+            Some(link) => ScanChange::evaluate_restore_link(original_path, link),
+            None => ScanChange::evaluate_restore(original_path, &self.hash),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -335,6 +372,10 @@ pub struct IndividualMappingRegistry {
 pub struct IndividualMapping {
     pub name: String,
     pub drives: BTreeMap<String, String>,
+    /// Home folder of each OS user with files in the backup.
+    /// Each user's files are stored in their own `user-*` folder.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub users: BTreeMap<String, String>,
     pub backups: VecDeque<FullBackup>,
 }
 
@@ -379,34 +420,72 @@ impl IndividualMapping {
         }
     }
 
-    pub fn game_file(&mut self, base: &StrictPath, original_file: &StrictPath, backup: &str) -> StrictPath {
+    fn user_folder_name(user: &str) -> String {
+        format!("user-{}", escape_folder_name(user))
+    }
+
+    /// The user as of the backup, for a file that belongs to them.
+    pub fn user(&self, name: Option<&String>) -> Option<User> {
+        name.map(|name| User {
+            name: name.to_string(),
+            home: StrictPath::new(self.users.get(name).cloned().unwrap_or_default()),
+        })
+    }
+
+    /// Where the file is stored, relative to the backup.
+    fn stored_file(&mut self, original_file: &StrictPath, user: Option<&User>) -> String {
         let (drive, plain_path) = original_file.split_drive();
         let drive_folder = self.drive_folder_name(&drive);
+        match user {
+            Some(user) => {
+                self.users.insert(user.name.clone(), user.home.render());
+                format!("{}/{}/{}", Self::user_folder_name(&user.name), drive_folder, plain_path)
+            }
+            None => format!("{}/{}", drive_folder, plain_path),
+        }
+    }
+
+    fn stored_file_immutable(&self, original_file: &StrictPath, user: Option<&String>) -> String {
+        let (drive, plain_path) = original_file.split_drive();
+        let drive_folder = self.drive_folder_name_immutable(&drive);
+        match user {
+            Some(user) => format!("{}/{}/{}", Self::user_folder_name(user), drive_folder, plain_path),
+            None => format!("{}/{}", drive_folder, plain_path),
+        }
+    }
+
+    pub fn game_file(
+        &mut self,
+        base: &StrictPath,
+        original_file: &StrictPath,
+        backup: &str,
+        user: Option<&User>,
+    ) -> StrictPath {
         StrictPath::relative(
-            format!("{}/{}/{}", backup, drive_folder, plain_path),
+            format!("{}/{}", backup, self.stored_file(original_file, user)),
             base.interpret().ok(),
         )
     }
 
-    pub fn game_file_immutable(&self, base: &StrictPath, original_file: &StrictPath, backup: &str) -> StrictPath {
-        let (drive, plain_path) = original_file.split_drive();
-        let drive_folder = self.drive_folder_name_immutable(&drive);
+    pub fn game_file_immutable(
+        &self,
+        base: &StrictPath,
+        original_file: &StrictPath,
+        backup: &str,
+        user: Option<&String>,
+    ) -> StrictPath {
         StrictPath::relative(
-            format!("{}/{}/{}", backup, drive_folder, plain_path),
+            format!("{}/{}", backup, self.stored_file_immutable(original_file, user)),
             base.interpret().ok(),
         )
     }
 
-    fn game_file_for_zip(&mut self, original_file: &StrictPath) -> String {
-        let (drive, plain_path) = original_file.split_drive();
-        let drive_folder = self.drive_folder_name(&drive);
-        format!("{}/{}", drive_folder, plain_path).replace('\\', "/")
+    fn game_file_for_zip(&mut self, original_file: &StrictPath, user: Option<&User>) -> String {
+        self.stored_file(original_file, user).replace('\\', "/")
     }
 
-    fn game_file_for_zip_immutable(&self, original_file: &StrictPath) -> String {
-        let (drive, plain_path) = original_file.split_drive();
-        let drive_folder = self.drive_folder_name_immutable(&drive);
-        format!("{}/{}", drive_folder, plain_path).replace('\\', "/")
+    fn game_file_for_zip_immutable(&self, original_file: &StrictPath, user: Option<&String>) -> String {
+        self.stored_file_immutable(original_file, user).replace('\\', "/")
     }
 
     fn latest_backup(&self) -> Option<(&FullBackup, Option<&DifferentialBackup>)> {
@@ -500,7 +579,7 @@ impl IndividualMapping {
         {
             let name = child.file_name().to_string_lossy();
 
-            if name.starts_with("drive-") && !self.has_backup(".") {
+            if (name.starts_with("drive-") || name.starts_with("user-")) && !self.has_backup(".") {
                 irrelevant.push(StrictPath::from(&child));
             }
             if name.starts_with("backup-") && !relevant.clone().any(|x| x == name) {
@@ -682,35 +761,49 @@ impl GameLayout {
                 BackupFormat::Simple => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            v.evaluate_restore(&redirected.as_ref().unwrap_or(&original_path).with_wine_prefix_case())
                         } else {
                             ScanChange::Unknown
                         },
-                        path: self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name),
+                        path: self.mapping.game_file_immutable(
+                            &self.path,
+                            &original_path,
+                            &backup.name,
+                            v.user.as_ref(),
+                        ),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
                         redirected,
                         original_path: Some(original_path),
                         container: None,
+                        tags: Default::default(),
+                        link: v.link.clone(),
+                        from_launcher: false,
+                        user: self.mapping.user(v.user.as_ref()),
                     });
                 }
                 BackupFormat::Zip => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            v.evaluate_restore(&redirected.as_ref().unwrap_or(&original_path).with_wine_prefix_case())
                         } else {
                             ScanChange::Unknown
                         },
-                        path: StrictPath::new(self.mapping.game_file_for_zip_immutable(&original_path)),
+                        path: StrictPath::new(
+                            self.mapping
+                                .game_file_for_zip_immutable(&original_path, v.user.as_ref()),
+                        ),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
                         redirected,
                         original_path: Some(original_path),
                         container: Some(self.path.joined(&backup.name)),
+                        tags: Default::default(),
+                        link: v.link.clone(),
+                        from_launcher: false,
+                        user: self.mapping.user(v.user.as_ref()),
                     });
                 }
             }
@@ -737,35 +830,49 @@ impl GameLayout {
                 BackupFormat::Simple => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            v.evaluate_restore(&redirected.as_ref().unwrap_or(&original_path).with_wine_prefix_case())
                         } else {
                             ScanChange::Unknown
                         },
-                        path: self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name),
+                        path: self.mapping.game_file_immutable(
+                            &self.path,
+                            &original_path,
+                            &backup.name,
+                            v.user.as_ref(),
+                        ),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
                         redirected,
                         original_path: Some(original_path),
                         container: None,
+                        tags: Default::default(),
+                        link: v.link.clone(),
+                        from_launcher: false,
+                        user: self.mapping.user(v.user.as_ref()),
                     });
                 }
                 BackupFormat::Zip => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            v.evaluate_restore(&redirected.as_ref().unwrap_or(&original_path).with_wine_prefix_case())
                         } else {
                             ScanChange::Unknown
                         },
-                        path: StrictPath::new(self.mapping.game_file_for_zip_immutable(&original_path)),
+                        path: StrictPath::new(
+                            self.mapping
+                                .game_file_for_zip_immutable(&original_path, v.user.as_ref()),
+                        ),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
                         redirected,
                         original_path: Some(original_path),
                         container: Some(self.path.joined(&backup.name)),
+                        tags: Default::default(),
+                        link: v.link.clone(),
+                        from_launcher: false,
+                        user: self.mapping.user(v.user.as_ref()),
                     });
                 }
             }
@@ -811,6 +918,10 @@ impl GameLayout {
                     ignored: false,
                     container: None,
                     redirected: None,
+                    tags: Default::default(),
+                    link: None,
+                    from_launcher: false,
+                    user: None,
                 });
             }
         }
@@ -939,7 +1050,7 @@ impl GameLayout {
             .mapping
             .backups
             .back()
-            .map(|x| x.children.iter().filter(|diff| !diff.locked).count())
+            .map(|x| x.children.iter().filter(|diff| !diff.locked && !diff.snapshot).count())
             .unwrap_or(0) as u8;
 
         if fulls > 0
@@ -969,6 +1080,8 @@ impl GameLayout {
                         IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            link: file.link.clone(),
+                            user: file.user.as_ref().map(|x| x.name.clone()),
                         },
                     );
                 }
@@ -990,6 +1103,7 @@ impl GameLayout {
             os: Some(Os::HOST),
             comment: None,
             locked: false,
+            snapshot: false,
             files,
             registry,
             children: VecDeque::new(),
@@ -1014,6 +1128,8 @@ impl GameLayout {
                         (!file.ignored).then(|| IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            link: file.link.clone(),
+                            user: file.user.as_ref().map(|x| x.name.clone()),
                         }),
                     );
                 }
@@ -1060,6 +1176,7 @@ impl GameLayout {
             os: Some(Os::HOST),
             comment: None,
             locked: false,
+            snapshot: false,
             files,
             registry,
         }
@@ -1075,7 +1192,31 @@ impl GameLayout {
                 continue;
             }
 
-            let target_file = self.mapping.game_file(&self.path, file.effective(), backup.name());
+            let target_file = self
+                .mapping
+                .game_file(&self.path, file.effective(), backup.name(), file.user.as_ref());
+            if let Some(link) = &file.link {
+                if target_file.read_link().as_ref() != Some(link) {
+                    if let Err(e) = target_file.create_symlink(link) {
+                        backup_info
+                            .failed_files
+                            .insert(file.clone(), BackupError::Raw(e.to_string()));
+                        continue;
+                    }
+                }
+                log::info!(
+                    "[{}] backed up link: {:?} -> {:?}",
+                    self.mapping.name,
+                    file.path,
+                    target_file
+                );
+                relevant_files.push(target_file);
+                continue;
+            }
+            if target_file.is_symlink() {
+                // Don't write through a link that was stored by a previous backup.
+                let _ = target_file.remove();
+            }
             if file.path.same_content(&target_file) {
                 log::info!(
                     "[{}] already matches: {:?} -> {:?}",
@@ -1168,7 +1309,27 @@ impl GameLayout {
                 continue;
             }
 
-            let target_file_id = self.mapping.game_file_for_zip(file.effective());
+            let target_file_id = self.mapping.game_file_for_zip(file.effective(), file.user.as_ref());
+
+            if let Some(link) = &file.link {
+                if let Err(e) = zip.add_symlink(&target_file_id, link, options) {
+                    log::error!(
+                        "[{}] unable to add zip link record: {:?} -> {} | {e}",
+                        self.mapping.name,
+                        &file.path,
+                        &target_file_id
+                    );
+                    fail_file(file, &mut backup_info, e.to_string());
+                } else {
+                    log::info!(
+                        "[{}] backed up link: {:?} -> {}",
+                        self.mapping.name,
+                        &file.path,
+                        &target_file_id
+                    );
+                }
+                continue;
+            }
 
             let mtime = match file.path.get_mtime_zip() {
                 Ok(x) => x,
@@ -1307,11 +1468,15 @@ impl GameLayout {
                 excess_fulls -= 1;
             }
 
-            let unlocked_diffs = full.children.iter().filter(|diff| !diff.locked).count();
+            let unlocked_diffs = full
+                .children
+                .iter()
+                .filter(|diff| !diff.locked && !diff.snapshot)
+                .count();
             let mut excess_diffs = unlocked_diffs.saturating_sub(self.retention.differential as usize);
 
             for (j, diff) in full.children.iter_mut().enumerate() {
-                let locked = diff.locked;
+                let locked = diff.locked || diff.snapshot;
                 if !locked && excess_diffs > 0 {
                     excess.push((i, Some(j)));
                     excess_diffs -= 1;
@@ -1336,6 +1501,74 @@ impl GameLayout {
         }
     }
 
+    /// Keep only the newest snapshots.
+    pub fn forget_excess_snapshots(&mut self, keep: u8) {
+        if self.forget_excess_snapshots_in_mapping(keep) {
+            self.save();
+            self.prune_irrelevant_parents();
+        }
+    }
+
+    /// A full snapshot is only removed once none of its children need to be kept.
+    /// Returns whether anything was removed.
+    fn forget_excess_snapshots_in_mapping(&mut self, keep: u8) -> bool {
+        let snapshots: Vec<_> = self
+            .mapping
+            .backups
+            .iter()
+            .enumerate()
+            .flat_map(|(i, full)| {
+                (full.snapshot && !full.locked).then_some((i, None)).into_iter().chain(
+                    full.children
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, diff)| diff.snapshot && !diff.locked)
+                        .map(move |(j, _)| (i, Some(j))),
+                )
+            })
+            .collect();
+
+        let mut excess: Vec<_> = snapshots
+            .iter()
+            .take(snapshots.len().saturating_sub(keep as usize))
+            .copied()
+            .collect();
+
+        let removable_fulls: HashSet<_> = excess
+            .iter()
+            .filter(|(i, diff)| {
+                diff.is_none() && (0..self.mapping.backups[*i].children.len()).all(|j| excess.contains(&(*i, Some(j))))
+            })
+            .map(|(i, _)| *i)
+            .collect();
+        excess.retain(|(i, diff)| match diff {
+            // Removing the full backup also removes its children.
+            Some(_) => !removable_fulls.contains(i),
+            None => removable_fulls.contains(i),
+        });
+
+        log::debug!("[{}] Excess snapshots: {:?}", &self.mapping.name, excess);
+
+        if excess.is_empty() {
+            return false;
+        }
+
+        // Remove indices from biggest to smallest so that the order is stable.
+        excess.reverse();
+        for (full, diff) in excess {
+            match diff {
+                Some(diff) => {
+                    self.mapping.backups[full].children.remove(diff);
+                }
+                None => {
+                    self.mapping.backups.remove(full);
+                }
+            }
+        }
+
+        true
+    }
+
     fn execute_backup(&mut self, backup: &Backup, scan: &ScanInfo, format: &BackupFormats) -> BackupInfo {
         if backup.only_inherits_and_overrides() {
             BackupInfo::default()
@@ -1385,6 +1618,8 @@ impl GameLayout {
                 IndividualMappingFile {
                     hash: file.path.sha1(),
                     size: file.path.size(),
+                    link: None,
+                    user: None,
                 },
             );
         }
@@ -1427,6 +1662,7 @@ impl GameLayout {
             os,
             comment,
             locked,
+            snapshot,
             files,
             registry,
         } = initial.children.pop_front()?;
@@ -1436,6 +1672,7 @@ impl GameLayout {
         initial.os = os;
         initial.comment = comment;
         initial.locked = initial.locked || locked;
+        initial.snapshot = snapshot;
         initial.files = files.into_iter().filter_map(|(k, v)| Some((k, v?))).collect();
         if let Some(registry) = registry {
             initial.registry = registry;
@@ -1495,6 +1732,50 @@ impl GameLayout {
         }
     }
 
+    /// Back up the game while it's running.
+    /// Unlike a normal backup, this will be differential whenever possible,
+    /// and it will not prune any old backups.
+    pub fn back_up_snapshot(
+        &mut self,
+        scan: &ScanInfo,
+        now: &chrono::DateTime<chrono::Utc>,
+        format: &BackupFormats,
+    ) -> Option<BackupInfo> {
+        if !scan.found_anything_processable() {
+            log::trace!("[{}] nothing changed since the last snapshot", &scan.game_name);
+            return None;
+        }
+
+        if let Err(e) = prepare_backup_target(&self.path) {
+            log::error!(
+                "[{}] failed to prepare snapshot target: {:?} | {e:?}",
+                scan.game_name,
+                &self.path
+            );
+            return Some(BackupInfo::total_failure(scan, BackupError::App(e)));
+        }
+
+        self.migrate_backups(true);
+        let mut backup = if self.mapping.backups.is_empty() {
+            Backup::Full(self.plan_full_backup(scan, now, format))
+        } else {
+            Backup::Differential(self.plan_differential_backup(scan, now, format))
+        };
+        if !backup.needed() {
+            return None;
+        }
+        backup.set_snapshot(true);
+
+        log::info!("[{}] creating a snapshot: {}", &scan.game_name, backup.name());
+        let backup_info = self.execute_backup(&backup, scan, format);
+        backup.prune_failures(&backup_info);
+        if backup.needed() {
+            self.insert_backup(backup);
+            self.save();
+        }
+        Some(backup_info)
+    }
+
     pub fn get_backups(&mut self) -> Vec<Backup> {
         let mut available_backups = vec![];
 
@@ -1506,6 +1787,10 @@ impl GameLayout {
         available_backups
     }
 
+    pub fn mapping(&self) -> &IndividualMapping {
+        &self.mapping
+    }
+
     pub fn has_backups(&self) -> bool {
         !self.mapping.backups.is_empty()
     }
@@ -1627,6 +1912,9 @@ impl GameLayout {
                 continue;
             }
 
+            // Wine folds case, so we reuse any existing folders with different capitalization.
+            let target = &target.with_wine_prefix_case();
+
             if let Some(container) = file.container.as_ref() {
                 if let Some(e) = failed_containers.get(container) {
                     log::warn!(
@@ -1673,9 +1961,10 @@ impl GameLayout {
                 }
             }
 
-            let outcome = match &file.container {
-                None => self.restore_file_from_simple(target, file),
-                Some(container) => {
+            let outcome = match (&file.link, &file.container) {
+                (Some(link), _) => self.restore_link(target, link),
+                (None, None) => self.restore_file_from_simple(target, file),
+                (None, Some(container)) => {
                     let Some(archive) = containers.get_mut(container) else {
                         continue;
                     };
@@ -1722,6 +2011,17 @@ impl GameLayout {
         }
     }
 
+    fn restore_link(&self, target: &StrictPath, link: &str) -> Result<(), AnyError> {
+        log::trace!(
+            "[{}] about to restore link: {:?} -> {}",
+            self.mapping.name,
+            &target,
+            link
+        );
+
+        target.create_symlink(link)
+    }
+
     fn restore_file_from_simple(&self, target: &StrictPath, file: &ScannedFile) -> Result<(), AnyError> {
         log::trace!(
             "[{}] about to restore (simple): {:?} -> {:?}",
@@ -1805,8 +2105,11 @@ impl GameLayout {
     }
 
     fn find_irrelevant_backup_files(&self, backup: &str, relevant_files: &[StrictPath]) -> Vec<StrictPath> {
+        // We compare the parent folder and the file name separately
+        // so that a stored link is identified by its own location rather than its target.
+        let identify = |path: &StrictPath| Some((path.popped().interpret().ok()?, path.leaf()?));
         #[allow(clippy::needless_collect)]
-        let relevant_files: Vec<_> = relevant_files.iter().filter_map(|x| x.interpret().ok()).collect();
+        let relevant_files: Vec<_> = relevant_files.iter().filter_map(identify).collect();
         let mut irrelevant_files = vec![];
 
         let Ok(walk_path) = self.path.joined(backup).interpret() else {
@@ -1818,17 +2121,20 @@ impl GameLayout {
             .follow_links(false)
             .into_iter()
             .filter_map(crate::scan::filter_map_walkdir)
-            .filter(|x| x.file_name().to_string_lossy().starts_with("drive-"))
+            .filter(|x| {
+                let name = x.file_name().to_string_lossy();
+                name.starts_with("drive-") || name.starts_with("user-")
+            })
         {
             for file in walkdir::WalkDir::new(drive_dir.path())
                 .max_depth(100)
                 .follow_links(false)
                 .into_iter()
                 .filter_map(crate::scan::filter_map_walkdir)
-                .filter(|x| x.file_type().is_file())
+                .filter(|x| x.file_type().is_file() || x.file_type().is_symlink())
             {
                 let backup_file = StrictPath::new(file.path().display().to_string());
-                let Ok(backup_path) = backup_file.interpret() else {
+                let Some(backup_path) = identify(&backup_file) else {
                     continue;
                 };
                 if !relevant_files.contains(&backup_path) {
@@ -1895,12 +2201,15 @@ impl GameLayout {
         if let Some((backup, diff)) = self.find_by_id(&backup_id) {
             match backup.format() {
                 BackupFormat::Simple => {
-                    for file in backup.files.keys() {
+                    for (file, data) in &backup.files {
                         let original_path = StrictPath::new(file.to_string());
-                        let stored = self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name);
-                        if !stored.is_file() {
+                        let stored = self.mapping.game_file_immutable(
+                            &self.path,
+                            &original_path,
+                            &backup.name,
+                            data.user.as_ref(),
+                        );
+                        if !stored.is_file() && !stored.is_symlink() {
                             #[cfg(test)]
                             eprintln!("can't find {}", stored.render());
                             return false;
@@ -1915,9 +2224,11 @@ impl GameLayout {
                         return false;
                     };
 
-                    for file in backup.files.keys() {
+                    for (file, data) in &backup.files {
                         let original_path = StrictPath::new(file.to_string());
-                        let stored = self.mapping.game_file_for_zip_immutable(&original_path);
+                        let stored = self
+                            .mapping
+                            .game_file_for_zip_immutable(&original_path, data.user.as_ref());
                         if archive.by_name(&stored).is_err() {
                             #[cfg(test)]
                             eprintln!("can't find {}", stored);
@@ -1931,16 +2242,19 @@ impl GameLayout {
                 match backup.format() {
                     BackupFormat::Simple => {
                         for (file, data) in &backup.files {
-                            if data.is_none() {
+                            let Some(data) = data else {
                                 // File is deliberately omitted.
                                 continue;
-                            }
+                            };
 
                             let original_path = StrictPath::new(file.to_string());
-                            let stored = self
-                                .mapping
-                                .game_file_immutable(&self.path, &original_path, &backup.name);
-                            if !stored.is_file() {
+                            let stored = self.mapping.game_file_immutable(
+                                &self.path,
+                                &original_path,
+                                &backup.name,
+                                data.user.as_ref(),
+                            );
+                            if !stored.is_file() && !stored.is_symlink() {
                                 #[cfg(test)]
                                 eprintln!("can't find {}", stored.render());
                                 return false;
@@ -1956,13 +2270,15 @@ impl GameLayout {
                         };
 
                         for (file, data) in &backup.files {
-                            if data.is_none() {
+                            let Some(data) = data else {
                                 // File is deliberately omitted.
                                 continue;
-                            }
+                            };
 
                             let original_path = StrictPath::new(file.to_string());
-                            let stored = self.mapping.game_file_for_zip_immutable(&original_path);
+                            let stored = self
+                                .mapping
+                                .game_file_for_zip_immutable(&original_path, data.user.as_ref());
                             if archive.by_name(&stored).is_err() {
                                 #[cfg(test)]
                                 eprintln!("can't find {}", stored);
@@ -2152,6 +2468,30 @@ mod tests {
             assert_eq!("drive-____C", mapping.drive_folder_name(r#"\\?\C:"#));
             assert_eq!("drive-__remote", mapping.drive_folder_name(r#"\\remote"#));
         }
+
+        #[test]
+        fn can_store_files_in_subtree_for_user() {
+            let mut mapping = IndividualMapping::new("foo".to_owned());
+            let alice = User {
+                name: s("alice"),
+                home: StrictPath::new(s("/home/alice")),
+            };
+
+            assert_eq!(
+                "user-alice/drive-0/home/alice/save.dat",
+                mapping.game_file_for_zip(&StrictPath::new(s("/home/alice/save.dat")), Some(&alice)),
+            );
+            assert_eq!(
+                "drive-0/shared/save.dat",
+                mapping.game_file_for_zip(&StrictPath::new(s("/shared/save.dat")), None),
+            );
+            assert_eq!(btree_map! { s("alice"): s("/home/alice") }, mapping.users);
+            assert_eq!(Some(alice), mapping.user(Some(&s("alice"))));
+            assert_eq!(
+                "user-alice/drive-0/home/alice/save.dat",
+                mapping.game_file_for_zip_immutable(&StrictPath::new(s("/home/alice/save.dat")), Some(&s("alice"))),
+            );
+        }
     }
 
     mod backup_layout {
@@ -2489,9 +2829,9 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1 },
-                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1, link: None, user: None },
+                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, link: None, user: None },
+                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, link: None, user: None },
                     },
                     ..Default::default()
                 },
@@ -2566,9 +2906,9 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3 },
-                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, link: None, user: None },
+                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3, link: None, user: None },
+                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -2582,8 +2922,8 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1 }),
-                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2 }),
+                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1, link: None, user: None }),
+                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2, link: None, user: None }),
                         StrictPath::new(repo_file("removed")).render(): None,
                     },
                     registry: None,
@@ -2613,9 +2953,9 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1 },
-                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2 },
-                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3 },
+                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1, link: None, user: None },
+                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2, link: None, user: None },
+                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3, link: None, user: None },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: format!("backup-{}-diff", now_str()),
@@ -3000,6 +3340,81 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_forget_excess_snapshots() {
+            let mut layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from_iter(vec![
+                        FullBackup {
+                            name: "1".to_string(),
+                            snapshot: true,
+                            children: VecDeque::from_iter(vec![DifferentialBackup {
+                                name: "1-a".to_string(),
+                                snapshot: true,
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "2".to_string(),
+                            snapshot: true,
+                            children: VecDeque::from_iter(vec![
+                                DifferentialBackup {
+                                    name: "2-a".to_string(),
+                                    snapshot: true,
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "2-b".to_string(),
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "2-c".to_string(),
+                                    snapshot: true,
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "2-d".to_string(),
+                                    comment: Some("Snapshot during play session".to_string()),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            assert!(layout.forget_excess_snapshots_in_mapping(1));
+            assert_eq!(
+                VecDeque::from_iter(vec![FullBackup {
+                    name: "2".to_string(),
+                    snapshot: true,
+                    children: VecDeque::from_iter(vec![
+                        DifferentialBackup {
+                            name: "2-b".to_string(),
+                            ..Default::default()
+                        },
+                        DifferentialBackup {
+                            name: "2-c".to_string(),
+                            snapshot: true,
+                            ..Default::default()
+                        },
+                        DifferentialBackup {
+                            name: "2-d".to_string(),
+                            comment: Some("Snapshot during play session".to_string()),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                }]),
+                layout.mapping.backups,
+            );
+            assert!(!layout.forget_excess_snapshots_in_mapping(1));
+        }
+
         fn make_path(file: &str) -> StrictPath {
             repo_path(&format!("tests/backup/game1/{}", file))
         }
@@ -3028,12 +3443,13 @@ mod tests {
                 mapping: IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
+                    users: Default::default(),
                     backups: VecDeque::from(vec![FullBackup {
                         name: "backup-1".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3055,6 +3471,10 @@ mod tests {
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path("backup-1", "file2.txt"),
@@ -3065,6 +3485,10 @@ mod tests {
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, false, &[], &Default::default()),
@@ -3078,12 +3502,13 @@ mod tests {
                 mapping: IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
+                    users: Default::default(),
                     backups: VecDeque::from(vec![FullBackup {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3105,6 +3530,10 @@ mod tests {
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path_zip("file2.txt"),
@@ -3115,6 +3544,10 @@ mod tests {
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, false, &[], &Default::default()),
@@ -3128,21 +3561,22 @@ mod tests {
                 mapping: IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
+                    users: Default::default(),
                     backups: VecDeque::from(vec![FullBackup {
                         name: "backup-1".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, link: None, user: None },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, link: None, user: None },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: "backup-2".into(),
                             when: past2(),
                             files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, link: None, user: None }),
                                 mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3166,6 +3600,10 @@ mod tests {
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path("backup-2", "changed.txt"),
@@ -3176,6 +3614,10 @@ mod tests {
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path("backup-2", "added.txt"),
@@ -3186,6 +3628,10 @@ mod tests {
                         change: Default::default(),
                         container: None,
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, false, &[], &Default::default()),
@@ -3199,21 +3645,22 @@ mod tests {
                 mapping: IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
+                    users: Default::default(),
                     backups: VecDeque::from(vec![FullBackup {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, link: None, user: None },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, link: None, user: None },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: "backup-2.zip".into(),
                             when: past2(),
                             files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, link: None, user: None }),
                                 mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3237,6 +3684,10 @@ mod tests {
                         change: Default::default(),
                         container: Some(make_path("backup-1.zip")),
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path_zip("changed.txt"),
@@ -3247,6 +3698,10 @@ mod tests {
                         change: Default::default(),
                         container: Some(make_path("backup-2.zip")),
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                     ScannedFile {
                         path: make_restorable_path_zip("added.txt"),
@@ -3257,6 +3712,10 @@ mod tests {
                         change: Default::default(),
                         container: Some(make_path("backup-2.zip")),
                         redirected: None,
+                        tags: Default::default(),
+                        link: None,
+                        from_launcher: false,
+                        user: None,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, false, &[], &Default::default()),
@@ -3297,12 +3756,13 @@ mod tests {
                 IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
+                    users: Default::default(),
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         when: now(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3320,10 +3780,14 @@ mod tests {
                     mapping_file_key("/file1.txt"): IndividualMappingFile {
                         hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
                         size: 1,
+                        link: None,
+                        user: None,
                     },
                     mapping_file_key("/file2.txt"): IndividualMappingFile {
                         hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
                         size: 2,
+                        link: None,
+                        user: None,
                     },
                 },
                 ..Default::default()
@@ -3342,6 +3806,10 @@ mod tests {
                             change: ScanChange::New,
                             container: None,
                             redirected: None,
+                            tags: Default::default(),
+                            link: None,
+                            from_launcher: false,
+                            user: None,
                         },
                         ScannedFile {
                             path: restorable_file_simple(".", "file2.txt"),
@@ -3352,6 +3820,10 @@ mod tests {
                             change: ScanChange::New,
                             container: None,
                             redirected: None,
+                            tags: Default::default(),
+                            link: None,
+                            from_launcher: false,
+                            user: None,
                         },
                     },
                     found_registry_keys: Default::default(),
@@ -3459,8 +3931,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3480,7 +3952,7 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3500,14 +3972,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: ".".into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3529,13 +4001,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: ".".into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3557,8 +4029,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3578,7 +4050,7 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         ..Default::default()
                     }]),
@@ -3598,14 +4070,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3627,13 +4099,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, link: None, user: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3665,7 +4137,7 @@ mod tests {
                 backups: VecDeque::from(vec![FullBackup {
                     name: ".".into(),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
                     },
                     ..Default::default()
                 }]),
@@ -3707,7 +4179,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
                         },
                         ..Default::default()
                     },
@@ -3724,7 +4196,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
                     },
                     ..Default::default()
                 }]),
@@ -3767,7 +4239,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 }),
+                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None }),
                         },
                         ..Default::default()
                     }]),
@@ -3785,7 +4257,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, link: None, user: None },
                     },
                     ..Default::default()
                 }]),
@@ -3811,5 +4283,84 @@ mod tests {
             game_layout.migrate_initial_empty_backup(false);
             assert_eq!(GameLayout::default().mapping, game_layout.mapping);
         }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_back_up_and_restore_links_without_touching_their_targets() {
+            let dir = crate::testing::scratch_dir("layout-links");
+            let outside = dir.joined("outside");
+            let first = outside.joined("first.sav");
+            let second = outside.joined("second.sav");
+            let extra = outside.joined("extra.sav");
+            outside.create_dirs().unwrap();
+            for (file, content) in [(&first, "first"), (&second, "second"), (&extra, "extra")] {
+                file.write_with_content(content).unwrap();
+            }
+
+            let saves = dir.joined("saves");
+            let link = saves.joined("save.sav");
+            let extra_link = saves.joined("extra.sav");
+            link.create_symlink(&first.interpret().unwrap()).unwrap();
+            extra_link.create_symlink(&extra.interpret().unwrap()).unwrap();
+
+            let linked = |path: &StrictPath, target: &StrictPath| {
+                let target = target.interpret().unwrap();
+                ScannedFile::new(
+                    path.render(),
+                    target.len() as u64,
+                    crate::scan::hash_link_target(&target),
+                )
+                .change_new()
+                .linked_to(&target)
+            };
+            let scan = |found_files| ScanInfo {
+                game_name: s("game"),
+                found_files,
+                ..Default::default()
+            };
+            let assert_targets_intact = || {
+                assert_eq!(Some(s("first")), first.read());
+                assert_eq!(Some(s("second")), second.read());
+                assert_eq!(Some(s("extra")), extra.read());
+            };
+
+            let mut layout = GameLayout::new(
+                dir.joined("backup"),
+                IndividualMapping::new(s("game")),
+                Retention::default(),
+            );
+            let base = layout.path.clone();
+
+            layout.back_up(
+                &scan(hash_set! { linked(&link, &first), linked(&extra_link, &extra) }),
+                &now(),
+                &BackupFormats::default(),
+            );
+            let stored = layout.mapping.game_file(&base, &link, ".", None);
+            let stored_extra = layout.mapping.game_file(&base, &extra_link, ".", None);
+            assert_eq!(Some(first.interpret().unwrap()), stored.read_link());
+            assert!(stored_extra.is_symlink());
+            assert_targets_intact();
+
+            // The stored link is replaced, and the one that's no longer needed is cleaned up.
+            link.create_symlink(&second.interpret().unwrap()).unwrap();
+            layout.back_up(
+                &scan(hash_set! { linked(&link, &second) }),
+                &now(),
+                &BackupFormats::default(),
+            );
+            assert_eq!(Some(second.interpret().unwrap()), stored.read_link());
+            assert!(!stored_extra.is_symlink());
+            assert_targets_intact();
+
+            // The existing link in the save folder is replaced.
+            link.create_symlink(&first.interpret().unwrap()).unwrap();
+            let restorable = layout.latest_backup(true, &[], &ToggledPaths::default()).unwrap();
+            layout.restore(&restorable, &ToggledRegistry::default());
+            assert_eq!(Some(second.interpret().unwrap()), link.read_link());
+            assert_targets_intact();
+
+            let _ = dir.remove();
+        }
     }
 }
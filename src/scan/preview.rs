@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    resource::config::{ToggledPaths, ToggledRegistry},
+    resource::config::{Root, ToggledPaths, ToggledRegistry, User},
     scan::{layout::Backup, BackupInfo, ScanChange, ScanChangeCount, ScannedFile, ScannedRegistry},
 };
 
@@ -34,6 +34,65 @@ impl ScanInfo {
         successful_bytes.checked_sub(failed_bytes).unwrap_or_default()
     }
 
+    /// Ignore restorable files from Steam accounts that were not selected in the roots.
+    pub fn ignore_unselected_steam_accounts(&mut self, roots: &[Root]) {
+        if roots.iter().all(|root| root.steam_accounts().is_empty()) {
+            return;
+        }
+
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                if !file.ignored
+                    && roots
+                        .iter()
+                        .any(|root| root.is_steam_account_excluded(file.original_path()))
+                {
+                    file.ignored = true;
+                }
+                file
+            })
+            .collect();
+    }
+
+    /// Ignore restorable files from users that we're not restoring for.
+    /// If a user's home folder has moved since the backup,
+    /// then their files are redirected to the new home folder.
+    pub fn ignore_other_users(&mut self, users: &[User]) {
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                let Some(owner) = file.user.as_ref() else {
+                    return file;
+                };
+
+                let Some(user) = users.iter().find(|user| user.name == owner.name) else {
+                    file.ignored = true;
+                    return file;
+                };
+
+                if owner.home.raw().is_empty() || user.home.equivalent(&owner.home) {
+                    return file;
+                }
+
+                let target = file.effective().replace(&owner.home, &user.home);
+                if target == *file.effective() {
+                    // A redirect has already moved the file outside of the home folder.
+                    return file;
+                }
+
+                if file.change != ScanChange::Unknown {
+                    file.change = match &file.link {
+                        Some(link) => ScanChange::evaluate_restore_link(&target, link),
+                        None => ScanChange::evaluate_restore(&target, &file.hash),
+                    };
+                }
+                file.redirected = Some(target);
+                file
+            })
+            .collect();
+    }
+
     pub fn total_possible_bytes(&self) -> u64 {
         self.found_files.iter().map(|x| x.size).sum::<u64>()
     }
@@ -63,6 +122,21 @@ impl ScanInfo {
         }
     }
 
+    /// For a restoration scan, find the live files that would be overwritten
+    /// even though they were modified after the backup was made.
+    pub fn files_newer_than_backup(&self) -> Vec<&ScannedFile> {
+        let Some(backup) = self.backup.as_ref() else {
+            return vec![];
+        };
+        let backup_time: std::time::SystemTime = (*backup.when()).into();
+
+        self.found_files
+            .iter()
+            .filter(|file| !file.ignored && file.change == ScanChange::Different)
+            .filter(|file| file.effective().get_mtime().is_ok_and(|mtime| mtime > backup_time))
+            .collect()
+    }
+
     pub fn update_ignored(&mut self, toggled_paths: &ToggledPaths, toggled_registry: &ToggledRegistry) {
         self.found_files = self
             .found_files
@@ -241,10 +315,79 @@ mod tests {
     use crate::{
         path::StrictPath,
         scan::{registry_compat::RegistryItem, ScannedRegistryValue},
+        testing::s,
     };
 
     use super::*;
 
+    fn restorable(path: &str, user: Option<&User>) -> ScannedFile {
+        ScannedFile {
+            path: StrictPath::new(format!("/backup{path}")),
+            original_path: Some(StrictPath::new(s(path))),
+            user: user.cloned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_ignore_other_users() {
+        let alice = User {
+            name: s("alice"),
+            home: StrictPath::new(s("/home/alice")),
+        };
+        let bob = User {
+            name: s("bob"),
+            home: StrictPath::new(s("/home/bob")),
+        };
+        let mut scan = ScanInfo {
+            found_files: hash_set! {
+                restorable("/home/alice/save", Some(&alice)),
+                restorable("/home/bob/save", Some(&bob)),
+                restorable("/shared/save", None),
+            },
+            ..Default::default()
+        };
+
+        scan.ignore_other_users(std::slice::from_ref(&alice));
+        assert_eq!(
+            hash_set! {
+                restorable("/home/alice/save", Some(&alice)),
+                restorable("/home/bob/save", Some(&bob)).ignored(),
+                restorable("/shared/save", None),
+            },
+            scan.found_files,
+        );
+    }
+
+    #[test]
+    fn can_redirect_files_when_user_home_has_moved() {
+        let old = User {
+            name: s("alice"),
+            home: StrictPath::new(s("/old/alice")),
+        };
+        let new = User {
+            name: s("alice"),
+            home: StrictPath::new(s("/home/alice")),
+        };
+        let mut scan = ScanInfo {
+            found_files: hash_set! {
+                restorable("/old/alice/save", Some(&old)),
+            },
+            ..Default::default()
+        };
+
+        scan.ignore_other_users(&[new]);
+        assert_eq!(
+            hash_set! {
+                ScannedFile {
+                    redirected: Some(StrictPath::new(s("/home/alice/save"))),
+                    ..restorable("/old/alice/save", Some(&old))
+                },
+            },
+            scan.found_files,
+        );
+    }
+
     #[test]
     fn game_is_brand_new() {
         let scan = ScanInfo {
@@ -599,4 +742,37 @@ mod tests {
         assert_eq!(ScanChange::Same, scan.overall_change());
         assert!(scan.can_report_game());
     }
+
+    #[test]
+    fn finds_files_newer_than_backup() {
+        use crate::scan::layout::FullBackup;
+
+        let file = format!("{}/tests/root1/game1/subdir/file2.txt", crate::testing::repo());
+        let scan = |when: chrono::DateTime<chrono::Utc>| ScanInfo {
+            found_files: hash_set! {
+                ScannedFile {
+                    original_path: Some(StrictPath::new(file.clone())),
+                    ..ScannedFile::with_name("backup/file2.txt").change_as(ScanChange::Different)
+                },
+                ScannedFile {
+                    original_path: Some(StrictPath::new(file.clone())),
+                    ..ScannedFile::with_name("backup/same.txt").change_as(ScanChange::Same)
+                },
+            },
+            backup: Some(Backup::Full(FullBackup {
+                name: ".".to_string(),
+                when,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let old = chrono::DateTime::from_timestamp(0, 0).unwrap();
+        assert_eq!(1, scan(old).files_newer_than_backup().len());
+
+        let future = chrono::Utc::now() + chrono::Duration::try_days(1).unwrap();
+        assert!(scan(future).files_newer_than_backup().is_empty());
+
+        assert!(ScanInfo::default().files_newer_than_backup().is_empty());
+    }
 }
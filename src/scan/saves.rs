@@ -1,7 +1,8 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
     prelude::StrictPath,
+    resource::{config::User, manifest::Tag},
     scan::{registry_compat::RegistryItem, ScanChange},
 };
 
@@ -20,6 +21,18 @@ pub struct ScannedFile {
     /// An enclosing archive file, if any, depending on the `BackupFormat`.
     pub container: Option<StrictPath>,
     pub redirected: Option<StrictPath>,
+    /// Tags from the manifest entries that matched this file.
+    pub tags: BTreeSet<Tag>,
+    /// When set, this is a symbolic link that was backed up as a link,
+    /// and this is where the link points.
+    pub link: Option<String>,
+    /// Whether this file was found via save locations from a launcher's own metadata,
+    /// rather than from the manifest.
+    pub from_launcher: bool,
+    /// The OS user whose home folder contains this file,
+    /// when scanning for multiple users.
+    /// When restoring, this is the user's home folder at the time of the backup.
+    pub user: Option<User>,
 }
 
 impl ScannedFile {
@@ -34,6 +47,10 @@ impl ScannedFile {
             change: Default::default(),
             container: None,
             redirected: None,
+            tags: Default::default(),
+            link: None,
+            from_launcher: false,
+            user: None,
         }
     }
 
@@ -56,6 +73,10 @@ impl ScannedFile {
             change,
             container: None,
             redirected: None,
+            tags: Default::default(),
+            link: None,
+            from_launcher: false,
+            user: None,
         }
     }
 
@@ -77,6 +98,18 @@ impl ScannedFile {
         self
     }
 
+    #[cfg(test)]
+    pub fn tagged(mut self, tags: &[Tag]) -> Self {
+        self.tags = tags.iter().copied().collect();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn linked_to(mut self, link: &str) -> Self {
+        self.link = Some(link.to_string());
+        self
+    }
+
     pub fn original_path(&self) -> &StrictPath {
         match &self.original_path {
             Some(x) => x,
@@ -1,14 +1,27 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::prelude::StrictPath;
+use crate::{
+    prelude::StrictPath,
+    resource::{config::Root, manifest::Os},
+    scan::title::normalize_title,
+};
+
+/// The account ID used for `userdata` folders is the lower half of the 64-bit Steam ID.
+const STEAM_ID_ACCOUNT_MASK: u64 = 0xFFFF_FFFF;
 
 #[derive(Clone, Debug, Default)]
-pub struct SteamShortcuts(HashMap<String, SteamShortcut>);
+pub struct SteamShortcuts {
+    shortcuts: HashMap<String, SteamShortcut>,
+    /// Normalized shortcut name -> original shortcut name.
+    normalized: HashMap<String, String>,
+}
 
 #[derive(Clone, Debug, Default)]
 pub struct SteamShortcut {
     pub id: u32,
     pub start_dir: Option<StrictPath>,
+    /// The Proton prefix (`compatdata/<id>/pfx`), if the shortcut has been run with Proton.
+    pub prefix: Option<StrictPath>,
 }
 
 impl SteamShortcuts {
@@ -23,6 +36,20 @@ impl SteamShortcuts {
             }
         };
 
+        // Proton prefixes for shortcuts are normally in the main library,
+        // but we check the others as well in case the user has moved them.
+        let mut libraries = vec![StrictPath::from(steam.path())];
+        if Os::HOST == Os::Linux {
+            if let Ok(paths) = steam.library_paths() {
+                for path in paths {
+                    let path = StrictPath::from(path);
+                    if !libraries.iter().any(|x| x.equivalent(&path)) {
+                        libraries.push(path);
+                    }
+                }
+            }
+        }
+
         let Ok(shortcuts) = steam.shortcuts() else {
             log::warn!("Unable to load Steam shortcuts");
             return instance;
@@ -36,7 +63,20 @@ impl SteamShortcuts {
                 &shortcut.start_dir
             );
             let start_dir = std::path::Path::new(shortcut.start_dir.trim_start_matches('"').trim_end_matches('"'));
-            instance.0.insert(
+            let prefix = if Os::HOST == Os::Linux {
+                find_prefix(&libraries, shortcut.app_id)
+            } else {
+                None
+            };
+            if let Some(prefix) = &prefix {
+                log::trace!(
+                    "Found Proton prefix for Steam shortcut {}: {:?}",
+                    &shortcut.app_name,
+                    prefix
+                );
+            }
+
+            instance.insert(
                 shortcut.app_name.clone(),
                 SteamShortcut {
                     id: shortcut.app_id,
@@ -45,6 +85,7 @@ impl SteamShortcuts {
                     } else {
                         None
                     },
+                    prefix,
                 },
             );
         }
@@ -52,7 +93,279 @@ impl SteamShortcuts {
         instance
     }
 
+    fn insert(&mut self, name: String, shortcut: SteamShortcut) {
+        self.normalized.insert(normalize_title(&name), name.clone());
+        self.shortcuts.insert(name, shortcut);
+    }
+
+    /// Shortcut names are entered by hand or by tools like Steam ROM Manager,
+    /// so we fall back to a normalized comparison with the game's title.
     pub fn get(&self, name: &str) -> Option<&SteamShortcut> {
-        self.0.get(name)
+        self.shortcuts.get(name).or_else(|| {
+            self.normalized
+                .get(&normalize_title(name))
+                .and_then(|original| self.shortcuts.get(original))
+        })
+    }
+
+    /// Find the name of the shortcut with a given generated app ID.
+    pub fn find_name_by_id(&self, id: u32) -> Option<&str> {
+        self.shortcuts
+            .iter()
+            .find(|(_, shortcut)| shortcut.id == id)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+fn find_prefix(libraries: &[StrictPath], app_id: u32) -> Option<StrictPath> {
+    libraries
+        .iter()
+        .map(|library| library.joined(&format!("steamapps/compatdata/{app_id}/pfx")))
+        .find(|prefix| prefix.is_dir())
+}
+
+/// A Steam account that has used this Steam installation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SteamAccount {
+    /// Account ID, as used for the folder names in `userdata`.
+    pub id: String,
+    /// 64-bit Steam ID, if known from `loginusers.vdf`.
+    pub steam_id: Option<String>,
+    pub account_name: Option<String>,
+    pub persona_name: Option<String>,
+    /// Whether this was the most recently logged in account.
+    pub most_recent: bool,
+}
+
+impl SteamAccount {
+    pub fn label(&self) -> String {
+        match &self.persona_name {
+            Some(persona) => format!("{} ({})", persona, self.id),
+            None => self.id.clone(),
+        }
+    }
+}
+
+mod login_users {
+    use std::collections::HashMap;
+
+    pub const FILE: &str = "config/loginusers.vdf";
+
+    #[derive(Debug, serde::Deserialize)]
+    pub struct User {
+        #[serde(rename = "AccountName", default)]
+        pub account_name: Option<String>,
+        #[serde(rename = "PersonaName", default)]
+        pub persona_name: Option<String>,
+        #[serde(rename = "MostRecent", default)]
+        pub most_recent: Option<String>,
+    }
+
+    pub type Data = HashMap<String, User>;
+}
+
+/// Steam accounts for each configured Steam root.
+#[derive(Clone, Debug, Default)]
+pub struct SteamAccounts(Vec<(StrictPath, BTreeMap<String, SteamAccount>)>);
+
+impl SteamAccounts {
+    pub fn discover(roots: &[Root]) -> Self {
+        let mut instance = Self::default();
+
+        for root in roots {
+            if let Root::Steam(root) = root {
+                if instance.0.iter().any(|(path, _)| path.equivalent(&root.path)) {
+                    continue;
+                }
+                instance.0.push((root.path.clone(), Self::in_root(&root.path)));
+            }
+        }
+
+        instance
+    }
+
+    /// Accounts from `loginusers.vdf`, plus any other accounts with a `userdata` folder.
+    pub fn in_root(root: &StrictPath) -> BTreeMap<String, SteamAccount> {
+        let mut accounts = BTreeMap::new();
+
+        let file = root.joined(login_users::FILE);
+        if let Some(content) = file.read() {
+            match keyvalues_serde::from_str::<login_users::Data>(&content) {
+                Ok(users) => {
+                    for (steam_id, user) in users {
+                        let Ok(parsed) = steam_id.parse::<u64>() else {
+                            continue;
+                        };
+                        let id = (parsed & STEAM_ID_ACCOUNT_MASK).to_string();
+                        accounts.insert(
+                            id.clone(),
+                            SteamAccount {
+                                id,
+                                steam_id: Some(steam_id),
+                                account_name: user.account_name,
+                                persona_name: user.persona_name,
+                                most_recent: user.most_recent.is_some_and(|x| x == "1"),
+                            },
+                        );
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Unable to parse Steam login users: {:?} | {e:?}", &file);
+                }
+            }
+        }
+
+        if let Ok(entries) = root.joined("userdata").read_dir() {
+            for entry in entries.filter_map(|x| x.ok()) {
+                let id = entry.file_name().to_string_lossy().to_string();
+                if id == "0" || !id.chars().all(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                accounts.entry(id.clone()).or_insert_with(|| SteamAccount {
+                    id,
+                    ..Default::default()
+                });
+            }
+        }
+
+        accounts
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(StrictPath, BTreeMap<String, SteamAccount>)> {
+        self.0.iter()
+    }
+
+    /// Label for the account that owns a file, if it's in a Steam root's `userdata` folder.
+    pub fn label(&self, path: &StrictPath) -> Option<String> {
+        self.0.iter().find_map(|(root, accounts)| {
+            let id = account_in_path(root, path)?;
+            Some(accounts.get(&id).map(|x| x.label()).unwrap_or(id))
+        })
+    }
+}
+
+/// If the path is inside of `<root>/userdata/<account>`, then this returns the account ID.
+pub fn account_in_path(root: &StrictPath, path: &StrictPath) -> Option<String> {
+    let root = root.interpreted().ok()?.render();
+    let path = path.interpreted().ok()?.render();
+
+    let relative = if Os::HOST.is_case_sensitive() {
+        path.strip_prefix(&root)?.to_string()
+    } else {
+        let prefix = path.get(..root.len())?;
+        if !prefix.eq_ignore_ascii_case(&root) {
+            return None;
+        }
+        path[root.len()..].to_string()
+    };
+
+    let mut parts = relative.trim_start_matches('/').split('/');
+    if !parts.next()?.eq_ignore_ascii_case("userdata") {
+        return None;
+    }
+    let account = parts.next()?;
+    (!account.is_empty() && account.chars().all(|c| c.is_ascii_digit())).then(|| account.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::repo;
+
+    #[test]
+    fn can_find_prefix() {
+        let libraries = vec![
+            StrictPath::new(format!("{}/tests/nonexistent", repo())),
+            StrictPath::new(format!("{}/tests/launchers/steam-shortcut", repo())),
+        ];
+        assert_eq!(
+            Some(StrictPath::new(format!(
+                "{}/tests/launchers/steam-shortcut/steamapps/compatdata/2786274309/pfx",
+                repo()
+            ))),
+            find_prefix(&libraries, 2786274309),
+        );
+        assert_eq!(None, find_prefix(&libraries, 1));
+    }
+
+    #[test]
+    fn can_find_account_in_path() {
+        let root = StrictPath::new("/steam".to_string());
+        assert_eq!(
+            Some("12345".to_string()),
+            account_in_path(
+                &root,
+                &StrictPath::new("/steam/userdata/12345/remote/file.sav".to_string())
+            )
+        );
+        assert_eq!(
+            None,
+            account_in_path(
+                &root,
+                &StrictPath::new("/steam/steamapps/common/12345/file.sav".to_string())
+            )
+        );
+        assert_eq!(
+            None,
+            account_in_path(&root, &StrictPath::new("/steam2/userdata/12345/file.sav".to_string()))
+        );
+    }
+
+    #[test]
+    fn can_read_accounts() {
+        let root = StrictPath::new(format!("{}/tests/launchers/steam-accounts", repo()));
+        assert_eq!(
+            BTreeMap::from([
+                (
+                    "39874".to_string(),
+                    SteamAccount {
+                        id: "39874".to_string(),
+                        steam_id: Some("76561197960305602".to_string()),
+                        account_name: Some("parent".to_string()),
+                        persona_name: Some("Parent".to_string()),
+                        most_recent: true,
+                    }
+                ),
+                (
+                    "54321".to_string(),
+                    SteamAccount {
+                        id: "54321".to_string(),
+                        ..Default::default()
+                    }
+                ),
+            ]),
+            SteamAccounts::in_root(&root),
+        );
+    }
+
+    #[test]
+    fn can_get_shortcut_by_normalized_name() {
+        let mut shortcuts = SteamShortcuts::default();
+        shortcuts.insert(
+            "Some Game: Deluxe Edition".to_string(),
+            SteamShortcut {
+                id: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(Some(1), shortcuts.get("Some Game: Deluxe Edition").map(|x| x.id));
+        assert_eq!(Some(1), shortcuts.get("some game").map(|x| x.id));
+        assert_eq!(None, shortcuts.get("Other Game").map(|x| x.id));
+    }
+
+    #[test]
+    fn can_find_shortcut_name_by_id() {
+        let mut shortcuts = SteamShortcuts::default();
+        shortcuts.insert(
+            "Some Game".to_string(),
+            SteamShortcut {
+                id: 3_000_000_000,
+                ..Default::default()
+            },
+        );
+        assert_eq!(Some("Some Game"), shortcuts.find_name_by_id(3_000_000_000));
+        assert_eq!(None, shortcuts.find_name_by_id(1));
     }
 }
@@ -48,6 +48,7 @@ pub struct TitleFinder {
     steam_ids: HashMap<u32, String>,
     gog_ids: HashMap<u64, String>,
     lutris_ids: HashMap<String, String>,
+    flatpak_ids: HashMap<String, String>,
     normalized: HashMap<String, String>,
     aliases: HashMap<String, String>,
 }
@@ -75,6 +76,7 @@ impl TitleFinder {
         let steam_ids = manifest.map_steam_ids_to_names();
         let gog_ids = manifest.map_gog_ids_to_names();
         let lutris_ids = manifest.map_lutris_ids_to_names();
+        let flatpak_ids = manifest.map_flatpak_ids_to_names();
         let normalized: HashMap<_, _> = games
             .keys()
             .map(|title| (normalize_title(title), title.to_owned()))
@@ -86,6 +88,7 @@ impl TitleFinder {
             steam_ids,
             gog_ids,
             lutris_ids,
+            flatpak_ids,
             normalized,
             aliases,
         }
@@ -141,6 +144,7 @@ impl TitleFinder {
             steam_id,
             gog_id,
             lutris_id,
+            flatpak_id,
             normalized,
             backup,
             restore,
@@ -149,7 +153,8 @@ impl TitleFinder {
         } = query;
 
         let mut output = BTreeSet::new();
-        let singular = !names.is_empty() || steam_id.is_some() || gog_id.is_some() || lutris_id.is_some();
+        let singular =
+            !names.is_empty() || steam_id.is_some() || gog_id.is_some() || lutris_id.is_some() || flatpak_id.is_some();
 
         'outer: {
             if singular {
@@ -180,6 +185,15 @@ impl TitleFinder {
                     }
                 }
 
+                if let Some(flatpak_id) = flatpak_id {
+                    if let Some(found) = self.flatpak_ids.get(&flatpak_id) {
+                        if self.eligible(found, backup, restore) {
+                            output.insert(found.to_owned());
+                            break 'outer;
+                        }
+                    }
+                }
+
                 for name in &names {
                     if self.games.contains_key(name) && self.eligible(name, backup, restore) {
                         output.insert(name.to_owned());
@@ -258,6 +272,9 @@ pub struct TitleQuery {
     // Search for a Lutris slug.
     /// This will cause only one result to be returned.
     pub lutris_id: Option<String>,
+    /// Search for a Flatpak ID.
+    /// This will cause only one result to be returned.
+    pub flatpak_id: Option<String>,
     /// Search by normalizing the `names`.
     pub normalized: bool,
     /// Only return games that are possible to back up.
@@ -332,6 +349,9 @@ mod tests {
             by-lutris:
                 id:
                     lutris: slug
+            by-flatpak:
+                id:
+                    flatpak: com.example.Game
             "#,
         )
         .unwrap();
@@ -387,6 +407,13 @@ mod tests {
                 ..Default::default()
             }),
         );
+        assert_eq!(
+            btree_set!["by-flatpak".to_string()],
+            finder.find(TitleQuery {
+                flatpak_id: Some("com.example.Game".to_string()),
+                ..Default::default()
+            }),
+        );
     }
 
     #[test]
@@ -0,0 +1,184 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    path::CommonPath,
+    prelude::StrictPath,
+    resource::{config::User, manifest::Os},
+};
+
+/// Accounts below this ID are reserved for system services on most Linux distros.
+const LINUX_MIN_UID: u32 = 1000;
+/// The `nobody` account.
+const LINUX_NOBODY_UID: u32 = 65534;
+
+/// Profile folders that don't belong to a real user.
+const WINDOWS_IGNORED_PROFILES: &[&str] = &["All Users", "Default", "Default User", "Public"];
+const MAC_IGNORED_PROFILES: &[&str] = &["Guest", "Shared"];
+
+/// Accounts rarely change, so we don't need to look them up for every scan.
+const DISCOVERY_TTL: Duration = Duration::from_secs(5 * 60);
+static DISCOVERED: Mutex<Option<(Instant, Vec<User>)>> = Mutex::new(None);
+
+/// Find local users with a home folder.
+pub fn discover() -> Vec<User> {
+    let mut cache = DISCOVERED.lock().unwrap();
+    if let Some((checked, users)) = cache.as_ref() {
+        if checked.elapsed() < DISCOVERY_TTL {
+            return users.clone();
+        }
+    }
+
+    let users = discover_uncached();
+    *cache = Some((Instant::now(), users.clone()));
+    users
+}
+
+/// The user running Ludusavi.
+pub fn current() -> Option<User> {
+    CommonPath::Home.get().map(|home| User {
+        name: crate::prelude::OS_USERNAME.to_string(),
+        home: StrictPath::new(home.to_string()),
+    })
+}
+
+fn discover_uncached() -> Vec<User> {
+    let users = match Os::HOST {
+        Os::Linux => match StrictPath::new("/etc/passwd".to_string()).read() {
+            Some(content) => parse_passwd(&content),
+            None => {
+                log::warn!("Unable to read /etc/passwd");
+                vec![]
+            }
+        },
+        Os::Windows => find_profiles("C:/Users", WINDOWS_IGNORED_PROFILES),
+        Os::Mac => find_profiles("/Users", MAC_IGNORED_PROFILES),
+        Os::Other => vec![],
+    };
+
+    let users: Vec<_> = users.into_iter().filter(|user| user.home.is_dir()).collect();
+    log::debug!("Discovered users: {:?}", &users);
+    users
+}
+
+/// Lines are formatted as `name:password:uid:gid:gecos:home:shell`.
+fn parse_passwd(content: &str) -> Vec<User> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            let parts: Vec<_> = line.split(':').collect();
+            if parts.len() < 7 {
+                return None;
+            }
+
+            let uid: u32 = parts[2].parse().ok()?;
+            if !(LINUX_MIN_UID..LINUX_NOBODY_UID).contains(&uid) {
+                return None;
+            }
+
+            let shell = parts[6];
+            if shell.ends_with("/nologin") || shell.ends_with("/false") {
+                return None;
+            }
+
+            let home = parts[5];
+            if home.is_empty() {
+                return None;
+            }
+
+            Some(User {
+                name: parts[0].to_string(),
+                home: StrictPath::new(home.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn find_profiles(folder: &str, ignored: &[&str]) -> Vec<User> {
+    let Ok(entries) = StrictPath::new(folder.to_string()).read_dir() else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || ignored.iter().any(|x| x.eq_ignore_ascii_case(&name)) {
+                return None;
+            }
+            Some(User {
+                home: StrictPath::from(entry.path()),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// The user whose home folder contains the path, if any.
+pub fn owner<'a>(users: &'a [User], path: &StrictPath) -> Option<&'a User> {
+    users.iter().find(|user| user.home.is_prefix_of(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_find_owner() {
+        let users = vec![
+            User {
+                name: "alice".to_string(),
+                home: StrictPath::new("/home/alice".to_string()),
+            },
+            User {
+                name: "bob".to_string(),
+                home: StrictPath::new("/home/bob".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            Some(&users[1]),
+            owner(
+                &users,
+                &StrictPath::new("/home/bob/.local/share/game/save.dat".to_string())
+            ),
+        );
+        assert_eq!(
+            None,
+            owner(&users, &StrictPath::new("/home/alice2/save.dat".to_string())),
+        );
+        assert_eq!(None, owner(&users, &StrictPath::new("/home/alice".to_string())));
+        assert_eq!(None, owner(&users, &StrictPath::new("/srv/save.dat".to_string())));
+    }
+
+    #[test]
+    fn can_parse_passwd() {
+        let content = r#"
+root:x:0:0:root:/root:/bin/bash
+daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin
+# comment:x:1000:1000::/home/comment:/bin/bash
+alice:x:1000:1000:Alice,,,:/home/alice:/bin/bash
+bob:x:1001:1001::/home/bob:/usr/bin/zsh
+service:x:1002:1002::/var/lib/service:/bin/false
+nobody:x:65534:65534:nobody:/nonexistent:/usr/sbin/nologin
+"#;
+        assert_eq!(
+            vec![
+                User {
+                    name: "alice".to_string(),
+                    home: StrictPath::new("/home/alice".to_string()),
+                },
+                User {
+                    name: "bob".to_string(),
+                    home: StrictPath::new("/home/bob".to_string()),
+                },
+            ],
+            parse_passwd(content),
+        );
+    }
+}
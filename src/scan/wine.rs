@@ -0,0 +1,125 @@
+use crate::{
+    prelude::StrictPath,
+    resource::{config::Root, manifest::Os},
+    scan::{heroic, lutris},
+};
+
+/// Common places to keep Wine prefixes outside of a launcher.
+const CANDIDATES: &[&str] = &[
+    "~/.wine",
+    "~/.local/share/wineprefixes/*",
+    "~/Games/*",
+    "~/.PlayOnLinux/wineprefix/*",
+    "~/.var/app/com.playonlinux.PlayOnLinux4/data/.PlayOnLinux/wineprefix/*",
+];
+
+/// Whether a folder looks like an initialized Wine prefix.
+pub fn is_prefix(path: &StrictPath) -> bool {
+    path.joined("drive_c").is_dir() && path.joined("system.reg").is_file()
+}
+
+/// Find Wine prefixes in common locations,
+/// excluding any that are already covered by the given roots.
+pub fn discover_prefixes(roots: &[Root]) -> Vec<StrictPath> {
+    if Os::HOST == Os::Windows {
+        return vec![];
+    }
+    discover_prefixes_in(CANDIDATES, roots)
+}
+
+fn discover_prefixes_in(candidates: &[&str], roots: &[Root]) -> Vec<StrictPath> {
+    let covered = covered_prefixes(roots);
+
+    let mut prefixes: Vec<StrictPath> = vec![];
+    for candidate in candidates {
+        for path in StrictPath::new(candidate.to_string()).glob() {
+            let Ok(path) = path.interpreted() else {
+                continue;
+            };
+            if !is_prefix(&path) {
+                continue;
+            }
+            if covered.iter().any(|x| x.equivalent(&path) || x.is_prefix_of(&path))
+                || prefixes.iter().any(|x| x.equivalent(&path))
+            {
+                log::trace!("Skipping Wine prefix that is already covered: {:?}", &path);
+                continue;
+            }
+            log::debug!("Discovered Wine prefix: {:?}", &path);
+            prefixes.push(path.rendered());
+        }
+    }
+
+    prefixes
+}
+
+/// Wine roots themselves, plus any prefixes that launcher roots manage.
+/// Other kinds of roots don't count, even if a prefix is inside of them,
+/// because they don't scan the prefix as a Windows environment.
+fn covered_prefixes(roots: &[Root]) -> Vec<StrictPath> {
+    let mut covered = vec![];
+
+    for root in roots {
+        match root {
+            Root::OtherWine(root) => covered.push(root.path.clone()),
+            Root::Heroic(root) => covered.extend(heroic::find_prefixes(root)),
+            Root::Lutris(root) => covered.extend(lutris::find_prefixes(root)),
+            _ => {}
+        }
+    }
+
+    covered
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{resource::manifest::Store, testing::repo};
+
+    fn candidates() -> Vec<String> {
+        vec![format!("{}/tests/wine-prefixes/*", repo())]
+    }
+
+    #[test]
+    fn can_discover_prefixes() {
+        let candidates = candidates();
+        let candidates: Vec<_> = candidates.iter().map(|x| x.as_str()).collect();
+        assert_eq!(
+            vec![
+                StrictPath::new(format!("{}/tests/wine-prefixes/covered", repo())),
+                StrictPath::new(format!("{}/tests/wine-prefixes/game", repo())),
+            ],
+            discover_prefixes_in(&candidates, &[]),
+        );
+    }
+
+    #[test]
+    fn skips_prefixes_covered_by_roots() {
+        let candidates = candidates();
+        let candidates: Vec<_> = candidates.iter().map(|x| x.as_str()).collect();
+        let roots = vec![Root::new(
+            format!("{}/tests/wine-prefixes/covered", repo()),
+            Store::OtherWine,
+        )];
+        assert_eq!(
+            vec![StrictPath::new(format!("{}/tests/wine-prefixes/game", repo()))],
+            discover_prefixes_in(&candidates, &roots),
+        );
+    }
+
+    #[test]
+    fn does_not_skip_prefixes_inside_of_other_roots() {
+        let candidates = candidates();
+        let candidates: Vec<_> = candidates.iter().map(|x| x.as_str()).collect();
+        let roots = vec![Root::new(format!("{}/tests/wine-prefixes", repo()), Store::OtherHome)];
+        assert_eq!(
+            vec![
+                StrictPath::new(format!("{}/tests/wine-prefixes/covered", repo())),
+                StrictPath::new(format!("{}/tests/wine-prefixes/game", repo())),
+            ],
+            discover_prefixes_in(&candidates, &roots),
+        );
+    }
+}
@@ -77,3 +77,11 @@ pub fn make_original_path(file: &str) -> StrictPath {
 pub fn s(text: &str) -> String {
     text.to_string()
 }
+
+/// An empty folder for tests that need to write real files.
+pub fn scratch_dir(name: &str) -> StrictPath {
+    let dir = std::env::temp_dir().join(format!("ludusavi-test-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    StrictPath::new(dir.to_string_lossy().replace('\\', "/"))
+}
@@ -1,4 +1,11 @@
+pub mod bottles;
+pub mod gog;
 pub mod heroic;
+pub mod itch;
+pub mod legendary;
+pub mod process;
+
+use crate::scan::SteamShortcuts;
 
 /// Returned game information with whatever we could find
 #[derive(Clone, Default, Debug)]
@@ -10,19 +17,66 @@ pub struct WrapGameInfo {
 
 impl WrapGameInfo {
     fn is_empty(&self) -> bool {
-        self.name.is_none() && self.gog_id.is_none()
+        self.name.is_none() && self.steam_id.is_none() && self.gog_id.is_none()
     }
 }
 
-pub fn infer_game_from_steam() -> Option<WrapGameInfo> {
-    let app_id = std::env::var("STEAMAPPID").ok()?.parse::<u32>().ok()?;
+/// Check if a command argument refers to a specific program,
+/// ignoring the folder and extension.
+/// This only looks at the text, since the program may be relative to a `PATH` entry.
+fn is_program(arg: &str, name: &str) -> bool {
+    let file = arg.rsplit(['/', '\\']).next().unwrap_or(arg);
+    let stem = match file.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file,
+    };
+    stem.eq_ignore_ascii_case(name)
+}
 
-    log::debug!("Found Steam environment variable: STEAMAPPID={}", app_id);
+/// Steam sets `STEAMAPPID` for both real games and non-Steam shortcuts.
+/// For shortcuts, the ID is generated by Steam, so it may be missing or zero,
+/// but `SteamGameId` also contains the shortcut ID in its upper 32 bits.
+fn parse_steam_app_id(app_id: Option<&str>, game_id: Option<&str>) -> Option<u32> {
+    if let Some(id) = app_id.and_then(|x| x.parse::<u32>().ok()).filter(|x| *x != 0) {
+        return Some(id);
+    }
 
-    let result = WrapGameInfo {
-        steam_id: Some(app_id),
-        ..Default::default()
-    };
+    let game_id = game_id?.parse::<u64>().ok()?;
+    if game_id > u32::MAX as u64 {
+        Some((game_id >> 32) as u32)
+    } else {
+        (game_id != 0).then_some(game_id as u32)
+    }
+}
+
+fn identify_steam_game(app_id: u32, shortcuts: &SteamShortcuts) -> WrapGameInfo {
+    match shortcuts.find_name_by_id(app_id) {
+        Some(name) => {
+            log::debug!("Steam app ID {} belongs to shortcut: {}", app_id, name);
+            WrapGameInfo {
+                name: Some(name.to_string()),
+                ..Default::default()
+            }
+        }
+        None => WrapGameInfo {
+            steam_id: Some(app_id),
+            ..Default::default()
+        },
+    }
+}
+
+pub fn infer_game_from_steam(shortcuts: &SteamShortcuts) -> Option<WrapGameInfo> {
+    let app_id = std::env::var("STEAMAPPID").ok();
+    let game_id = std::env::var("SteamGameId").ok();
+
+    log::debug!(
+        "Found Steam environment variables: STEAMAPPID={:?}, SteamGameId={:?}",
+        &app_id,
+        &game_id
+    );
+
+    let app_id = parse_steam_app_id(app_id.as_deref(), game_id.as_deref())?;
+    let result = identify_steam_game(app_id, shortcuts);
 
     (!result.is_empty()).then_some(result)
 }
@@ -90,3 +144,45 @@ pub mod lutris {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_recognize_program() {
+        assert!(is_program("legendary", "legendary"));
+        assert!(is_program("legendary.exe", "legendary"));
+        assert!(is_program("/usr/bin/legendary", "legendary"));
+        assert!(is_program("C:\\Program Files\\Legendary.EXE", "legendary"));
+        assert!(!is_program("legendary-gl", "legendary"));
+        assert!(!is_program("/legendary/game.exe", "legendary"));
+    }
+
+    #[test]
+    fn can_parse_steam_app_id() {
+        assert_eq!(Some(123), parse_steam_app_id(Some("123"), None));
+        assert_eq!(Some(123), parse_steam_app_id(Some("123"), Some("456")));
+        assert_eq!(None, parse_steam_app_id(Some("0"), None));
+        assert_eq!(None, parse_steam_app_id(None, None));
+    }
+
+    #[test]
+    fn can_parse_steam_shortcut_id_from_game_id() {
+        let shortcut_id: u32 = 3_000_000_000;
+        let game_id = ((shortcut_id as u64) << 32) | 0x0200_0000;
+        assert_eq!(
+            Some(shortcut_id),
+            parse_steam_app_id(Some("0"), Some(&game_id.to_string()))
+        );
+    }
+
+    #[test]
+    fn identifies_steam_game_without_shortcut() {
+        let info = identify_steam_game(123, &SteamShortcuts::default());
+        assert_eq!(None, info.name);
+        assert_eq!(Some(123), info.steam_id);
+    }
+}
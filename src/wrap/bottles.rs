@@ -0,0 +1,152 @@
+use std::env;
+
+use crate::{
+    prelude::StrictPath,
+    resource::config::Root,
+    scan::bottles::{get_bottles, Bottle},
+    wrap::WrapGameInfo,
+};
+
+/// Arguments accepted by `bottles-cli run`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RunArgs {
+    bottle: Option<String>,
+    program: Option<String>,
+    executable: Option<String>,
+}
+
+fn parse_run_args(commands: &[String]) -> RunArgs {
+    let mut out = RunArgs::default();
+
+    let mut args = commands.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-b" | "--bottle" => out.bottle = args.next().cloned(),
+            "-p" | "--program" => out.program = args.next().cloned(),
+            "-e" | "--executable" => out.executable = args.next().cloned(),
+            _ => {
+                if let Some((key, value)) = arg.split_once('=') {
+                    match key {
+                        "--bottle" => out.bottle = Some(value.to_string()),
+                        "--program" => out.program = Some(value.to_string()),
+                        "--executable" => out.executable = Some(value.to_string()),
+                        _ => {}
+                    }
+                } else if out.executable.is_none() && arg.to_lowercase().ends_with(".exe") {
+                    out.executable = Some(arg.clone());
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn find_program(bottles: &[Bottle], args: &RunArgs, prefix: Option<&StrictPath>) -> Option<String> {
+    bottles
+        .iter()
+        .filter(|bottle| match (&args.bottle, prefix) {
+            (Some(name), _) => bottle.data.name == *name,
+            (None, Some(prefix)) => bottle.prefix.equivalent(prefix),
+            (None, None) => true,
+        })
+        .find_map(|bottle| {
+            log::debug!("Looking for program in bottle {:?}", &bottle.prefix);
+            bottle.data.external_programs.values().find_map(|program| {
+                let matched = match (&args.program, &args.executable) {
+                    (Some(name), _) => program.name == *name,
+                    (None, Some(executable)) => {
+                        StrictPath::new(program.path.clone()).equivalent(&StrictPath::new(executable.clone()))
+                    }
+                    (None, None) => false,
+                };
+                matched.then(|| program.name.clone())
+            })
+        })
+}
+
+/// Infer the game from a program launched through Bottles.
+///
+/// This supports `bottles-cli run -b <bottle> -p <program>` as well as
+/// launching an executable directly while Bottles sets `WINEPREFIX`.
+/// When no bottle is specified, we check all Bottles roots for the program.
+pub fn infer_game_from_bottles(roots: &[Root], commands: &[String]) -> Option<WrapGameInfo> {
+    let args = parse_run_args(commands);
+    let prefix = env::var("WINEPREFIX").ok().map(StrictPath::new);
+
+    log::debug!(
+        "Found Bottles launch info: bottle={:?}, program={:?}, executable={:?}, WINEPREFIX={:?}",
+        &args.bottle,
+        &args.program,
+        &args.executable,
+        &prefix,
+    );
+
+    let name = roots
+        .iter()
+        .filter_map(|root| match root {
+            Root::Bottles(root) => Some(root),
+            _ => None,
+        })
+        .find_map(|root| find_program(&get_bottles(&root.path), &args, prefix.as_ref()))
+        .or(args.program);
+
+    let result = WrapGameInfo {
+        name,
+        ..Default::default()
+    };
+
+    (!result.is_empty()).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{resource::config::root, testing::repo};
+
+    fn roots() -> Vec<Root> {
+        vec![Root::Bottles(root::Bottles {
+            path: format!("{}/tests/launchers/bottles", repo()).into(),
+            ..Default::default()
+        })]
+    }
+
+    fn commands(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn can_parse_run_args() {
+        assert_eq!(
+            RunArgs {
+                bottle: Some("Gaming".to_string()),
+                program: Some("Game 2".to_string()),
+                executable: None,
+            },
+            parse_run_args(&commands(&["bottles-cli", "run", "-b", "Gaming", "--program=Game 2"])),
+        );
+    }
+
+    #[test]
+    fn can_find_program_by_name() {
+        let bottles = get_bottles(&roots()[0].path().clone());
+        let args = parse_run_args(&commands(&["bottles-cli", "run", "-b", "Gaming", "-p", "Game 2"]));
+        assert_eq!(Some("Game 2".to_string()), find_program(&bottles, &args, None));
+    }
+
+    #[test]
+    fn can_find_program_by_executable() {
+        let bottles = get_bottles(&roots()[0].path().clone());
+        let args = parse_run_args(&commands(&["wine", "/games/game-1/game-1.exe"]));
+        assert_eq!(Some("game-1".to_string()), find_program(&bottles, &args, None));
+    }
+
+    #[test]
+    fn ignores_program_from_other_bottle() {
+        let bottles = get_bottles(&roots()[0].path().clone());
+        let args = parse_run_args(&commands(&["bottles-cli", "run", "-b", "Other", "-p", "Game 2"]));
+        assert_eq!(None, find_program(&bottles, &args, None));
+    }
+}
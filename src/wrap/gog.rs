@@ -0,0 +1,80 @@
+use crate::wrap::{is_program, WrapGameInfo};
+
+/// Find the GOG game ID from one of these commands:
+///
+/// * GOG Galaxy: `GalaxyClient.exe /command=runGame /gameId=<id> /path=<path>`
+/// * gogdl (used by Heroic): `gogdl launch <path> <id>`
+fn parse_game_id(commands: &[String]) -> Option<u64> {
+    let galaxy = commands.iter().find_map(|arg| {
+        let (key, value) = arg.split_once('=')?;
+        key.eq_ignore_ascii_case("/gameId").then(|| value.parse().ok())?
+    });
+    if galaxy.is_some() {
+        return galaxy;
+    }
+
+    let start = commands.iter().position(|arg| is_program(arg, "gogdl"))?;
+    let mut args = commands[start + 1..].iter().skip_while(|arg| *arg != "launch");
+    args.next()?;
+    args.filter(|arg| !arg.starts_with('-')).nth(1)?.parse().ok()
+}
+
+/// Infer the game from a GOG Galaxy or gogdl launch command.
+pub fn infer_game_from_gog(commands: &[String]) -> Option<WrapGameInfo> {
+    let game_id = parse_game_id(commands)?;
+
+    log::debug!("Found GOG game ID in commands: {}", game_id);
+
+    Some(WrapGameInfo {
+        gog_id: Some(game_id),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn commands(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn can_parse_game_id_from_galaxy() {
+        assert_eq!(
+            Some(1207658924),
+            parse_game_id(&commands(&[
+                "GalaxyClient.exe",
+                "/command=runGame",
+                "/gameId=1207658924",
+                "/path=C:\\Games\\Foo",
+            ])),
+        );
+    }
+
+    #[test]
+    fn can_parse_game_id_from_gogdl() {
+        assert_eq!(
+            Some(1207658924),
+            parse_game_id(&commands(&[
+                "/opt/heroic/gogdl",
+                "--auth-config-path",
+                "auth.json",
+                "launch",
+                "/games/foo",
+                "1207658924",
+                "--platform=windows",
+            ])),
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_commands() {
+        assert_eq!(
+            None,
+            parse_game_id(&commands(&["game.exe", "launch", "/games/foo", "123"]))
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    prelude::StrictPath,
+    resource::config::Root,
+    scan::itch::{get_caves, Cave},
+    wrap::WrapGameInfo,
+};
+
+/// The itch app does not identify the game in the launch environment,
+/// but it does launch games from their install folder.
+/// We look for an installed game whose folder contains either
+/// the executable being launched or the current working directory.
+fn find_cave<'a>(caves: &'a [Cave], candidates: &[StrictPath]) -> Option<&'a Cave> {
+    caves.iter().find(|cave| {
+        let Some(install_dir) = cave.install_dir.as_ref() else {
+            return false;
+        };
+        candidates
+            .iter()
+            .any(|candidate| candidate.equivalent(install_dir) || install_dir.is_prefix_of(candidate))
+    })
+}
+
+pub fn infer_game_from_itch(roots: &[Root], commands: &[String]) -> Option<WrapGameInfo> {
+    let mut candidates: Vec<_> = commands
+        .iter()
+        .map(|x| StrictPath::new(x.clone()))
+        .filter(|x| x.is_absolute())
+        .collect();
+    if let Ok(cwd) = std::env::current_dir() {
+        candidates.push(StrictPath::from(cwd));
+    }
+
+    log::debug!("Looking for itch game containing any of: {:?}", &candidates);
+
+    let name = roots
+        .iter()
+        .filter_map(|root| match root {
+            Root::Itch(root) => Some(root),
+            _ => None,
+        })
+        .find_map(|root| {
+            let caves = get_caves(&root.path).ok()?;
+            find_cave(&caves, &candidates).map(|cave| cave.title.clone())
+        });
+
+    let result = WrapGameInfo {
+        name,
+        ..Default::default()
+    };
+
+    (!result.is_empty()).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::repo;
+
+    fn caves() -> Vec<Cave> {
+        get_caves(&StrictPath::new(format!("{}/tests/launchers/itch", repo()))).unwrap()
+    }
+
+    #[test]
+    fn can_find_cave_by_executable() {
+        let candidates = vec![StrictPath::new(
+            "/home/user/.wine/drive_c/itch/game-2/game.exe".to_string(),
+        )];
+        assert_eq!(
+            Some("Game 2".to_string()),
+            find_cave(&caves(), &candidates).map(|x| x.title.clone())
+        );
+    }
+
+    #[test]
+    fn can_find_cave_by_install_dir() {
+        let candidates = vec![StrictPath::new("/home/user/.config/itch/apps/game-1".to_string())];
+        assert_eq!(
+            Some("game-1".to_string()),
+            find_cave(&caves(), &candidates).map(|x| x.title.clone())
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_paths() {
+        let candidates = vec![StrictPath::new("/home/user/.config/itch/apps/game-10/game".to_string())];
+        assert_eq!(None, find_cave(&caves(), &candidates).map(|x| x.title.clone()));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    prelude::StrictPath,
+    resource::config::Root,
+    scan::launchers::{heroic, legendary},
+    wrap::{is_program, WrapGameInfo},
+};
+
+/// Find the app name from `legendary launch <app_name>`.
+fn parse_app_name(commands: &[String]) -> Option<String> {
+    let start = commands.iter().position(|arg| is_program(arg, "legendary"))?;
+
+    let mut args = commands[start + 1..].iter().skip_while(|arg| *arg != "launch");
+    args.next()?;
+    args.find(|arg| !arg.starts_with('-')).cloned()
+}
+
+fn find_title(roots: &[Root], app_name: &str) -> Option<String> {
+    let mut games = vec![];
+    for root in roots {
+        match root {
+            Root::Legendary(root) => games.extend(legendary::get_games(&root.path)),
+            Root::Heroic(root) => games.extend(heroic::legendary::get_installed(root, None)),
+            _ => {}
+        }
+    }
+    if games.is_empty() {
+        games.extend(legendary::get_games(&StrictPath::new(
+            "~/.config/legendary".to_string(),
+        )));
+    }
+
+    games
+        .into_iter()
+        .find(|game| game.app_name == app_name)
+        .map(|game| game.title)
+}
+
+/// Infer the game from a `legendary launch <app_name>` command.
+pub fn infer_game_from_legendary(roots: &[Root], commands: &[String]) -> Option<WrapGameInfo> {
+    let app_name = parse_app_name(commands)?;
+
+    log::debug!("Found Legendary app name in commands: {}", &app_name);
+
+    let result = WrapGameInfo {
+        name: find_title(roots, &app_name),
+        ..Default::default()
+    };
+
+    (!result.is_empty()).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{resource::config::root, testing::repo};
+
+    fn commands(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn can_parse_app_name() {
+        assert_eq!(
+            Some("app-1".to_string()),
+            parse_app_name(&commands(&["/usr/bin/legendary", "launch", "--offline", "app-1"])),
+        );
+        assert_eq!(
+            Some("app-1".to_string()),
+            parse_app_name(&commands(&[
+                "legendary.exe",
+                "-y",
+                "launch",
+                "app-1",
+                "--skip-version-check"
+            ])),
+        );
+        assert_eq!(None, parse_app_name(&commands(&["legendary", "list"])));
+        assert_eq!(None, parse_app_name(&commands(&["game.exe", "launch", "app-1"])));
+    }
+
+    #[test]
+    fn can_find_title_in_root() {
+        let roots = vec![Root::Legendary(root::Legendary {
+            path: format!("{}/tests/launchers/legendary", repo()).into(),
+            ..Default::default()
+        })];
+        assert_eq!(Some("game-1".to_string()), find_title(&roots, "app-1"));
+        assert_eq!(None, find_title(&roots, "app-2"));
+    }
+}
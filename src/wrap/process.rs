@@ -0,0 +1,525 @@
+use std::{
+    process::{Child, Command, ExitStatus},
+    time::{Duration, Instant},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long to wait for a `--wait-for` process to appear after the game command exits.
+const WAIT_FOR_STARTUP: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default)]
+pub struct WaitOptions {
+    /// Wait for any processes spawned by the game command,
+    /// not just the command itself.
+    pub children: bool,
+    /// Afterwards, wait for processes whose executable matches this glob.
+    pub pattern: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+    pub status: ExitStatus,
+    /// Termination signals that we received while the game was running,
+    /// including ones that the terminal already sent to the game directly.
+    pub received_signals: Vec<i32>,
+    /// Termination signals that we passed along to the game ourselves.
+    pub forwarded_signals: Vec<i32>,
+}
+
+impl Outcome {
+    /// If the game was killed by a signal,
+    /// then we follow the shell convention of 128 plus the signal number.
+    pub fn exit_code(&self) -> i32 {
+        if let Some(code) = self.status.code() {
+            return code;
+        }
+        match self.signal() {
+            Some(signal) => 128 + signal,
+            None => 1,
+        }
+    }
+
+    pub fn signal(&self) -> Option<i32> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            self.status.signal()
+        }
+
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Process {
+    pid: u32,
+    parent: u32,
+    name: String,
+    /// Full executable path or invocation, when available.
+    exe: Option<String>,
+}
+
+impl Process {
+    fn matches(&self, glob: &globset::GlobMatcher) -> bool {
+        glob.is_match(&self.name)
+            || self
+                .exe
+                .as_deref()
+                .is_some_and(|exe| glob.is_match(exe) || glob.is_match(file_name(exe)))
+    }
+}
+
+/// Wine reports Windows-style paths, so we handle both separators on every OS.
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Run the game command and wait until the game is really done,
+/// since some launchers return while the game is still running.
+pub fn launch(commands: &[String], options: &WaitOptions) -> std::io::Result<Outcome> {
+    let glob = match &options.pattern {
+        Some(pattern) => match globset::GlobBuilder::new(pattern).case_insensitive(true).build() {
+            Ok(glob) => Some(glob.compile_matcher()),
+            Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)),
+        },
+        None => None,
+    };
+
+    #[cfg(target_os = "linux")]
+    if options.children {
+        become_subreaper();
+    }
+
+    let mut command = Command::new(&commands[0]);
+    command.args(&commands[1..]);
+
+    // When running from a terminal, the game needs to stay in the foreground process group
+    // so that it can read input, and the terminal will signal all of its processes for us.
+    // Otherwise, the game gets its own process group so that we can forward signals to all of its processes.
+    #[cfg(unix)]
+    let foreground = unsafe { libc::isatty(libc::STDIN_FILENO) } == 1;
+    #[cfg(unix)]
+    if !foreground {
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    }
+
+    let mut child = command.spawn()?;
+
+    #[cfg(unix)]
+    let forwarder = SignalForwarder::start(child.id(), foreground);
+
+    let status = if options.children {
+        wait_for_children(child)
+    } else {
+        child.wait()
+    };
+
+    #[cfg(unix)]
+    let (received_signals, forwarded_signals) = forwarder.map(|x| x.stop()).unwrap_or_default();
+    #[cfg(not(unix))]
+    let (received_signals, forwarded_signals) = (vec![], vec![]);
+
+    let status = status?;
+
+    if let Some(glob) = glob {
+        // If we were asked to stop, then there's no point in waiting for the game to start.
+        if received_signals.is_empty() {
+            wait_for_pattern(&glob);
+        }
+    }
+
+    Ok(Outcome {
+        status,
+        received_signals,
+        forwarded_signals,
+    })
+}
+
+#[cfg(unix)]
+struct SignalForwarder {
+    handle: signal_hook::iterator::Handle,
+    /// Returns the signals that were received and the ones that were forwarded.
+    thread: std::thread::JoinHandle<(Vec<i32>, Vec<i32>)>,
+}
+
+#[cfg(unix)]
+impl SignalForwarder {
+    const SIGNALS: [i32; 4] = [
+        signal_hook::consts::SIGHUP,
+        signal_hook::consts::SIGINT,
+        signal_hook::consts::SIGQUIT,
+        signal_hook::consts::SIGTERM,
+    ];
+
+    /// If the game is in the `foreground` process group,
+    /// then the terminal already sends keyboard signals to it,
+    /// so we only forward the others and only to the game command itself.
+    fn start(pid: u32, foreground: bool) -> Option<Self> {
+        let mut signals = match signal_hook::iterator::Signals::new(Self::SIGNALS) {
+            Ok(signals) => signals,
+            Err(e) => {
+                log::warn!("WRAP::execute: Unable to forward signals to the game: {:?}", e);
+                return None;
+            }
+        };
+        let handle = signals.handle();
+
+        let thread = std::thread::spawn(move || {
+            let mut received = vec![];
+            let mut forwarded = vec![];
+            for signal in signals.forever() {
+                received.push(signal);
+                if !foreground {
+                    log::warn!(
+                        "WRAP::execute: Forwarding signal {} to game process group {}",
+                        signal,
+                        pid
+                    );
+                    unsafe {
+                        libc::kill(-(pid as libc::pid_t), signal);
+                    }
+                    forwarded.push(signal);
+                } else if signal == signal_hook::consts::SIGINT || signal == signal_hook::consts::SIGQUIT {
+                    log::warn!("WRAP::execute: Game received signal {} from the terminal", signal);
+                } else {
+                    log::warn!("WRAP::execute: Forwarding signal {} to game process {}", signal, pid);
+                    unsafe {
+                        libc::kill(pid as libc::pid_t, signal);
+                    }
+                    forwarded.push(signal);
+                }
+            }
+            (received, forwarded)
+        });
+
+        Some(Self { handle, thread })
+    }
+
+    fn stop(self) -> (Vec<i32>, Vec<i32>) {
+        self.handle.close();
+        let signals = self.thread.join().unwrap_or_default();
+
+        // Once we've taken over these signals, they won't terminate the process anymore by default,
+        // so we need to explicitly restore that behavior.
+        // The SIGINT handler is replaced rather than added to, but the others only need to be registered once.
+        crate::prelude::unregister_sigint();
+        static RESTORE_DEFAULTS: std::sync::Once = std::sync::Once::new();
+        RESTORE_DEFAULTS.call_once(|| {
+            for signal in Self::SIGNALS {
+                if signal != signal_hook::consts::SIGINT {
+                    let _ = signal_hook::flag::register_conditional_default(
+                        signal,
+                        std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true)),
+                    );
+                }
+            }
+        });
+
+        signals
+    }
+}
+
+/// Orphaned descendants will be reparented to us instead of to init,
+/// so we can wait for them even after the game command exits.
+#[cfg(target_os = "linux")]
+fn become_subreaper() {
+    let result = unsafe { libc::prctl(libc::PR_SET_CHILD_SUBREAPER, 1, 0, 0, 0) };
+    if result != 0 {
+        log::warn!(
+            "WRAP::execute: Unable to track child processes: {:?}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn wait_for_children(mut child: Child) -> std::io::Result<ExitStatus> {
+    let status = child.wait()?;
+
+    loop {
+        let mut raw = 0;
+        let pid = unsafe { libc::waitpid(-1, &mut raw, 0) };
+        if pid > 0 {
+            log::debug!("WRAP::execute: Child process exited: {}", pid);
+            continue;
+        }
+
+        let error = std::io::Error::last_os_error();
+        if error.kind() == std::io::ErrorKind::Interrupted {
+            continue;
+        }
+        // Most likely `ECHILD`, meaning that there's nothing left to wait for.
+        log::debug!("WRAP::execute: Done waiting for child processes: {:?}", error);
+        break;
+    }
+
+    Ok(status)
+}
+
+/// Without a subreaper, we have to poll the process list and keep track of descendants ourselves.
+#[cfg(not(target_os = "linux"))]
+fn wait_for_children(mut child: Child) -> std::io::Result<ExitStatus> {
+    let mut tracked = std::collections::HashSet::from([child.id()]);
+    let mut status = None;
+
+    loop {
+        if status.is_none() {
+            status = child.try_wait()?;
+        }
+
+        let any_running = track_descendants(&mut tracked, &list());
+        if let Some(status) = status {
+            if !any_running {
+                return Ok(status);
+            }
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Add the descendants of any tracked processes and report whether any tracked processes are still running.
+/// We keep exited processes in the set because their children may still refer to them as the parent.
+#[cfg(any(test, not(target_os = "linux")))]
+fn track_descendants(tracked: &mut std::collections::HashSet<u32>, processes: &[Process]) -> bool {
+    loop {
+        let new: Vec<_> = processes
+            .iter()
+            .filter(|p| tracked.contains(&p.parent) && !tracked.contains(&p.pid))
+            .map(|p| p.pid)
+            .collect();
+        if new.is_empty() {
+            break;
+        }
+        tracked.extend(new);
+    }
+
+    processes.iter().any(|p| tracked.contains(&p.pid))
+}
+
+fn wait_for_pattern(glob: &globset::GlobMatcher) {
+    let own_pid = std::process::id();
+    let started = Instant::now();
+    let mut seen = false;
+
+    loop {
+        let running = list().iter().any(|p| p.pid != own_pid && p.matches(glob));
+        if running {
+            if !seen {
+                log::debug!("WRAP::execute: Found process matching {:?}", glob.glob());
+            }
+            seen = true;
+        } else if seen {
+            log::debug!("WRAP::execute: Process matching {:?} exited", glob.glob());
+            break;
+        } else if started.elapsed() > WAIT_FOR_STARTUP {
+            log::warn!("WRAP::execute: No process matching {:?} started", glob.glob());
+            break;
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn list() -> Vec<Process> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            let (name, parent) = parse_proc_stat(&stat)?;
+
+            // The name from `stat` is truncated, so we also check the invocation.
+            // For Wine, this will be the Windows path to the game executable.
+            let exe = std::fs::read(entry.path().join("cmdline")).ok().and_then(|raw| {
+                raw.split(|x| *x == 0)
+                    .next()
+                    .filter(|x| !x.is_empty())
+                    .map(|x| String::from_utf8_lossy(x).to_string())
+            });
+
+            Some(Process { pid, parent, name, exe })
+        })
+        .collect()
+}
+
+/// The format is `pid (name) state parent ...`, where the name may contain spaces and parentheses.
+/// Zombie processes are skipped since they're already done.
+#[cfg(any(test, target_os = "linux"))]
+fn parse_proc_stat(stat: &str) -> Option<(String, u32)> {
+    let start = stat.find('(')?;
+    let end = stat.rfind(')')?;
+    let name = stat.get(start + 1..end)?.to_string();
+
+    let mut fields = stat.get(end + 1..)?.split_whitespace();
+    let state = fields.next()?;
+    if state == "Z" {
+        return None;
+    }
+    let parent = fields.next()?.parse().ok()?;
+
+    Some((name, parent))
+}
+
+#[cfg(target_os = "windows")]
+fn list() -> Vec<Process> {
+    use winapi::um::{
+        handleapi::{CloseHandle, INVALID_HANDLE_VALUE},
+        tlhelp32::{CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS},
+    };
+
+    let mut processes = vec![];
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return processes;
+        }
+
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        if Process32FirstW(snapshot, &mut entry) != 0 {
+            loop {
+                let len = entry
+                    .szExeFile
+                    .iter()
+                    .position(|x| *x == 0)
+                    .unwrap_or(entry.szExeFile.len());
+                processes.push(Process {
+                    pid: entry.th32ProcessID,
+                    parent: entry.th32ParentProcessID,
+                    name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+                    exe: None,
+                });
+
+                if Process32NextW(snapshot, &mut entry) == 0 {
+                    break;
+                }
+            }
+        }
+
+        CloseHandle(snapshot);
+    }
+
+    processes
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn list() -> Vec<Process> {
+    let Ok(output) = Command::new("ps").args(["-axo", "pid=,ppid=,comm="]).output() else {
+        return vec![];
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ps_line)
+        .collect()
+}
+
+#[cfg(any(test, not(any(target_os = "linux", target_os = "windows"))))]
+fn parse_ps_line(line: &str) -> Option<Process> {
+    let (pid, rest) = line.trim().split_once(char::is_whitespace)?;
+    let rest = rest.trim_start();
+    let (parent, command) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let command = command.trim();
+
+    Some(Process {
+        pid: pid.parse().ok()?,
+        parent: parent.parse().ok()?,
+        name: file_name(command).to_string(),
+        exe: (!command.is_empty()).then(|| command.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::hash_set;
+
+    use super::*;
+
+    fn process(pid: u32, parent: u32) -> Process {
+        Process {
+            pid,
+            parent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_parse_proc_stat() {
+        assert_eq!(
+            Some(("Game (x64).exe".to_string(), 42)),
+            parse_proc_stat("1234 (Game (x64).exe) S 42 1234 1234 0 -1")
+        );
+        assert_eq!(None, parse_proc_stat("1234 (game) Z 42 1234 1234 0 -1"));
+        assert_eq!(None, parse_proc_stat("garbage"));
+    }
+
+    #[test]
+    fn can_parse_ps_line() {
+        assert_eq!(
+            Some(Process {
+                pid: 123,
+                parent: 1,
+                name: "Game".to_string(),
+                exe: Some("/Applications/Game.app/Contents/MacOS/Game".to_string()),
+            }),
+            parse_ps_line("  123     1 /Applications/Game.app/Contents/MacOS/Game")
+        );
+        assert_eq!(None, parse_ps_line("abc 1 foo"));
+    }
+
+    #[test]
+    fn can_match_process_by_pattern() {
+        let glob = globset::GlobBuilder::new("game*.exe")
+            .case_insensitive(true)
+            .build()
+            .unwrap()
+            .compile_matcher();
+
+        let wine = Process {
+            name: "GameWin64-Ship".to_string(),
+            exe: Some("C:\\Games\\Foo\\GameWin64-Shipping.exe".to_string()),
+            ..Default::default()
+        };
+        assert!(wine.matches(&glob));
+
+        let native = Process {
+            name: "GAME.EXE".to_string(),
+            ..Default::default()
+        };
+        assert!(native.matches(&glob));
+
+        let other = Process {
+            name: "steam".to_string(),
+            exe: Some("/usr/bin/steam".to_string()),
+            ..Default::default()
+        };
+        assert!(!other.matches(&glob));
+    }
+
+    #[test]
+    fn can_track_descendants() {
+        let mut tracked = hash_set![10];
+
+        // The launcher spawned the game and its helper, then exited.
+        let processes = vec![process(1, 0), process(11, 10), process(12, 11), process(20, 1)];
+        assert!(track_descendants(&mut tracked, &processes));
+        assert_eq!(hash_set![10, 11, 12], tracked);
+
+        // The game exited.
+        let processes = vec![process(1, 0), process(20, 1)];
+        assert!(!track_descendants(&mut tracked, &processes));
+    }
+}